@@ -0,0 +1,72 @@
+use crate::account::{amount, keep_nonzero, AccountClient, Balance, SignedClient, TradingFee};
+use crate::auth::{binance::send, Signer};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Account {
+    balances: Vec<AssetBalance>,
+}
+
+#[derive(Deserialize)]
+struct AssetBalance {
+    asset: String,
+    free: String,
+    locked: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SymbolFee {
+    symbol: String,
+    maker_commission: String, // fraction, 0.001 = 0.1%
+    taker_commission: String,
+}
+
+pub struct BinanceAccount {
+    client: SignedClient,
+}
+
+impl BinanceAccount {
+    pub fn new(api_url: String, signer: Box<dyn Signer>) -> Self {
+        Self {
+            client: SignedClient::new("Binance", api_url, signer),
+        }
+    }
+}
+
+#[async_trait]
+impl AccountClient for BinanceAccount {
+    fn name(&self) -> &str {
+        &self.client.name
+    }
+
+    async fn balances(&self) -> Result<Vec<Balance>, Box<dyn std::error::Error>> {
+        let request = self.client.get("/api/v3/account", "omitZeroBalances=true")?;
+        let account: Account = send(request).await?;
+        Ok(keep_nonzero(
+            account
+                .balances
+                .into_iter()
+                .map(|b| Balance {
+                    asset: b.asset,
+                    free: amount(&b.free),
+                    locked: amount(&b.locked),
+                })
+                .collect(),
+        ))
+    }
+
+    async fn trading_fees(&self) -> Result<Vec<TradingFee>, Box<dyn std::error::Error>> {
+        let request = self.client.get("/sapi/v1/asset/tradeFee", "")?;
+        let fees: Vec<SymbolFee> = send(request).await?;
+        Ok(fees
+            .into_iter()
+            .map(|f| TradingFee {
+                symbol: Some(f.symbol),
+                maker_fee: amount(&f.maker_commission) * 100.0,
+                taker_fee: amount(&f.taker_commission) * 100.0,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::account::{amount, keep_nonzero, AccountClient, Balance, SignedClient, TradingFee};
+use crate::auth::{bybit::send, Signer};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct List<T> {
+    list: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct Wallet {
+    coin: Vec<CoinBalance>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CoinBalance {
+    coin: String,
+    wallet_balance: String,
+    #[serde(default)]
+    locked: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FeeRate {
+    symbol: String,
+    maker_fee_rate: String, // fraction
+    taker_fee_rate: String,
+}
+
+pub struct BybitAccount {
+    client: SignedClient,
+}
+
+impl BybitAccount {
+    pub fn new(api_url: String, signer: Box<dyn Signer>) -> Self {
+        Self {
+            client: SignedClient::new("Bybit", api_url, signer),
+        }
+    }
+}
+
+#[async_trait]
+impl AccountClient for BybitAccount {
+    fn name(&self) -> &str {
+        &self.client.name
+    }
+
+    // Spot balances live in the unified trading account.
+    async fn balances(&self) -> Result<Vec<Balance>, Box<dyn std::error::Error>> {
+        let request = self.client.get("/v5/account/wallet-balance", "accountType=UNIFIED")?;
+        let wallets: List<Wallet> = send(request).await?;
+        Ok(keep_nonzero(
+            wallets
+                .list
+                .into_iter()
+                .flat_map(|w| w.coin)
+                .map(|c| {
+                    let locked = amount(&c.locked);
+                    Balance {
+                        asset: c.coin,
+                        free: amount(&c.wallet_balance) - locked,
+                        locked,
+                    }
+                })
+                .collect(),
+        ))
+    }
+
+    async fn trading_fees(&self) -> Result<Vec<TradingFee>, Box<dyn std::error::Error>> {
+        let request = self.client.get("/v5/account/fee-rate", "category=spot")?;
+        let rates: List<FeeRate> = send(request).await?;
+        Ok(rates
+            .list
+            .into_iter()
+            .map(|r| TradingFee {
+                symbol: Some(r.symbol),
+                maker_fee: amount(&r.maker_fee_rate) * 100.0,
+                taker_fee: amount(&r.taker_fee_rate) * 100.0,
+            })
+            .collect())
+    }
+}
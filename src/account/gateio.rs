@@ -0,0 +1,61 @@
+use crate::account::{amount, keep_nonzero, AccountClient, Balance, SignedClient, TradingFee};
+use crate::auth::{gateio::send, Signer};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct SpotAccount {
+    currency: String,
+    available: String,
+    locked: String,
+}
+
+#[derive(Deserialize)]
+struct WalletFee {
+    maker_fee: String, // fraction
+    taker_fee: String,
+}
+
+pub struct GateIoAccount {
+    client: SignedClient,
+}
+
+impl GateIoAccount {
+    pub fn new(api_url: String, signer: Box<dyn Signer>) -> Self {
+        Self {
+            client: SignedClient::new("Gate.io", api_url, signer),
+        }
+    }
+}
+
+#[async_trait]
+impl AccountClient for GateIoAccount {
+    fn name(&self) -> &str {
+        &self.client.name
+    }
+
+    async fn balances(&self) -> Result<Vec<Balance>, Box<dyn std::error::Error>> {
+        let request = self.client.get("/api/v4/spot/accounts", "")?;
+        let accounts: Vec<SpotAccount> = send(request).await?;
+        Ok(keep_nonzero(
+            accounts
+                .into_iter()
+                .map(|a| Balance {
+                    asset: a.currency,
+                    free: amount(&a.available),
+                    locked: amount(&a.locked),
+                })
+                .collect(),
+        ))
+    }
+
+    async fn trading_fees(&self) -> Result<Vec<TradingFee>, Box<dyn std::error::Error>> {
+        let request = self.client.get("/api/v4/wallet/fee", "")?;
+        let fee: WalletFee = send(request).await?;
+        Ok(vec![TradingFee {
+            symbol: None,
+            maker_fee: amount(&fee.maker_fee) * 100.0,
+            taker_fee: amount(&fee.taker_fee) * 100.0,
+        }])
+    }
+}
@@ -0,0 +1,64 @@
+use crate::account::{amount, keep_nonzero, AccountClient, Balance, SignedClient, TradingFee};
+use crate::auth::{kucoin::send, Signer};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Account {
+    currency: String,
+    available: String,
+    holds: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BaseFee {
+    maker_fee_rate: String, // fraction
+    taker_fee_rate: String,
+}
+
+pub struct KucoinAccount {
+    client: SignedClient,
+}
+
+impl KucoinAccount {
+    pub fn new(api_url: String, signer: Box<dyn Signer>) -> Self {
+        Self {
+            client: SignedClient::new("Kucoin", api_url, signer),
+        }
+    }
+}
+
+#[async_trait]
+impl AccountClient for KucoinAccount {
+    fn name(&self) -> &str {
+        &self.client.name
+    }
+
+    async fn balances(&self) -> Result<Vec<Balance>, Box<dyn std::error::Error>> {
+        let request = self.client.get("/api/v1/accounts", "type=trade")?;
+        let accounts: Vec<Account> = send(request).await?;
+        Ok(keep_nonzero(
+            accounts
+                .into_iter()
+                .map(|a| Balance {
+                    asset: a.currency,
+                    free: amount(&a.available),
+                    locked: amount(&a.holds),
+                })
+                .collect(),
+        ))
+    }
+
+    // The base fee is the account's tier; per-symbol rates only differ by a
+    // market's fee class.
+    async fn trading_fees(&self) -> Result<Vec<TradingFee>, Box<dyn std::error::Error>> {
+        let request = self.client.get("/api/v1/base-fee", "currencyType=0")?;
+        let fee: BaseFee = send(request).await?;
+        Ok(vec![TradingFee {
+            symbol: None,
+            maker_fee: amount(&fee.maker_fee_rate) * 100.0,
+            taker_fee: amount(&fee.taker_fee_rate) * 100.0,
+        }])
+    }
+}
@@ -0,0 +1,93 @@
+use crate::auth::{self, Signer};
+use crate::config::Config;
+use crate::exchanges::{exchange_key, Exchange};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub mod binance;
+pub mod bybit;
+pub mod gateio;
+pub mod kucoin;
+
+/// Signed account queries on one venue.
+#[async_trait]
+pub trait AccountClient: Send + Sync {
+    fn name(&self) -> &str;
+    /// Non-zero balances of the spot/trading account.
+    async fn balances(&self) -> Result<Vec<Balance>, Box<dyn std::error::Error>>;
+    /// The account's fee tier, per symbol where the venue reports it that way.
+    async fn trading_fees(&self) -> Result<Vec<TradingFee>, Box<dyn std::error::Error>>;
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Balance {
+    pub asset: String,
+    pub free: f64,
+    pub locked: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TradingFee {
+    pub symbol: Option<String>, // None when the rate applies to every market
+    pub maker_fee: f64,         // percent, like exchanges.<key>.taker_fee
+    pub taker_fee: f64,
+}
+
+/// Account client for `exchange`; fails when the venue has no signed API
+/// support here or no keys are configured for it.
+pub fn build_account(config: &Config, exchange: &dyn Exchange) -> Result<Box<dyn AccountClient>, String> {
+    type Build = fn(String, Box<dyn Signer>) -> Box<dyn AccountClient>;
+    let key = exchange_key(exchange.name());
+    let (default_url, build): (&str, Build) = match key.as_str() {
+        "binance" => ("https://api.binance.com", |url, signer| Box::new(binance::BinanceAccount::new(url, signer))),
+        "bybit" => ("https://api.bybit.com", |url, signer| Box::new(bybit::BybitAccount::new(url, signer))),
+        "kucoin" => ("https://api.kucoin.com", |url, signer| Box::new(kucoin::KucoinAccount::new(url, signer))),
+        "gateio" => ("https://api.gateio.ws", |url, signer| Box::new(gateio::GateIoAccount::new(url, signer))),
+        _ => return Err(format!("Account queries are not supported on {}", exchange.name())),
+    };
+
+    let venue = config.exchange(&key);
+    let signer = auth::build_signer(&key, &venue, config.execution.recv_window_ms)
+        .ok_or_else(|| format!("No API credentials configured for {}", exchange.name()))?;
+    let api_url = venue
+        .api_url
+        .as_deref()
+        .unwrap_or(default_url)
+        .trim_end_matches('/')
+        .to_string();
+    Ok(build(api_url, signer))
+}
+
+// Venues report amounts as decimal strings.
+fn amount(value: &str) -> f64 {
+    value.parse().unwrap_or_default()
+}
+
+fn keep_nonzero(mut balances: Vec<Balance>) -> Vec<Balance> {
+    balances.retain(|b| b.free > 0.0 || b.locked > 0.0);
+    balances.sort_by(|a, b| a.asset.cmp(&b.asset));
+    balances
+}
+
+struct SignedClient {
+    name: String,
+    api_url: String,
+    signer: Box<dyn Signer>,
+    client: reqwest::Client,
+}
+
+impl SignedClient {
+    fn new(name: &str, api_url: String, signer: Box<dyn Signer>) -> Self {
+        Self {
+            name: name.to_string(),
+            api_url,
+            signer,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn get(&self, path: &str, query: &str) -> Result<reqwest::RequestBuilder, Box<dyn std::error::Error>> {
+        auth::signed_request(&self.client, self.signer.as_ref(), reqwest::Method::GET, &self.api_url, path, query, "")
+    }
+}
@@ -0,0 +1,414 @@
+use crate::config::{AlertConfig, TelegramConfig};
+use crate::models::TriangularArbitrageOpportunity;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+pub mod rules;
+pub mod sinks;
+
+use rules::{AlertChannel, AlertRule, RuleStore};
+use sinks::{BellSink, DiscordSink, SlackSink, TelegramSink, WebhookSink};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Alert {
+    pub threshold: f64,
+    pub fired_at: DateTime<Utc>,
+    pub opportunity: TriangularArbitrageOpportunity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_id: Option<String>, // set when fired by an alert rule rather than the global threshold
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rule_name: Option<String>,
+    #[serde(skip)]
+    pub printed: bool, // already printed to the console by the bell sink
+}
+
+impl Alert {
+    /// One-line human summary used by the chat-style sinks.
+    pub fn summary(&self) -> String {
+        let rule = self
+            .rule_name
+            .as_ref()
+            .map(|name| format!("[{}] ", name))
+            .unwrap_or_default();
+        format!(
+            "🚨 {}{} {} net {:.4}% (gross {:.4}%, fees {:.4}%) via {}",
+            rule,
+            self.opportunity.exchange,
+            self.opportunity.path,
+            self.opportunity.net_profit_percentage,
+            self.opportunity.gross_profit_percentage,
+            self.opportunity.estimated_fees,
+            self.opportunity.pairs,
+        )
+    }
+}
+
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    fn name(&self) -> &str;
+    async fn send(&self, alert: &Alert) -> Result<(), Box<dyn std::error::Error>>;
+}
+
+// Firings are kept only while they are cooling down, so the maps stay the
+// size of what alerted recently rather than of everything that ever did.
+#[derive(Default)]
+struct CooldownState {
+    cycles: HashMap<String, u64>, // exchange → scans seen
+    // Exchange → opportunity key → exchange cycle it last fired in.
+    last_fired: HashMap<String, HashMap<String, u64>>,
+    // Exchange → opportunity key → first time it was seen, for opportunities
+    // present in that exchange's latest scan.
+    first_seen: HashMap<String, HashMap<String, DateTime<Utc>>>,
+    // Rule id → exchange + opportunity key → last firing.
+    rule_fired: HashMap<String, HashMap<String, DateTime<Utc>>>,
+}
+
+/// Fires alerts for opportunities at or above the configured threshold. Each
+/// call to `process` is one cycle for that exchange; a path that fired stays
+/// quiet for `cooldown_cycles` cycles, and duplicates within a cycle fire once.
+///
+/// Rules from the `RuleStore` are evaluated in the same pass, each with its
+/// own filters, channel and cooldown (in seconds, since a rule can span
+/// exchanges scanned at different rates).
+pub struct AlertEngine {
+    client: reqwest::Client,
+    rules: RuleStore,
+    state: Mutex<CooldownState>,
+    console_output: bool,
+}
+
+impl AlertEngine {
+    pub fn new(rules: RuleStore) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(WEBHOOK_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            rules,
+            state: Mutex::new(CooldownState::default()),
+            console_output: true,
+        }
+    }
+
+    /// Keeps the bell sink from printing, for full-screen UIs that own the
+    /// terminal.
+    pub fn without_console_output(mut self) -> Self {
+        self.console_output = false;
+        self
+    }
+
+    pub fn rules(&self) -> &RuleStore {
+        &self.rules
+    }
+
+    /// Sinks are built from the config on every call so a reloaded config
+    /// takes effect on the next cycle.
+    pub fn sinks(&self, config: &AlertConfig) -> Vec<Box<dyn AlertSink>> {
+        let mut sinks: Vec<Box<dyn AlertSink>> = Vec::new();
+
+        if config.enable_sound {
+            sinks.push(Box::new(BellSink::new(self.console_output)));
+        }
+        if let Some(url) = &config.webhook_url {
+            sinks.push(Box::new(WebhookSink::new(self.client.clone(), url)));
+        }
+        if let Some(url) = &config.discord_webhook_url {
+            sinks.push(Box::new(DiscordSink::new(self.client.clone(), url)));
+        }
+        if let Some(url) = &config.slack_webhook_url {
+            sinks.push(Box::new(SlackSink::new(self.client.clone(), url)));
+        }
+        if let Some(telegram) = &config.telegram {
+            sinks.push(Box::new(TelegramSink::new(self.client.clone(), telegram)));
+        }
+
+        sinks
+    }
+
+    pub fn channel_sink(&self, channel: &AlertChannel) -> Box<dyn AlertSink> {
+        match channel {
+            AlertChannel::Bell => Box::new(BellSink::new(self.console_output)),
+            AlertChannel::Webhook { url } => Box::new(WebhookSink::new(self.client.clone(), url)),
+            AlertChannel::Discord { url } => Box::new(DiscordSink::new(self.client.clone(), url)),
+            AlertChannel::Slack { url } => Box::new(SlackSink::new(self.client.clone(), url)),
+            AlertChannel::Telegram {
+                bot_token,
+                chat_id,
+                api_url,
+            } => Box::new(TelegramSink::new(
+                self.client.clone(),
+                &TelegramConfig {
+                    bot_token: bot_token.clone(),
+                    chat_id: chat_id.clone(),
+                    api_url: api_url.clone().unwrap_or_else(|| TelegramConfig::DEFAULT_API_URL.to_string()),
+                },
+            )),
+        }
+    }
+
+    /// Picks the opportunities that should alert this cycle and records them
+    /// as fired.
+    pub fn select(
+        &self,
+        config: &AlertConfig,
+        exchange: &str,
+        opportunities: &[TriangularArbitrageOpportunity],
+    ) -> Vec<Alert> {
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        let cycle = {
+            let cycle = state.cycles.entry(exchange.to_string()).or_insert(0);
+            *cycle += 1;
+            *cycle
+        };
+
+        let now = Utc::now();
+        let previous = state.first_seen.remove(exchange).unwrap_or_default();
+        let seen: HashMap<String, DateTime<Utc>> = opportunities
+            .iter()
+            .map(|o| {
+                let key = format!("{}|{}", o.path, o.pairs);
+                let first_seen = previous.get(&key).copied().unwrap_or(now);
+                (key, first_seen)
+            })
+            .collect();
+        state.first_seen.insert(exchange.to_string(), seen);
+
+        let last_fired = state.last_fired.entry(exchange.to_string()).or_default();
+        last_fired.retain(|_, fired| cycle - *fired <= config.cooldown_cycles);
+
+        if config.high_profit_threshold <= 0.0 {
+            return Vec::new();
+        }
+
+        let mut alerts = Vec::new();
+        for opportunity in opportunities {
+            if opportunity.net_profit_percentage < config.high_profit_threshold {
+                continue;
+            }
+
+            let key = format!("{}|{}", opportunity.path, opportunity.pairs);
+            if last_fired.contains_key(&key) {
+                continue;
+            }
+
+            last_fired.insert(key, cycle);
+            alerts.push(Alert {
+                threshold: config.high_profit_threshold,
+                fired_at: now,
+                opportunity: opportunity.clone(),
+                rule_id: None,
+                rule_name: None,
+                printed: false,
+            });
+        }
+
+        alerts
+    }
+
+    /// Picks the rule alerts for this cycle. Call after `select` for the same
+    /// cycle, which records when each opportunity was first seen.
+    pub fn select_rules(
+        &self,
+        rules: &[AlertRule],
+        exchange: &str,
+        opportunities: &[TriangularArbitrageOpportunity],
+    ) -> Vec<(AlertRule, Alert)> {
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        let now = Utc::now();
+        let mut alerts = Vec::new();
+
+        // Deleted rules take their firings with them.
+        state.rule_fired.retain(|id, fired| match rules.iter().find(|r| &r.id == id) {
+            Some(rule) => {
+                fired.retain(|_, at| (now - *at).num_seconds() < rule.rule.cooldown_secs as i64);
+                !fired.is_empty()
+            }
+            None => false,
+        });
+
+        for rule in rules.iter().filter(|r| r.rule.enabled) {
+            for opportunity in opportunities.iter().filter(|o| rule.matches(o)) {
+                let key = format!("{}|{}", opportunity.path, opportunity.pairs);
+
+                let first_seen = state
+                    .first_seen
+                    .get(exchange)
+                    .and_then(|seen| seen.get(&key))
+                    .copied()
+                    .unwrap_or(now);
+                if (now - first_seen).num_seconds() < rule.rule.min_persistence_secs as i64 {
+                    continue;
+                }
+
+                let fired = state.rule_fired.entry(rule.id.clone()).or_default();
+                let fired_key = format!("{}|{}", exchange, key);
+                if fired.contains_key(&fired_key) {
+                    continue;
+                }
+
+                fired.insert(fired_key, now);
+                alerts.push((
+                    rule.clone(),
+                    Alert {
+                        threshold: rule.rule.min_net_profit,
+                        fired_at: now,
+                        opportunity: opportunity.clone(),
+                        rule_id: Some(rule.id.clone()),
+                        rule_name: Some(rule.rule.name.clone()),
+                        printed: false,
+                    },
+                ));
+            }
+        }
+
+        alerts
+    }
+
+    /// Selects this cycle's alerts and delivers them: threshold alerts to
+    /// every configured sink, rule alerts to the rule's channel. The bell
+    /// rings before this returns; network sinks are sent from a spawned task,
+    /// so a slow webhook or chat API never holds up the scan. Delivery
+    /// failures are logged, never returned.
+    pub async fn process(
+        &self,
+        config: &AlertConfig,
+        exchange: &str,
+        opportunities: &[TriangularArbitrageOpportunity],
+    ) -> Vec<Alert> {
+        let mut alerts = self.select(config, exchange, opportunities);
+        let rule_alerts = self.select_rules(&self.rules.list(), exchange, opportunities);
+
+        for alert in &mut alerts {
+            self.deliver(self.sinks(config), alert).await;
+        }
+
+        for (rule, mut alert) in rule_alerts {
+            self.deliver(vec![self.channel_sink(&rule.rule.channel)], &mut alert).await;
+            alerts.push(alert);
+        }
+
+        alerts
+    }
+
+    async fn deliver(&self, sinks: Vec<Box<dyn AlertSink>>, alert: &mut Alert) {
+        let (local, remote): (Vec<_>, Vec<_>) = sinks.into_iter().partition(|s| s.name() == "bell");
+        for sink in &local {
+            send_logged(sink.as_ref(), alert).await;
+        }
+        alert.printed |= self.console_output && !local.is_empty();
+
+        if !remote.is_empty() {
+            let alert = alert.clone();
+            tokio::spawn(async move {
+                for sink in &remote {
+                    send_logged(sink.as_ref(), &alert).await;
+                }
+            });
+        }
+    }
+}
+
+async fn send_logged(sink: &dyn AlertSink, alert: &Alert) {
+    if let Err(e) = sink.send(alert).await {
+        log::error!("{} alert delivery failed: {}", sink.name(), e);
+    }
+}
+
+pub fn print_alert(alert: &Alert) {
+    println!("{}", alert.summary().bright_red().bold());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::opportunity;
+    use rules::AlertRuleInput;
+
+    fn engine() -> AlertEngine {
+        AlertEngine::new(RuleStore::empty("unused.json"))
+    }
+
+    fn rule(id: &str, cooldown_secs: u64) -> AlertRule {
+        AlertRule {
+            id: id.to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            rule: AlertRuleInput {
+                name: id.to_string(),
+                enabled: true,
+                exchange: None,
+                anchor_currency: None,
+                assets: Vec::new(),
+                min_net_profit: 0.5,
+                min_notional: 0.0,
+                min_persistence_secs: 0,
+                channel: AlertChannel::Bell,
+                cooldown_secs,
+            },
+        }
+    }
+
+    fn paths(alerts: &[Alert]) -> Vec<&str> {
+        alerts.iter().map(|a| a.opportunity.path.as_str()).collect()
+    }
+
+    #[test]
+    fn a_path_stays_quiet_for_the_cooldown_and_is_then_forgotten() {
+        let engine = engine();
+        let config = AlertConfig {
+            cooldown_cycles: 2,
+            ..AlertConfig::default()
+        };
+        let found = [opportunity("Binance", "A", 1.5), opportunity("Binance", "B", 0.5)];
+
+        assert_eq!(paths(&engine.select(&config, "Binance", &found)), ["A"]);
+        assert!(engine.select(&config, "Binance", &found).is_empty());
+        // Another exchange counts its own cycles.
+        assert_eq!(paths(&engine.select(&config, "Kraken", &[opportunity("Kraken", "A", 1.5)])), ["A"]);
+        assert!(engine.select(&config, "Binance", &[]).is_empty());
+        assert_eq!(engine.state.lock().unwrap().last_fired["Binance"].len(), 1);
+
+        // Cycle 4: the two quiet cycles are over, so the old entry is gone
+        // and the path alerts again.
+        assert_eq!(paths(&engine.select(&config, "Binance", &found)), ["A"]);
+        assert_eq!(engine.state.lock().unwrap().last_fired["Binance"]["A|"], 4);
+        for _ in 0..3 {
+            engine.select(&config, "Binance", &[]);
+        }
+        assert!(engine.state.lock().unwrap().last_fired["Binance"].is_empty());
+    }
+
+    #[test]
+    fn rule_firings_expire_with_the_cooldown_or_the_rule() {
+        let engine = engine();
+        let found = [opportunity("Binance", "A", 1.0), opportunity("Binance", "B", 0.2)];
+        let rules = [rule("quick", 0), rule("slow", 3600)];
+
+        let fired: Vec<(String, String)> = engine
+            .select_rules(&rules, "Binance", &found)
+            .into_iter()
+            .map(|(rule, alert)| (rule.id, alert.opportunity.path))
+            .collect();
+        assert_eq!(fired, [("quick".to_string(), "A".to_string()), ("slow".to_string(), "A".to_string())]);
+
+        // Without a cooldown the rule fires every cycle; the other waits.
+        let again = engine.select_rules(&rules, "Binance", &found);
+        assert_eq!(again.iter().map(|(rule, _)| rule.id.as_str()).collect::<Vec<_>>(), ["quick"]);
+
+        engine.select_rules(&rules, "Binance", &[]);
+        let kept: Vec<String> = engine.state.lock().unwrap().rule_fired.keys().cloned().collect();
+        assert_eq!(kept, ["slow"]);
+
+        engine.select_rules(&rules[..1], "Binance", &[]);
+        assert!(engine.state.lock().unwrap().rule_fired.is_empty());
+    }
+}
@@ -0,0 +1,334 @@
+use crate::exchanges::exchange_key;
+use crate::models::TriangularArbitrageOpportunity;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Stands in for channel secrets in API responses; sent back unchanged in a
+// `PUT`, it keeps the stored value.
+const REDACTED: &str = "<redacted>";
+
+/// Where a rule's alerts are delivered. Webhook URLs carry their own
+/// credentials, so they are secrets just like the Telegram bot token.
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum AlertChannel {
+    Bell,
+    Webhook {
+        url: String,
+    },
+    Discord {
+        url: String,
+    },
+    Slack {
+        url: String,
+    },
+    Telegram {
+        bot_token: String,
+        chat_id: String,
+        #[serde(default)]
+        api_url: Option<String>,
+    },
+}
+
+/// The user-editable part of a rule, as accepted by `POST`/`PUT /api/v1/alerts`.
+/// Every filter is optional; an opportunity must pass all of them.
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AlertRuleInput {
+    pub name: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub exchange: Option<String>, // exchange name or config key, e.g. "Gate.io" or "gateio"
+    #[serde(default)]
+    pub anchor_currency: Option<String>, // first currency of the path
+    #[serde(default)]
+    pub assets: Vec<String>, // all must appear in the path
+    #[serde(default)]
+    pub min_net_profit: f64, // percent
+    #[serde(default)]
+    pub min_notional: f64, // in anchor currency
+    #[serde(default)]
+    pub min_persistence_secs: u64, // how long the opportunity must have been seen
+    pub channel: AlertChannel,
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64, // per opportunity
+}
+
+impl AlertChannel {
+    /// This channel with its secrets replaced, for API responses. The rules
+    /// file keeps the real values.
+    pub fn redacted(&self) -> Self {
+        let mut channel = self.clone();
+        match &mut channel {
+            AlertChannel::Bell => {}
+            AlertChannel::Webhook { url } | AlertChannel::Discord { url } | AlertChannel::Slack { url } => {
+                *url = REDACTED.to_string();
+            }
+            AlertChannel::Telegram { bot_token, .. } => *bot_token = REDACTED.to_string(),
+        }
+        channel
+    }
+
+    /// Takes `previous`'s secret wherever this channel still holds the
+    /// redacted stand-in, so a rule read from the API can be edited and
+    /// sent back as is.
+    pub fn keep_secrets(&mut self, previous: &AlertChannel) {
+        match (self, previous) {
+            (AlertChannel::Webhook { url }, AlertChannel::Webhook { url: previous })
+            | (AlertChannel::Discord { url }, AlertChannel::Discord { url: previous })
+            | (AlertChannel::Slack { url }, AlertChannel::Slack { url: previous })
+                if url == REDACTED =>
+            {
+                *url = previous.clone();
+            }
+            (AlertChannel::Telegram { bot_token, .. }, AlertChannel::Telegram { bot_token: previous, .. })
+                if bot_token == REDACTED =>
+            {
+                *bot_token = previous.clone();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_cooldown_secs() -> u64 {
+    300
+}
+
+#[derive(Clone, Serialize, Deserialize, ToSchema)]
+pub struct AlertRule {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub rule: AlertRuleInput,
+}
+
+impl AlertRuleInput {
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut errors = Vec::new();
+
+        if self.name.trim().is_empty() {
+            errors.push("name must not be empty".to_string());
+        }
+        if !self.min_net_profit.is_finite() {
+            errors.push("min_net_profit must be a number".to_string());
+        }
+        if !self.min_notional.is_finite() || self.min_notional < 0.0 {
+            errors.push("min_notional must not be negative".to_string());
+        }
+        if self.assets.iter().any(|a| a.trim().is_empty()) {
+            errors.push("assets must not contain empty entries".to_string());
+        }
+
+        let urls = match &self.channel {
+            AlertChannel::Bell => vec![],
+            AlertChannel::Webhook { url } | AlertChannel::Discord { url } | AlertChannel::Slack { url } => {
+                vec![Some(url)]
+            }
+            AlertChannel::Telegram {
+                bot_token,
+                chat_id,
+                api_url,
+            } => {
+                if bot_token.trim().is_empty() || chat_id.trim().is_empty() {
+                    errors.push("channel.bot_token and channel.chat_id must not be empty".to_string());
+                }
+                vec![api_url.as_ref()]
+            }
+        };
+        for url in urls.into_iter().flatten() {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                errors.push("channel URLs must start with http:// or https://".to_string());
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; ").into())
+        }
+    }
+}
+
+impl AlertRule {
+    pub fn redacted(&self) -> Self {
+        let mut rule = self.clone();
+        rule.rule.channel = self.rule.channel.redacted();
+        rule
+    }
+
+    /// Checks the static filters; persistence is checked by the engine, which
+    /// knows when the opportunity was first seen.
+    pub fn matches(&self, opportunity: &TriangularArbitrageOpportunity) -> bool {
+        let rule = &self.rule;
+        let currencies: Vec<&str> = opportunity.path.split(" → ").map(str::trim).collect();
+
+        if let Some(exchange) = &rule.exchange {
+            if exchange_key(exchange) != exchange_key(&opportunity.exchange) {
+                return false;
+            }
+        }
+        if let Some(anchor) = &rule.anchor_currency {
+            if !currencies.first().is_some_and(|c| c.eq_ignore_ascii_case(anchor)) {
+                return false;
+            }
+        }
+        if !rule
+            .assets
+            .iter()
+            .all(|asset| currencies.iter().any(|c| c.eq_ignore_ascii_case(asset.trim())))
+        {
+            return false;
+        }
+
+        opportunity.net_profit_percentage >= rule.min_net_profit && opportunity.max_notional >= rule.min_notional
+    }
+}
+
+/// Alert rules kept in memory and written through to a JSON file on every
+/// change. A change that cannot be written is not applied.
+pub struct RuleStore {
+    path: PathBuf,
+    rules: Mutex<Vec<AlertRule>>,
+}
+
+impl RuleStore {
+    /// Loads the rules at `path`; a missing file is an empty rule set.
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.into();
+        let rules = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e).into()),
+        };
+
+        Ok(Self {
+            path,
+            rules: Mutex::new(rules),
+        })
+    }
+
+    /// An empty store at `path`, for when the existing file is unreadable.
+    pub fn empty(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            rules: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn list(&self) -> Vec<AlertRule> {
+        self.rules.lock().unwrap().clone()
+    }
+
+    pub fn get(&self, id: &str) -> Option<AlertRule> {
+        self.rules.lock().unwrap().iter().find(|r| r.id == id).cloned()
+    }
+
+    pub fn create(&self, input: AlertRuleInput) -> Result<AlertRule, Box<dyn std::error::Error>> {
+        input.validate()?;
+
+        let now = Utc::now();
+        let rule = AlertRule {
+            id: Uuid::new_v4().to_string(),
+            created_at: now,
+            updated_at: now,
+            rule: input,
+        };
+
+        let mut rules = self.rules.lock().unwrap();
+        let mut updated = rules.clone();
+        updated.push(rule.clone());
+        self.save(&updated)?;
+        *rules = updated;
+        Ok(rule)
+    }
+
+    /// Replaces the rule with `id`; `Ok(None)` when it does not exist.
+    pub fn update(&self, id: &str, input: AlertRuleInput) -> Result<Option<AlertRule>, Box<dyn std::error::Error>> {
+        input.validate()?;
+
+        let mut rules = self.rules.lock().unwrap();
+        let mut updated = rules.clone();
+        let Some(rule) = updated.iter_mut().find(|r| r.id == id) else {
+            return Ok(None);
+        };
+        rule.rule = input;
+        rule.updated_at = Utc::now();
+        let rule = rule.clone();
+
+        self.save(&updated)?;
+        *rules = updated;
+        Ok(Some(rule))
+    }
+
+    /// Removes the rule with `id`; `Ok(false)` when it does not exist.
+    pub fn delete(&self, id: &str) -> Result<bool, Box<dyn std::error::Error>> {
+        let mut rules = self.rules.lock().unwrap();
+        let updated: Vec<AlertRule> = rules.iter().filter(|r| r.id != id).cloned().collect();
+        if updated.len() == rules.len() {
+            return Ok(false);
+        }
+
+        self.save(&updated)?;
+        *rules = updated;
+        Ok(true)
+    }
+
+    // Write to a sibling temp file and rename so a crash never leaves a
+    // truncated rule file behind.
+    fn save(&self, rules: &[AlertRule]) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, serde_json::to_string_pretty(rules)?)?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn telegram(bot_token: &str) -> AlertChannel {
+        AlertChannel::Telegram {
+            bot_token: bot_token.to_string(),
+            chat_id: "42".to_string(),
+            api_url: None,
+        }
+    }
+
+    #[test]
+    fn secrets_are_redacted_and_kept_on_round_trip() {
+        let stored = telegram("123:secret");
+        let shown = serde_json::to_string(&stored.redacted()).unwrap();
+        assert!(!shown.contains("secret") && shown.contains("\"chat_id\":\"42\""), "{}", shown);
+
+        let mut sent_back = stored.redacted();
+        sent_back.keep_secrets(&stored);
+        assert!(matches!(sent_back, AlertChannel::Telegram { bot_token, .. } if bot_token == "123:secret"));
+
+        // A new secret, or a different kind of channel, replaces the old one.
+        let mut replaced = telegram("456:other");
+        replaced.keep_secrets(&stored);
+        assert!(matches!(replaced, AlertChannel::Telegram { bot_token, .. } if bot_token == "456:other"));
+        let mut switched = AlertChannel::Slack {
+            url: REDACTED.to_string(),
+        };
+        switched.keep_secrets(&stored);
+        assert!(matches!(switched, AlertChannel::Slack { url } if url == REDACTED));
+    }
+}
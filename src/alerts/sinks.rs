@@ -0,0 +1,273 @@
+use crate::alerts::{print_alert, Alert, AlertSink};
+use crate::config::TelegramConfig;
+use async_trait::async_trait;
+use std::io::Write;
+
+/// Rings the terminal bell and, unless the screen belongs to a full-screen
+/// UI, prints the alert.
+pub struct BellSink {
+    print: bool,
+}
+
+impl BellSink {
+    pub fn new(print: bool) -> Self {
+        Self { print }
+    }
+}
+
+#[async_trait]
+impl AlertSink for BellSink {
+    fn name(&self) -> &str {
+        "bell"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), Box<dyn std::error::Error>> {
+        print!("\x07");
+        if self.print {
+            print_alert(alert);
+        }
+        std::io::stdout().flush()?;
+        Ok(())
+    }
+}
+
+async fn post_json(
+    client: &reqwest::Client,
+    url: &str,
+    body: &serde_json::Value,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // The URL holds the webhook's or bot's credentials; keep it out of the
+    // error, which gets logged.
+    let response = client
+        .post(url)
+        .json(body)
+        .send()
+        .await
+        .map_err(reqwest::Error::without_url)?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP Error: {}", response.status()).into());
+    }
+
+    Ok(())
+}
+
+/// Posts the full `Alert` as JSON.
+pub struct WebhookSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookSink {
+    pub fn new(client: reqwest::Client, url: &str) -> Self {
+        Self {
+            client,
+            url: url.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl AlertSink for WebhookSink {
+    fn name(&self) -> &str {
+        "webhook"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), Box<dyn std::error::Error>> {
+        post_json(&self.client, &self.url, &serde_json::to_value(alert)?).await
+    }
+}
+
+/// Discord incoming webhook (`{"content": ...}`).
+pub struct DiscordSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl DiscordSink {
+    pub fn new(client: reqwest::Client, url: &str) -> Self {
+        Self {
+            client,
+            url: url.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl AlertSink for DiscordSink {
+    fn name(&self) -> &str {
+        "discord"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), Box<dyn std::error::Error>> {
+        let body = serde_json::json!({ "content": alert.summary() });
+        post_json(&self.client, &self.url, &body).await
+    }
+}
+
+/// Slack incoming webhook (`{"text": ...}`).
+pub struct SlackSink {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl SlackSink {
+    pub fn new(client: reqwest::Client, url: &str) -> Self {
+        Self {
+            client,
+            url: url.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl AlertSink for SlackSink {
+    fn name(&self) -> &str {
+        "slack"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), Box<dyn std::error::Error>> {
+        let body = serde_json::json!({ "text": alert.summary() });
+        post_json(&self.client, &self.url, &body).await
+    }
+}
+
+/// Telegram Bot API `sendMessage`. `api_url` can point at a local stand-in.
+pub struct TelegramSink {
+    client: reqwest::Client,
+    url: String,
+    chat_id: String,
+}
+
+impl TelegramSink {
+    pub fn new(client: reqwest::Client, config: &TelegramConfig) -> Self {
+        Self {
+            client,
+            url: format!(
+                "{}/bot{}/sendMessage",
+                config.api_url.trim_end_matches('/'),
+                config.bot_token
+            ),
+            chat_id: config.chat_id.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl AlertSink for TelegramSink {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    async fn send(&self, alert: &Alert) -> Result<(), Box<dyn std::error::Error>> {
+        let body = serde_json::json!({
+            "chat_id": self.chat_id,
+            "text": alert.summary(),
+            "disable_web_page_preview": true,
+        });
+        // Errors from reqwest include the URL, which embeds the bot token.
+        post_json(&self.client, &self.url, &body)
+            .await
+            .map_err(|e| e.to_string().replace(&self.url, "<telegram sendMessage>").into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TriangularArbitrageOpportunity;
+    use crate::test_support::{json_body, opportunity, serve_once, serve_once_with_status};
+    use chrono::Utc;
+
+    fn alert() -> Alert {
+        Alert {
+            threshold: 0.5,
+            fired_at: Utc::now(),
+            opportunity: TriangularArbitrageOpportunity {
+                pairs: "BTCUSDT, ETHBTC, ETHUSDT".to_string(),
+                ..opportunity("Binance", "USDT → BTC → ETH → USDT", 0.6)
+            },
+            rule_id: None,
+            rule_name: Some("big".to_string()),
+            printed: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn webhook_posts_the_full_alert() {
+        let (url, server) = serve_once("");
+        WebhookSink::new(reqwest::Client::new(), &format!("{}/hook", url))
+            .send(&alert())
+            .await
+            .unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /hook "));
+        let body = json_body(&request);
+        assert_eq!(body["threshold"], 0.5);
+        assert_eq!(body["rule_name"], "big");
+        assert_eq!(body["opportunity"]["path"], "USDT → BTC → ETH → USDT");
+    }
+
+    #[tokio::test]
+    async fn discord_and_slack_post_the_summary() {
+        let summary = alert().summary();
+
+        let (url, server) = serve_once("");
+        DiscordSink::new(reqwest::Client::new(), &format!("{}/api/webhooks/1/abc", url))
+            .send(&alert())
+            .await
+            .unwrap();
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /api/webhooks/1/abc "));
+        assert_eq!(json_body(&request), serde_json::json!({ "content": summary }));
+
+        let (url, server) = serve_once("ok");
+        SlackSink::new(reqwest::Client::new(), &format!("{}/services/T/B/x", url))
+            .send(&alert())
+            .await
+            .unwrap();
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /services/T/B/x "));
+        assert_eq!(json_body(&request), serde_json::json!({ "text": summary }));
+    }
+
+    #[tokio::test]
+    async fn telegram_sends_to_the_chat() {
+        let (url, server) = serve_once(r#"{"ok":true}"#);
+        let config = TelegramConfig {
+            bot_token: "123:abc".to_string(),
+            chat_id: "-100".to_string(),
+            api_url: format!("{}/", url),
+        };
+        TelegramSink::new(reqwest::Client::new(), &config)
+            .send(&alert())
+            .await
+            .unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /bot123:abc/sendMessage "));
+        let body = json_body(&request);
+        assert_eq!(body["chat_id"], "-100");
+        assert_eq!(body["text"], alert().summary());
+        assert_eq!(body["disable_web_page_preview"], true);
+    }
+
+    #[tokio::test]
+    async fn non_success_status_is_an_error() {
+        let (url, server) = serve_once_with_status("404 Not Found", "");
+        let config = TelegramConfig {
+            bot_token: "123:abc".to_string(),
+            chat_id: "-100".to_string(),
+            api_url: url,
+        };
+        let error = TelegramSink::new(reqwest::Client::new(), &config)
+            .send(&alert())
+            .await
+            .unwrap_err()
+            .to_string();
+        server.join().unwrap();
+
+        assert_eq!(error, "HTTP Error: 404 Not Found");
+    }
+}
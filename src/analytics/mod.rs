@@ -0,0 +1,332 @@
+use crate::scanner::ScanOutcome;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use utoipa::ToSchema;
+
+const TOP_CURRENCIES: usize = 5;
+const DEFAULT_WINDOW_MINUTES: i64 = 15;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AnalyticsSnapshot {
+    pub window_secs: i64,
+    pub generated_at: DateTime<Utc>,
+    pub exchanges: Vec<ExchangeAnalytics>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExchangeAnalytics {
+    pub exchange: String,
+    pub scans: usize,
+    pub fetch_success_rate: f64, // 0.0 - 1.0
+    pub opportunities_per_minute: f64, // distinct opportunities, however many scans each lasted
+    pub mean_net_profit: Option<f64>,
+    pub p95_net_profit: Option<f64>,
+    pub mean_lifetime_secs: Option<f64>,
+    pub top_currencies: Vec<CurrencyCount>,
+    pub last_cycle: Option<CycleAnalytics>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CurrencyCount {
+    pub currency: String,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CycleAnalytics {
+    pub timestamp: DateTime<Utc>,
+    pub success: bool,
+    pub opportunity_count: usize,
+    pub mean_net_profit: Option<f64>,
+    pub best_net_profit: Option<f64>,
+}
+
+struct ScanSample {
+    at: DateTime<Utc>,
+    success: bool,
+    net_profits: Vec<f64>,
+}
+
+// One opportunity from the scan it first appeared in until the first scan
+// without it. Rates and profit stats count episodes, not sightings, so they
+// don't depend on how often the exchange is scanned.
+struct Episode {
+    first_seen: DateTime<Utc>,
+    best_net_profit: f64,
+    currencies: Vec<String>,
+}
+
+#[derive(Default)]
+struct ExchangeState {
+    samples: VecDeque<ScanSample>,
+    // Opportunity key → its episode, for opportunities present in the latest
+    // scan.
+    open: HashMap<String, Episode>,
+    // (closed at, episode) of opportunities that have since disappeared.
+    closed: VecDeque<(DateTime<Utc>, Episode)>,
+}
+
+/// Rolling per-exchange statistics over the last `window` of scans.
+pub struct Analytics {
+    window: Duration,
+    state: Mutex<HashMap<String, ExchangeState>>,
+}
+
+impl Default for Analytics {
+    fn default() -> Self {
+        Self::new(Duration::minutes(DEFAULT_WINDOW_MINUTES))
+    }
+}
+
+impl Analytics {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            state: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn record(&self, outcome: &ScanOutcome) {
+        self.record_at(outcome, Utc::now());
+    }
+
+    fn record_at(&self, outcome: &ScanOutcome, now: DateTime<Utc>) {
+        let mut state = self.state.lock().unwrap();
+        let exchange = state.entry(outcome.exchange.clone()).or_default();
+
+        exchange.samples.push_back(ScanSample {
+            at: now,
+            success: outcome.error.is_none(),
+            net_profits: outcome.opportunities.iter().map(|o| o.net_profit_percentage).collect(),
+        });
+
+        // A failed fetch says nothing about whether opportunities persisted.
+        if outcome.error.is_none() {
+            let mut still_open = HashMap::new();
+            for opportunity in &outcome.opportunities {
+                let key = format!("{}|{}", opportunity.path, opportunity.pairs);
+                let mut episode = exchange.open.remove(&key).unwrap_or_else(|| Episode {
+                    first_seen: now,
+                    best_net_profit: opportunity.net_profit_percentage,
+                    currencies: path_currencies(&opportunity.path),
+                });
+                episode.best_net_profit = episode.best_net_profit.max(opportunity.net_profit_percentage);
+                still_open.insert(key, episode);
+            }
+            for (_, episode) in exchange.open.drain() {
+                exchange.closed.push_back((now, episode));
+            }
+            exchange.open = still_open;
+        }
+
+        let cutoff = now - self.window;
+        while exchange.samples.front().is_some_and(|s| s.at < cutoff) {
+            exchange.samples.pop_front();
+        }
+        while exchange.closed.front().is_some_and(|(at, _)| *at < cutoff) {
+            exchange.closed.pop_front();
+        }
+    }
+
+    pub fn snapshot(&self) -> AnalyticsSnapshot {
+        self.snapshot_at(Utc::now())
+    }
+
+    fn snapshot_at(&self, now: DateTime<Utc>) -> AnalyticsSnapshot {
+        let state = self.state.lock().unwrap();
+
+        let mut exchanges: Vec<ExchangeAnalytics> = state
+            .iter()
+            .map(|(name, exchange)| summarize(name, exchange, now))
+            .collect();
+        exchanges.sort_by(|a, b| a.exchange.cmp(&b.exchange));
+
+        AnalyticsSnapshot {
+            window_secs: self.window.num_seconds(),
+            generated_at: now,
+            exchanges,
+        }
+    }
+}
+
+fn summarize(name: &str, exchange: &ExchangeState, now: DateTime<Utc>) -> ExchangeAnalytics {
+    let scans = exchange.samples.len();
+    let successes = exchange.samples.iter().filter(|s| s.success).count();
+
+    // Episodes that ended within the window plus those still open, the
+    // latter aged up to now.
+    let episodes: Vec<(&Episode, Duration)> = exchange
+        .closed
+        .iter()
+        .map(|(closed_at, episode)| (episode, *closed_at - episode.first_seen))
+        .chain(exchange.open.values().map(|episode| (episode, now - episode.first_seen)))
+        .collect();
+
+    let mut net_profits: Vec<f64> = episodes.iter().map(|(episode, _)| episode.best_net_profit).collect();
+    net_profits.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let elapsed_minutes = exchange
+        .samples
+        .front()
+        .map(|first| (now - first.at).num_milliseconds() as f64 / 60_000.0)
+        .unwrap_or(0.0)
+        .max(1.0 / 60.0);
+
+    let lifetimes: Vec<f64> = episodes
+        .iter()
+        .map(|(_, lifetime)| lifetime.num_milliseconds() as f64 / 1000.0)
+        .collect();
+
+    let mut currency_counts: HashMap<&str, usize> = HashMap::new();
+    for currency in episodes.iter().flat_map(|(episode, _)| episode.currencies.iter()) {
+        *currency_counts.entry(currency.as_str()).or_default() += 1;
+    }
+    let mut top_currencies: Vec<CurrencyCount> = currency_counts
+        .into_iter()
+        .map(|(currency, count)| CurrencyCount {
+            currency: currency.to_string(),
+            count,
+        })
+        .collect();
+    top_currencies.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.currency.cmp(&b.currency)));
+    top_currencies.truncate(TOP_CURRENCIES);
+
+    ExchangeAnalytics {
+        exchange: name.to_string(),
+        scans,
+        fetch_success_rate: if scans == 0 { 0.0 } else { successes as f64 / scans as f64 },
+        opportunities_per_minute: episodes.len() as f64 / elapsed_minutes,
+        mean_net_profit: mean(&net_profits),
+        p95_net_profit: percentile(&net_profits, 0.95),
+        mean_lifetime_secs: mean(&lifetimes),
+        top_currencies,
+        last_cycle: exchange.samples.back().map(|sample| CycleAnalytics {
+            timestamp: sample.at,
+            success: sample.success,
+            opportunity_count: sample.net_profits.len(),
+            mean_net_profit: mean(&sample.net_profits),
+            best_net_profit: sample.net_profits.iter().copied().reduce(f64::max),
+        }),
+    }
+}
+
+// Distinct currencies of a "A → B → C → A" path.
+fn path_currencies(path: &str) -> Vec<String> {
+    let mut currencies: Vec<String> = path.split(" → ").map(|c| c.trim().to_string()).collect();
+    currencies.sort();
+    currencies.dedup();
+    currencies
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+// Nearest-rank percentile of an ascending slice.
+fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TriangularArbitrageOpportunity;
+    use crate::test_support::opportunity;
+
+    fn outcome(opportunities: Vec<TriangularArbitrageOpportunity>) -> ScanOutcome {
+        ScanOutcome {
+            exchange: "Binance".to_string(),
+            total_pairs: 3,
+            rejected: Default::default(),
+            fetch_ms: 10,
+            detect_ms: 1,
+            opportunities,
+            error: None,
+            error_kind: None,
+            tickers: Vec::new(),
+        }
+    }
+
+    // One opportunity open for the first minute of a two-minute run, with
+    // its net profit climbing from 0.5% to 1.0%, scanned every `interval_secs`.
+    fn run(interval_secs: i64) -> ExchangeAnalytics {
+        let analytics = Analytics::default();
+        let start = Utc::now();
+        let scans = 120 / interval_secs;
+        for i in 0..scans {
+            let at = start + Duration::seconds(i * interval_secs);
+            let opportunities = if i * interval_secs < 60 {
+                let net_profit = 0.5 + 0.5 * (i * interval_secs) as f64 / (60 - interval_secs) as f64;
+                vec![opportunity("Binance", "USDT → BTC → ETH → USDT", net_profit)]
+            } else {
+                Vec::new()
+            };
+            analytics.record_at(&outcome(opportunities), at);
+        }
+        analytics
+            .snapshot_at(start + Duration::seconds(120))
+            .exchanges
+            .remove(0)
+    }
+
+    #[test]
+    fn persisting_opportunities_count_once() {
+        for interval_secs in [2, 10, 30] {
+            let stats = run(interval_secs);
+            assert!((stats.opportunities_per_minute - 0.5).abs() < 1e-9, "{}s: {:?}", interval_secs, stats);
+            assert!((stats.mean_net_profit.unwrap() - 1.0).abs() < 1e-9);
+            assert!((stats.mean_lifetime_secs.unwrap() - 60.0).abs() < 1e-9);
+            assert_eq!(stats.top_currencies[0].count, 1);
+        }
+    }
+
+    #[test]
+    fn failed_scans_keep_episodes_open() {
+        let analytics = Analytics::default();
+        let start = Utc::now();
+        let path = "USDT → BTC → ETH → USDT";
+
+        analytics.record_at(&outcome(vec![opportunity("Binance", path, 0.4)]), start);
+        let failed = ScanOutcome {
+            error: Some("timeout".to_string()),
+            ..outcome(Vec::new())
+        };
+        analytics.record_at(&failed, start + Duration::seconds(10));
+        analytics.record_at(&outcome(vec![opportunity("Binance", path, 0.2)]), start + Duration::seconds(20));
+        analytics.record_at(&outcome(vec![opportunity("Binance", "USDT → ETH → BTC → USDT", 0.8)]), start + Duration::seconds(30));
+
+        let stats = analytics.snapshot_at(start + Duration::seconds(30)).exchanges.remove(0);
+        assert_eq!(stats.scans, 4);
+        assert_eq!(stats.fetch_success_rate, 0.75);
+        // The first path lasted 30s at a best of 0.4%; the second just opened.
+        assert_eq!(stats.mean_lifetime_secs, Some(15.0));
+        assert_eq!(stats.p95_net_profit, Some(0.8));
+        assert!((stats.mean_net_profit.unwrap() - 0.6).abs() < 1e-9);
+        assert_eq!(stats.last_cycle.unwrap().opportunity_count, 1);
+    }
+
+    #[test]
+    fn closed_episodes_leave_the_window() {
+        let analytics = Analytics::new(Duration::minutes(1));
+        let start = Utc::now();
+
+        analytics.record_at(&outcome(vec![opportunity("Binance", "USDT → BTC → ETH → USDT", 0.4)]), start);
+        analytics.record_at(&outcome(Vec::new()), start + Duration::seconds(5));
+        analytics.record_at(&outcome(Vec::new()), start + Duration::seconds(90));
+
+        let stats = analytics.snapshot_at(start + Duration::seconds(90)).exchanges.remove(0);
+        assert_eq!(stats.scans, 1);
+        assert_eq!(stats.opportunities_per_minute, 0.0);
+        assert_eq!(stats.mean_net_profit, None);
+    }
+}
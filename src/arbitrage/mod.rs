@@ -1,15 +1,57 @@
-use crate::models::{Ticker, TriangularArbitrageOpportunity};
+use crate::models::{OpportunityLeg, RejectionCounts, Ticker, TradeSide, TriangularArbitrageOpportunity};
 use std::collections::HashMap;
 use uuid::Uuid;
 use chrono::Utc;
 
+mod route;
+
+pub use route::{start_currency, Route};
+
 pub struct ArbitrageDetector;
 
 impl ArbitrageDetector {
+    /// Drops tickers the profit calculation can't use, counting each reason.
+    /// Venues that don't report volume keep all their pairs under
+    /// `min_quote_volume`.
+    pub fn sanitize_tickers(
+        tickers: Vec<Ticker>,
+        min_quote_volume: Option<f64>,
+    ) -> (Vec<Ticker>, RejectionCounts) {
+        let mut rejected = RejectionCounts::default();
+        
+        let valid = tickers
+            .into_iter()
+            .filter(|t| {
+                let prices_valid = [t.bid_price, t.ask_price]
+                    .iter()
+                    .all(|price| price.is_finite() && *price > 0.0);
+                if !prices_valid {
+                    rejected.invalid_price += 1;
+                    return false;
+                }
+                if t.bid_price > t.ask_price {
+                    rejected.crossed_book += 1;
+                    return false;
+                }
+                if let (Some(min), Some(volume)) = (min_quote_volume, t.quote_volume) {
+                    if volume < min {
+                        rejected.low_volume += 1;
+                        return false;
+                    }
+                }
+                true
+            })
+            .collect();
+        
+        (valid, rejected)
+    }
+    
     pub fn find_triangular_opportunities(
         tickers: &[Ticker],
         exchange_name: &str,
         min_profit: f64,
+        fee_rate: f64,
+        max_results: usize,
     ) -> Vec<TriangularArbitrageOpportunity> {
         let mut opportunities = Vec::new();
         
@@ -21,7 +63,7 @@ impl ArbitrageDetector {
         let mut quote_groups: HashMap<String, Vec<&Ticker>> = HashMap::new();
         for ticker in tickers {
             quote_groups.entry(ticker.quote_currency.clone())
-                        .or_insert_with(Vec::new)
+                        .or_default()
                         .push(ticker);
         }
         
@@ -36,17 +78,13 @@ impl ArbitrageDetector {
                     let reverse_symbol = format!("{}{}", intermediate_currency, base_currency);
                     let forward_symbol = format!("{}{}", base_currency, intermediate_currency);
                     
-                    if let Some(third_ticker) = symbol_map.get(&reverse_symbol) {
-                        if let Some(opportunity) = Self::calculate_profit(
-                            base_ticker, second_ticker, third_ticker,
-                            exchange_name, min_profit
-                        ) {
-                            opportunities.push(opportunity);
-                        }
-                    } else if let Some(third_ticker) = symbol_map.get(&forward_symbol) {
-                        if let Some(opportunity) = Self::calculate_profit_reverse(
-                            base_ticker, second_ticker, third_ticker,
-                            exchange_name, min_profit
+                    let third_ticker = symbol_map
+                        .get(&reverse_symbol)
+                        .or_else(|| symbol_map.get(&forward_symbol));
+                    if let Some(third_ticker) = third_ticker {
+                        if let Some(opportunity) = Self::evaluate(
+                            [base_ticker, second_ticker, third_ticker],
+                            exchange_name, min_profit, fee_rate
                         ) {
                             opportunities.push(opportunity);
                         }
@@ -56,92 +94,187 @@ impl ArbitrageDetector {
         }
         
         opportunities.sort_by(|a, b| b.net_profit_percentage.partial_cmp(&a.net_profit_percentage).unwrap());
-        opportunities.into_iter().take(20).collect()
+        opportunities.into_iter().take(max_results).collect()
     }
     
-    fn calculate_profit(
-        first: &Ticker,
-        second: &Ticker,
-        third: &Ticker,
+    /// Prices the cycle `A → Q → B → A` over `A/Q`, `B/Q` and `B/A` (or
+    /// `A/B`), starting with A. Each leg trades out of the currency held at
+    /// that point, so the legs, the profit and `max_notional` all follow the
+    /// advertised path.
+    fn evaluate(
+        tickers: [&Ticker; 3],
         exchange: &str,
         min_profit: f64,
+        fee_rate: f64,
     ) -> Option<TriangularArbitrageOpportunity> {
-        let buy_first = first.ask_price;
-        let buy_second = second.ask_price;
-        let sell_third = third.bid_price;
+        let start = &tickers[0].base_currency;
+        let mut currencies = vec![start.clone()];
+        let mut legs = Vec::with_capacity(tickers.len());
+        let mut amount = 1.0;
         
-        let initial_amount = 1.0;
-        let amount_after_first = initial_amount / buy_first;
-        let amount_after_second = amount_after_first / buy_second;
-        let final_amount = amount_after_second * sell_third;
+        for ticker in tickers {
+            let route = Route::from_holding(ticker, currencies.last()?)?;
+            amount = match route.side {
+                TradeSide::Buy => amount / route.price,
+                TradeSide::Sell => amount * route.price,
+            };
+            currencies.push(route.to.clone());
+            legs.push(OpportunityLeg {
+                symbol: route.symbol,
+                venue_symbol: ticker.venue_symbol.clone(),
+                side: route.side,
+                price: route.price,
+                quantity: route.available,
+            });
+        }
+        if currencies.last() != Some(start) {
+            return None;
+        }
         
-        let gross_profit_percentage = ((final_amount - initial_amount) / initial_amount) * 100.0;
-        let estimated_fees = 0.1 * 3.0; // 0.1% per trade * 3 trades = 0.3%
+        let gross_profit_percentage = (amount - 1.0) * 100.0;
+        let estimated_fees = fee_rate * 3.0; // fee per trade * 3 trades
         let net_profit_percentage = gross_profit_percentage - estimated_fees;
-        
-        if net_profit_percentage > min_profit {
-            Some(TriangularArbitrageOpportunity {
-                id: Uuid::new_v4().to_string(),
-                exchange: exchange.to_string(),
-                path: format!("{} → {} → {} → {}", 
-                             first.base_currency,
-                             first.quote_currency, 
-                             second.base_currency,
-                             first.base_currency),
-                pairs: format!("{}, {}, {}", 
-                              first.symbol,
-                              second.symbol, 
-                              third.symbol),
-                gross_profit_percentage,
-                estimated_fees,
-                net_profit_percentage,
-                timestamp: Utc::now(),
-            })
-        } else {
-            None
+        if net_profit_percentage <= min_profit {
+            return None;
         }
+        
+        Some(TriangularArbitrageOpportunity {
+            id: Uuid::new_v4().to_string(),
+            exchange: exchange.to_string(),
+            path: currencies.join(" → "),
+            pairs: tickers.map(|t| t.symbol.as_str()).join(", "),
+            gross_profit_percentage,
+            estimated_fees,
+            net_profit_percentage,
+            timestamp: Utc::now(),
+            max_notional: Self::executable_notional(&legs),
+            funded_notional: None,
+            fundable: None,
+            legs,
+        })
     }
     
-    fn calculate_profit_reverse(
-        first: &Ticker,
-        second: &Ticker,
-        third: &Ticker,
-        exchange: &str,
-        min_profit: f64,
-    ) -> Option<TriangularArbitrageOpportunity> {
-        let buy_first = first.ask_price;
-        let buy_second = second.ask_price;
-        let buy_third = third.ask_price;
-        
-        let initial_amount = 1.0;
-        let amount_after_first = initial_amount / buy_first;
-        let amount_after_second = amount_after_first / buy_second;
-        let final_amount = amount_after_second / buy_third;
+    /// Largest amount of the path's first currency that fits the top-of-book
+    /// quantity of every leg, following the same conversions as the profit
+    /// calculation: a buy divides by its price and is capped at
+    /// `quantity * price` of input, a sell multiplies by its price and is
+    /// capped at `quantity` of input.
+    pub fn executable_notional(legs: &[OpportunityLeg]) -> f64 {
+        let mut notional = f64::INFINITY;
+        let mut conversion = 1.0; // input of the current leg per unit of starting amount
         
-        let gross_profit_percentage = ((final_amount - initial_amount) / initial_amount) * 100.0;
-        let estimated_fees = 0.1 * 3.0; // 0.1% per trade * 3 trades = 0.3%
-        let net_profit_percentage = gross_profit_percentage - estimated_fees;
+        for leg in legs {
+            if leg.price <= 0.0 || conversion <= 0.0 {
+                return 0.0;
+            }
+            
+            let (capacity, next_conversion) = match leg.side {
+                TradeSide::Buy => (leg.quantity * leg.price, conversion / leg.price),
+                TradeSide::Sell => (leg.quantity, conversion * leg.price),
+            };
+            
+            notional = notional.min(capacity / conversion);
+            conversion = next_conversion;
+        }
         
-        if net_profit_percentage > min_profit {
-            Some(TriangularArbitrageOpportunity {
-                id: Uuid::new_v4().to_string(),
-                exchange: exchange.to_string(),
-                path: format!("{} → {} → {} → {}", 
-                             first.base_currency,
-                             first.quote_currency, 
-                             second.base_currency,
-                             first.base_currency),
-                pairs: format!("{}, {}, {}", 
-                              first.symbol,
-                              second.symbol, 
-                              third.symbol),
-                gross_profit_percentage,
-                estimated_fees,
-                net_profit_percentage,
-                timestamp: Utc::now(),
-            })
-        } else {
-            None
+        if notional.is_finite() { notional } else { 0.0 }
+    }
+    
+    /// Caps each opportunity's `max_notional` at the balance held of its
+    /// path's first currency and marks it fundable when that leaves
+    /// something to trade. `balances` holds free amounts by canonical
+    /// currency.
+    pub fn apply_balances(
+        opportunities: &mut [TriangularArbitrageOpportunity],
+        balances: &HashMap<String, f64>,
+    ) {
+        for opportunity in opportunities {
+            let held = balances.get(&start_currency(opportunity)).copied().unwrap_or(0.0);
+            let funded = held.min(opportunity.max_notional);
+            opportunity.funded_notional = Some(funded);
+            opportunity.fundable = Some(funded > 0.0);
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ticker;
+
+    fn sides(opportunity: &TriangularArbitrageOpportunity) -> Vec<TradeSide> {
+        opportunity.legs.iter().map(|leg| leg.side).collect()
+    }
+
+    #[test]
+    fn cycle_sells_the_start_then_buys_and_sells_back() {
+        // BTC → USDT → ETH → BTC: 40000 / 2000 * 0.0501 = 1.002
+        let tickers = [
+            ticker("BTC", "USDT", 40000.0, 40010.0, 1.0),
+            ticker("ETH", "USDT", 1999.0, 2000.0, 10.0),
+            ticker("ETH", "BTC", 0.0501, 0.0502, 100.0),
+        ];
+        let found = ArbitrageDetector::find_triangular_opportunities(&tickers, "test", 0.0, 0.0, 10);
+
+        assert_eq!(found.len(), 1);
+        let opportunity = &found[0];
+        assert_eq!(opportunity.path, "BTC → USDT → ETH → BTC");
+        assert_eq!(sides(opportunity), [TradeSide::Sell, TradeSide::Buy, TradeSide::Sell]);
+        assert_eq!(opportunity.legs[0].price, 40000.0);
+        assert_eq!(opportunity.legs[1].price, 2000.0);
+        assert!((opportunity.gross_profit_percentage - 0.2).abs() < 1e-9);
+        // 10 ETH on the ask is 20000 USDT, i.e. half a BTC.
+        assert!((opportunity.max_notional - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn cycle_through_the_start_quoted_pair_buys_back() {
+        // ETH → USDT → BTC → ETH over ETH/BTC: 2010 / 40000 / 0.05 = 1.005
+        let tickers = [
+            ticker("ETH", "USDT", 2010.0, 2011.0, 100.0),
+            ticker("BTC", "USDT", 39990.0, 40000.0, 1.0),
+            ticker("ETH", "BTC", 0.0499, 0.05, 100.0),
+        ];
+        let found = ArbitrageDetector::find_triangular_opportunities(&tickers, "test", 0.0, 0.0, 10);
+
+        assert_eq!(found.len(), 1);
+        let opportunity = &found[0];
+        assert_eq!(opportunity.path, "ETH → USDT → BTC → ETH");
+        assert_eq!(sides(opportunity), [TradeSide::Sell, TradeSide::Buy, TradeSide::Buy]);
+        assert!((opportunity.gross_profit_percentage - 0.5).abs() < 1e-9);
+        // 1 BTC on the ask costs 40000 USDT, i.e. about 19.9 ETH.
+        assert!((opportunity.max_notional - 40000.0 / 2010.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn fees_above_the_gross_profit_drop_the_cycle() {
+        let tickers = [
+            ticker("BTC", "USDT", 40000.0, 40010.0, 1.0),
+            ticker("ETH", "USDT", 1999.0, 2000.0, 10.0),
+            ticker("ETH", "BTC", 0.0501, 0.0502, 100.0),
+        ];
+        // 0.2% gross against 3 × 0.1% in fees
+        assert!(ArbitrageDetector::find_triangular_opportunities(&tickers, "test", 0.0, 0.1, 10).is_empty());
+    }
+
+    #[test]
+    fn balances_cap_the_executable_notional() {
+        let tickers = [
+            ticker("BTC", "USDT", 40000.0, 40010.0, 1.0),
+            ticker("ETH", "USDT", 1999.0, 2000.0, 10.0),
+            ticker("ETH", "BTC", 0.0501, 0.0502, 100.0),
+        ];
+        let mut found = ArbitrageDetector::find_triangular_opportunities(&tickers, "test", 0.0, 0.0, 10);
+
+        ArbitrageDetector::apply_balances(&mut found, &HashMap::from([("BTC".to_string(), 2.0)]));
+        assert_eq!(found[0].funded_notional, Some(found[0].max_notional));
+        assert_eq!(found[0].fundable, Some(true));
+
+        ArbitrageDetector::apply_balances(&mut found, &HashMap::from([("BTC".to_string(), 0.1)]));
+        assert_eq!(found[0].funded_notional, Some(0.1));
+
+        ArbitrageDetector::apply_balances(&mut found, &HashMap::from([("USDT".to_string(), 1000.0)]));
+        assert_eq!(found[0].funded_notional, Some(0.0));
+        assert_eq!(found[0].fundable, Some(false));
+    }
+}
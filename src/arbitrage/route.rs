@@ -0,0 +1,141 @@
+use crate::models::{Ticker, TradeSide, TriangularArbitrageOpportunity};
+
+/// First currency of an opportunity's path, which its cycle starts and
+/// ends in.
+pub fn start_currency(opportunity: &TriangularArbitrageOpportunity) -> String {
+    opportunity
+        .path
+        .split(" → ")
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string()
+}
+
+/// How one leg trades out of `from`: buying the base with the quote at the
+/// ask, or selling the base for the quote at the bid.
+#[derive(Debug, Clone)]
+pub struct Route {
+    pub symbol: String,
+    pub side: TradeSide,
+    pub from: String,
+    pub to: String,
+    pub price: f64,
+    pub available: f64, // top-of-book quantity, in the base asset
+}
+
+impl Route {
+    /// Trades `ticker` on `side` at the current top of book.
+    pub fn new(ticker: &Ticker, side: TradeSide) -> Option<Self> {
+        let (from, to, price, available) = match side {
+            TradeSide::Buy => (&ticker.quote_currency, &ticker.base_currency, ticker.ask_price, ticker.ask_qty),
+            TradeSide::Sell => (&ticker.base_currency, &ticker.quote_currency, ticker.bid_price, ticker.bid_qty),
+        };
+
+        (price > 0.0).then(|| Self {
+            symbol: ticker.symbol.clone(),
+            side,
+            from: from.clone(),
+            to: to.clone(),
+            price,
+            available,
+        })
+    }
+
+    /// Trades `ticker` out of `holding`, on whichever side spends it.
+    pub fn from_holding(ticker: &Ticker, holding: &str) -> Option<Self> {
+        let side = if ticker.quote_currency == holding {
+            TradeSide::Buy
+        } else if ticker.base_currency == holding {
+            TradeSide::Sell
+        } else {
+            return None;
+        };
+        Self::new(ticker, side)
+    }
+
+    /// Spends up to `amount` of the held currency against the top of book. Returns the
+    /// amount spent and the `to` amount received before fees.
+    pub fn fill(&self, amount: f64) -> (f64, f64) {
+        match self.side {
+            TradeSide::Buy => {
+                let bought = (amount / self.price).min(self.available);
+                (bought * self.price, bought)
+            }
+            TradeSide::Sell => {
+                let sold = amount.min(self.available);
+                (sold, sold * self.price)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ticker;
+
+    // 1 BTC on the bid at 99, 2 on the ask.
+    fn btc_usdt(ask: f64) -> Ticker {
+        Ticker {
+            ask_qty: 2.0,
+            ..ticker("BTC", "USDT", 99.0, ask, 1.0)
+        }
+    }
+
+    #[test]
+    fn holding_the_quote_buys_at_the_ask() {
+        let route = Route::from_holding(&btc_usdt(100.0), "USDT").unwrap();
+        assert_eq!(route.side, TradeSide::Buy);
+        assert_eq!(route.to, "BTC");
+        assert_eq!(route.price, 100.0);
+        assert_eq!(route.available, 2.0);
+    }
+
+    #[test]
+    fn holding_the_base_sells_at_the_bid() {
+        let route = Route::from_holding(&btc_usdt(100.0), "BTC").unwrap();
+        assert_eq!(route.side, TradeSide::Sell);
+        assert_eq!(route.to, "USDT");
+        assert_eq!(route.price, 99.0);
+        assert_eq!(route.available, 1.0);
+    }
+
+    #[test]
+    fn unrelated_currency_or_empty_side_has_no_route() {
+        assert!(Route::from_holding(&btc_usdt(100.0), "ETH").is_none());
+        assert!(Route::from_holding(&btc_usdt(0.0), "USDT").is_none());
+    }
+
+    #[test]
+    fn fill_is_capped_by_top_of_book() {
+        let book = btc_usdt(100.0);
+
+        let buy = Route::from_holding(&book, "USDT").unwrap();
+        assert_eq!(buy.fill(150.0), (150.0, 1.5));
+        assert_eq!(buy.fill(500.0), (200.0, 2.0)); // 2 BTC on the ask
+
+        let sell = Route::from_holding(&book, "BTC").unwrap();
+        assert_eq!(sell.fill(0.5), (0.5, 49.5));
+        assert_eq!(sell.fill(3.0), (1.0, 99.0)); // 1 BTC on the bid
+    }
+
+    #[test]
+    fn start_currency_is_the_first_on_the_path() {
+        let opportunity = TriangularArbitrageOpportunity {
+            id: String::new(),
+            exchange: String::new(),
+            path: "ETH → USDT → BTC → ETH".to_string(),
+            pairs: String::new(),
+            gross_profit_percentage: 0.0,
+            estimated_fees: 0.0,
+            net_profit_percentage: 0.0,
+            timestamp: chrono::Utc::now(),
+            legs: Vec::new(),
+            max_notional: 0.0,
+            funded_notional: None,
+            fundable: None,
+        };
+        assert_eq!(start_currency(&opportunity), "ETH");
+    }
+}
@@ -0,0 +1,98 @@
+use crate::config::ExchangeConfig;
+use reqwest::Method;
+
+pub mod binance;
+pub mod bybit;
+pub mod gateio;
+pub mod kucoin;
+
+/// API key material from `[exchanges.<key>]`.
+#[derive(Clone)]
+pub struct Credentials {
+    pub api_key: String,
+    pub api_secret: String,
+    pub passphrase: Option<String>,
+}
+
+impl Credentials {
+    pub fn from_config(config: &ExchangeConfig) -> Option<Self> {
+        Some(Self {
+            api_key: config.api_key.clone()?,
+            api_secret: config.api_secret.clone()?,
+            passphrase: config.passphrase.clone(),
+        })
+    }
+}
+
+// Same rule as ExchangeConfig: key material never reaches logs.
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("api_key", &"<redacted>")
+            .field("api_secret", &"<redacted>")
+            .field("passphrase", &self.passphrase.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+/// Query string and headers that authenticate one request.
+pub struct SignedRequest {
+    pub query: String,
+    pub headers: Vec<(&'static str, String)>,
+}
+
+/// Request signing for one venue. `path` starts with `/` and `query` has no
+/// leading `?`; either may be folded into the signature.
+pub trait Signer: Send + Sync {
+    fn sign(&self, method: &Method, path: &str, query: &str, body: &str) -> Result<SignedRequest, Box<dyn std::error::Error>>;
+}
+
+/// Signer for the venue behind `key`, or `None` without credentials or
+/// signing support.
+pub fn build_signer(key: &str, config: &ExchangeConfig, recv_window_ms: u64) -> Option<Box<dyn Signer>> {
+    let credentials = Credentials::from_config(config)?;
+    let signer: Box<dyn Signer> = match key {
+        "binance" => Box::new(binance::BinanceSigner::new(credentials, recv_window_ms)),
+        "bybit" => Box::new(bybit::BybitSigner::new(credentials, recv_window_ms)),
+        "kucoin" => Box::new(kucoin::KucoinSigner::new(credentials)),
+        "gateio" => Box::new(gateio::GateIoSigner::new(credentials)),
+        _ => return None,
+    };
+    Some(signer)
+}
+
+/// The signer, or an error naming the venue that has no keys configured.
+pub fn require<'a>(signer: &'a Option<Box<dyn Signer>>, venue: &str) -> Result<&'a dyn Signer, Box<dyn std::error::Error>> {
+    signer
+        .as_deref()
+        .ok_or_else(|| format!("No API credentials configured for {}", venue).into())
+}
+
+/// Builds a signed request against `base_url`. A non-empty body is sent as JSON.
+pub fn signed_request(
+    client: &reqwest::Client,
+    signer: &dyn Signer,
+    method: Method,
+    base_url: &str,
+    path: &str,
+    query: &str,
+    body: &str,
+) -> Result<reqwest::RequestBuilder, Box<dyn std::error::Error>> {
+    let signed = signer.sign(&method, path, query, body)?;
+    let url = if signed.query.is_empty() {
+        format!("{}{}", base_url, path)
+    } else {
+        format!("{}{}?{}", base_url, path, signed.query)
+    };
+
+    let mut request = client.request(method, url);
+    for (name, value) in signed.headers {
+        request = request.header(name, value);
+    }
+    if !body.is_empty() {
+        request = request
+            .header("Content-Type", "application/json")
+            .body(body.to_string());
+    }
+    Ok(request)
+}
@@ -0,0 +1,412 @@
+use crate::arbitrage::{start_currency, ArbitrageDetector, Route};
+use crate::config::Config;
+use crate::currency::CurrencyNormalizer;
+use crate::exchanges::find_exchange;
+use crate::models::{Ticker, TradeSide, TriangularArbitrageOpportunity};
+use crate::recorder::{BatchReader, RecordedBatch};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+/// Settings for one backtest run.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestParams {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub min_profit: f64,
+    pub min_quote_volume: Option<f64>,
+    // Percent per trade, for both detection and execution; `None` uses each
+    // recorded exchange's configured taker fee.
+    pub fee_rate: Option<f64>,
+    pub latency_ms: i64,
+    pub notional: f64, // size per trade, in the path's first currency
+    pub max_results: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Distribution {
+    pub count: usize,
+    pub min: Option<f64>,
+    pub p50: Option<f64>,
+    pub p90: Option<f64>,
+    pub p99: Option<f64>,
+    pub max: Option<f64>,
+    pub mean: Option<f64>,
+}
+
+impl Distribution {
+    fn from_values(mut values: Vec<f64>) -> Self {
+        values.sort_by(|a, b| a.total_cmp(b));
+        let percentile = |p: f64| {
+            if values.is_empty() {
+                return None;
+            }
+            let rank = (p * values.len() as f64).ceil() as usize;
+            Some(values[rank.clamp(1, values.len()) - 1])
+        };
+
+        Self {
+            count: values.len(),
+            min: values.first().copied(),
+            p50: percentile(0.50),
+            p90: percentile(0.90),
+            p99: percentile(0.99),
+            max: values.last().copied(),
+            mean: (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestReport {
+    pub params: BacktestParams,
+    pub exchanges: Vec<String>,
+    pub first_batch: Option<DateTime<Utc>>,
+    pub last_batch: Option<DateTime<Utc>>,
+    pub batches: usize,
+    pub detections: usize, // opportunities across all batches
+    pub episodes: usize,   // distinct runs of an opportunity across consecutive batches
+    pub trades: usize,
+    pub profitable_trades: usize,
+    pub hit_rate: f64, // profitable / trades
+    pub mean_fill_ratio: Option<f64>,
+    pub mean_return_pct: Option<f64>,
+    // Amounts are in the currency each path starts from, so they are only
+    // summed per currency.
+    pub pnl_by_currency: BTreeMap<String, f64>,
+    pub opportunity_size: Distribution,   // executable notional at detection
+    pub opportunity_duration_secs: Distribution,
+    pub trade_return_pct: Distribution,
+}
+
+/// Result of simulating one trade against the execution-time book.
+#[derive(Debug, Clone)]
+struct SimulatedTrade {
+    currency: String,
+    size: f64,
+    pnl: f64,
+    fill_ratio: f64,
+}
+
+struct PendingTrade {
+    opportunity: TriangularArbitrageOpportunity,
+    execute_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct Totals {
+    exchanges: Vec<String>,
+    first_batch: Option<DateTime<Utc>>,
+    last_batch: Option<DateTime<Utc>>,
+    batches: usize,
+    detections: usize,
+    episodes: usize,
+    sizes: Vec<f64>,
+    durations: Vec<f64>,
+    trades: Vec<SimulatedTrade>,
+}
+
+/// Replays recordings through the detector and a simple execution model:
+/// each opportunity is traded once, when it first appears, against the book
+/// as it stood `latency_ms` later (the latest recorded batch at or before
+/// that time), starting with `notional` of the path's first currency (capped
+/// at what the book could absorb at detection). Legs fill sequentially on the
+/// opportunity's sides up to the top-of-book quantity; what a partial fill leaves
+/// stranded is unwound back to the starting currency at the same book, paying
+/// fees again.
+pub struct Backtest {
+    params: BacktestParams,
+}
+
+impl Backtest {
+    pub fn new(params: BacktestParams) -> Self {
+        Self { params }
+    }
+
+    /// Runs every input in turn; each file is one exchange's stream.
+    pub fn run(&self, config: &Config, inputs: &[PathBuf]) -> Result<BacktestReport, Box<dyn std::error::Error>> {
+        let mut totals = Totals::default();
+        for input in inputs {
+            self.run_stream(config, BatchReader::open(input)?, &mut totals)?;
+        }
+        Ok(self.report(totals))
+    }
+
+    fn run_stream(
+        &self,
+        config: &Config,
+        reader: BatchReader,
+        totals: &mut Totals,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let latency = Duration::milliseconds(self.params.latency_ms.max(0));
+        let mut fee_rate = self.params.fee_rate;
+        let mut book: Option<(DateTime<Utc>, HashMap<String, Ticker>)> = None;
+        let mut pending: Vec<PendingTrade> = Vec::new();
+        let mut open: HashMap<String, DateTime<Utc>> = HashMap::new();
+
+        for batch in reader {
+            let RecordedBatch {
+                timestamp,
+                exchange,
+                mut tickers,
+            } = batch?;
+            if self.params.from.is_some_and(|from| timestamp < from) {
+                continue;
+            }
+            if self.params.to.is_some_and(|to| timestamp >= to) {
+                break;
+            }
+
+            // Trades due before this batch fill against the previous book.
+            if let Some((_, current)) = &book {
+                let (due, waiting): (Vec<_>, Vec<_>) = pending.into_iter().partition(|p| p.execute_at < timestamp);
+                pending = waiting;
+                let fee_rate = fee_rate.unwrap_or(config.detector.fee_rate);
+                totals
+                    .trades
+                    .extend(due.iter().map(|p| self.simulate(&p.opportunity, current, fee_rate)));
+            }
+
+            if !totals.exchanges.contains(&exchange) {
+                totals.exchanges.push(exchange.clone());
+            }
+            totals.first_batch = Some(totals.first_batch.map_or(timestamp, |first| first.min(timestamp)));
+            totals.last_batch = Some(totals.last_batch.map_or(timestamp, |last| last.max(timestamp)));
+            totals.batches += 1;
+            let fee_rate = *fee_rate.get_or_insert_with(|| {
+                find_exchange(config, &exchange, None).map_or(config.detector.fee_rate, |e| e.taker_fee())
+            });
+
+            CurrencyNormalizer::global().normalize_tickers(&exchange, &mut tickers);
+            let (tickers, _) = ArbitrageDetector::sanitize_tickers(tickers, self.params.min_quote_volume);
+            let opportunities = ArbitrageDetector::find_triangular_opportunities(
+                &tickers,
+                &exchange,
+                self.params.min_profit,
+                fee_rate,
+                self.params.max_results,
+            );
+            totals.detections += opportunities.len();
+
+            let mut still_open = HashMap::new();
+            for opportunity in opportunities {
+                let key = format!("{}|{}", opportunity.path, opportunity.pairs);
+                let first_seen = match open.remove(&key) {
+                    Some(first_seen) => first_seen,
+                    None => {
+                        totals.episodes += 1;
+                        totals.sizes.push(opportunity.max_notional);
+                        pending.push(PendingTrade {
+                            opportunity,
+                            execute_at: timestamp + latency,
+                        });
+                        timestamp
+                    }
+                };
+                still_open.insert(key, first_seen);
+            }
+            totals
+                .durations
+                .extend(open.values().map(|first_seen| seconds(timestamp - *first_seen)));
+            open = still_open;
+
+            book = Some((
+                timestamp,
+                tickers.into_iter().map(|t| (t.symbol.clone(), t)).collect(),
+            ));
+        }
+
+        if let Some((last, current)) = &book {
+            let fee_rate = fee_rate.unwrap_or(config.detector.fee_rate);
+            totals
+                .trades
+                .extend(pending.iter().map(|p| self.simulate(&p.opportunity, current, fee_rate)));
+            // Still open at the end: count them up to the last batch.
+            totals
+                .durations
+                .extend(open.values().map(|first_seen| seconds(*last - *first_seen)));
+        }
+
+        Ok(())
+    }
+
+    fn simulate(
+        &self,
+        opportunity: &TriangularArbitrageOpportunity,
+        book: &HashMap<String, Ticker>,
+        fee_rate: f64,
+    ) -> SimulatedTrade {
+        let fee = fee_rate / 100.0;
+        let size = self.params.notional.min(opportunity.max_notional).max(0.0);
+        let currency = start_currency(opportunity);
+
+        let mut holding = currency.clone();
+        let mut amount = size;
+        let mut recovered = 0.0; // starting currency that never left or came back from unwinding
+        let mut fill_ratio = 1.0;
+        let mut done: Vec<Route> = Vec::new();
+
+        for leg in &opportunity.legs {
+            let route = book
+                .get(&leg.symbol)
+                .and_then(|ticker| Route::new(ticker, leg.side))
+                .filter(|route| route.from == holding);
+            let Some(route) = route else {
+                recovered += unwind(book, &done, amount, fee);
+                amount = 0.0;
+                fill_ratio = 0.0;
+                break;
+            };
+
+            let (filled_in, out) = route.fill(amount);
+            let leftover = amount - filled_in;
+            if leftover > 0.0 {
+                recovered += unwind(book, &done, leftover, fee);
+            }
+            if amount > 0.0 {
+                fill_ratio *= filled_in / amount;
+            }
+            amount = out * (1.0 - fee);
+            holding = route.to.clone();
+            done.push(route);
+        }
+
+        if holding != currency {
+            recovered += unwind(book, &done, amount, fee);
+            amount = 0.0;
+        }
+
+        SimulatedTrade {
+            currency,
+            size,
+            pnl: amount + recovered - size,
+            fill_ratio,
+        }
+    }
+
+    fn report(&self, totals: Totals) -> BacktestReport {
+        let trades = &totals.trades;
+        let profitable_trades = trades.iter().filter(|t| t.pnl > 0.0).count();
+        let returns: Vec<f64> = trades
+            .iter()
+            .filter(|t| t.size > 0.0)
+            .map(|t| t.pnl / t.size * 100.0)
+            .collect();
+
+        let mut pnl_by_currency = BTreeMap::new();
+        for trade in trades {
+            *pnl_by_currency.entry(trade.currency.clone()).or_insert(0.0) += trade.pnl;
+        }
+
+        let trade_return_pct = Distribution::from_values(returns);
+        BacktestReport {
+            params: self.params.clone(),
+            exchanges: totals.exchanges,
+            first_batch: totals.first_batch,
+            last_batch: totals.last_batch,
+            batches: totals.batches,
+            detections: totals.detections,
+            episodes: totals.episodes,
+            trades: trades.len(),
+            profitable_trades,
+            hit_rate: if trades.is_empty() { 0.0 } else { profitable_trades as f64 / trades.len() as f64 },
+            mean_fill_ratio: (!trades.is_empty())
+                .then(|| trades.iter().map(|t| t.fill_ratio).sum::<f64>() / trades.len() as f64),
+            mean_return_pct: trade_return_pct.mean,
+            pnl_by_currency,
+            opportunity_size: Distribution::from_values(totals.sizes),
+            opportunity_duration_secs: Distribution::from_values(totals.durations),
+            trade_return_pct,
+        }
+    }
+}
+
+// Converts `amount` (held in the currency the `done` routes led to) back
+// through them in reverse at `book`, crossing the spread and paying fees,
+// ignoring depth. Unpriceable inventory is written off.
+fn unwind(book: &HashMap<String, Ticker>, done: &[Route], amount: f64, fee: f64) -> f64 {
+    let mut amount = amount;
+    for route in done.iter().rev() {
+        let Some(ticker) = book.get(&route.symbol) else {
+            return 0.0;
+        };
+        amount = match route.side {
+            // Sell back the base we bought.
+            TradeSide::Buy => amount * ticker.bid_price,
+            // Buy back the base we sold.
+            TradeSide::Sell if ticker.ask_price > 0.0 => amount / ticker.ask_price,
+            TradeSide::Sell => return 0.0,
+        } * (1.0 - fee);
+    }
+    amount
+}
+
+fn seconds(duration: Duration) -> f64 {
+    duration.num_milliseconds() as f64 / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ticker;
+
+    fn book(tickers: &[Ticker]) -> HashMap<String, Ticker> {
+        tickers.iter().map(|t| (t.symbol.clone(), t.clone())).collect()
+    }
+
+    #[test]
+    fn distribution_of_nothing_is_empty() {
+        let distribution = Distribution::from_values(Vec::new());
+        assert_eq!(distribution.count, 0);
+        assert_eq!(distribution.p50, None);
+        assert_eq!(distribution.mean, None);
+    }
+
+    #[test]
+    fn distribution_uses_nearest_rank_percentiles() {
+        let distribution = Distribution::from_values((1..=100).rev().map(f64::from).collect());
+        assert_eq!(distribution.count, 100);
+        assert_eq!(distribution.min, Some(1.0));
+        assert_eq!(distribution.p50, Some(50.0));
+        assert_eq!(distribution.p90, Some(90.0));
+        assert_eq!(distribution.p99, Some(99.0));
+        assert_eq!(distribution.max, Some(100.0));
+        assert_eq!(distribution.mean, Some(50.5));
+    }
+
+    #[test]
+    fn unwind_reverses_routes_across_the_spread_with_fees() {
+        let btc_usdt = ticker("BTC", "USDT", 99.0, 100.0, 10.0);
+        let eth_btc = ticker("ETH", "BTC", 0.049, 0.05, 10.0);
+        let book = book(&[btc_usdt.clone(), eth_btc.clone()]);
+        // USDT → BTC (buy) → ETH (buy), holding 2 ETH.
+        let done = [
+            Route::from_holding(&btc_usdt, "USDT").unwrap(),
+            Route::from_holding(&eth_btc, "BTC").unwrap(),
+        ];
+
+        let back = unwind(&book, &done, 2.0, 0.0);
+        assert!((back - 2.0 * 0.049 * 99.0).abs() < 1e-9);
+
+        let with_fees = unwind(&book, &done, 2.0, 0.001);
+        assert!((with_fees - 2.0 * 0.049 * 0.999 * 99.0 * 0.999).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unwind_buys_back_what_a_sell_gave_up() {
+        let btc_usdt = ticker("BTC", "USDT", 99.0, 100.0, 10.0);
+        let book = book(std::slice::from_ref(&btc_usdt));
+        let done = [Route::from_holding(&btc_usdt, "BTC").unwrap()];
+
+        assert!((unwind(&book, &done, 99.0, 0.0) - 0.99).abs() < 1e-12);
+    }
+
+    #[test]
+    fn unwind_writes_off_unpriceable_inventory() {
+        let btc_usdt = ticker("BTC", "USDT", 99.0, 100.0, 10.0);
+        let done = [Route::from_holding(&btc_usdt, "USDT").unwrap()];
+
+        assert_eq!(unwind(&HashMap::new(), &done, 1.0, 0.0), 0.0);
+    }
+}
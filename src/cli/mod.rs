@@ -0,0 +1,335 @@
+use crate::backtest::{Backtest, BacktestParams, BacktestReport, Distribution};
+use crate::config::Config;
+use crate::currency::CurrencyNormalizer;
+use crate::exchanges::replay::ReplayExchange;
+use crate::exchanges::snapshot::SnapshotExchange;
+use crate::exchanges::{find_exchange, Exchange, NormalizedExchange};
+use crate::export::{CsvExporter, CsvRow};
+use crate::recorder::Recorder;
+use crate::scanner::{self, ScanOutcome};
+use crate::ui::UserInterface;
+use chrono::{DateTime, Utc};
+use clap::{Args, ValueEnum};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Exit code when the scan ran but tickers could not be fetched or read.
+pub const EXIT_FETCH_FAILED: i32 = 1;
+/// Exit code for arguments that name no known exchange.
+pub const EXIT_USAGE: i32 = 2;
+
+#[derive(Args)]
+pub struct ScanArgs {
+    /// Exchange to scan: name, config key or numeric id
+    #[arg(long, required_unless_present = "input")]
+    exchange: Option<String>,
+
+    /// Minimum net profit percentage (defaults to `detector.default_min_profit`)
+    #[arg(long, allow_negative_numbers = true)]
+    min_profit: Option<f64>,
+
+    /// Drop pairs with less 24h quote volume than this
+    #[arg(long)]
+    min_quote_volume: Option<f64>,
+
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+
+    /// Scan a saved JSON array of tickers instead of fetching live data
+    #[arg(long)]
+    input: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct ReplayArgs {
+    /// Recording to play back (a `.ndjson.gz` file written by the recorder)
+    #[arg(long)]
+    input: PathBuf,
+
+    /// Playback speed relative to the recording (1 is real time); 0 replays
+    /// as fast as possible
+    #[arg(long, default_value_t = 0.0)]
+    speed: f64,
+
+    /// Minimum net profit percentage (defaults to `detector.default_min_profit`)
+    #[arg(long, allow_negative_numbers = true)]
+    min_profit: Option<f64>,
+
+    /// Drop pairs with less 24h quote volume than this
+    #[arg(long)]
+    min_quote_volume: Option<f64>,
+
+    /// `json` prints one scan per line
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    format: OutputFormat,
+}
+
+#[derive(Args)]
+pub struct BacktestArgs {
+    /// Recordings to test against; each file is simulated on its own
+    #[arg(long, required = true, num_args = 1..)]
+    input: Vec<PathBuf>,
+
+    /// Skip batches recorded before this time (RFC 3339)
+    #[arg(long)]
+    from: Option<DateTime<Utc>>,
+
+    /// Stop at batches recorded at or after this time (RFC 3339)
+    #[arg(long)]
+    to: Option<DateTime<Utc>>,
+
+    /// Minimum net profit percentage (defaults to `detector.default_min_profit`)
+    #[arg(long, allow_negative_numbers = true)]
+    min_profit: Option<f64>,
+
+    /// Drop pairs with less 24h quote volume than this
+    #[arg(long)]
+    min_quote_volume: Option<f64>,
+
+    /// Fee percentage per leg (defaults to the recorded exchange's taker fee)
+    #[arg(long)]
+    fee_rate: Option<f64>,
+
+    /// Delay between detecting an opportunity and executing it
+    #[arg(long, default_value_t = 250)]
+    latency_ms: i64,
+
+    /// Target size per trade, in the path's starting currency
+    #[arg(long, default_value_t = 1000.0)]
+    notional: f64,
+
+    #[arg(long, value_enum, default_value_t = ReportFormat::Table)]
+    format: ReportFormat,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    Json,
+    Table,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum OutputFormat {
+    Json,
+    Csv,
+    Table,
+}
+
+/// Runs one scan without the web server, prints it to stdout in the chosen
+/// format and returns the process exit code. Errors go to stderr so the
+/// output stays machine-readable.
+pub async fn scan(config: &Config, args: ScanArgs) -> i32 {
+    let recorder = Arc::new(Recorder::new(&config.recorder.directory));
+    let exchange = match &args.exchange {
+        Some(query) => match find_exchange(config, query, Some(&recorder)) {
+            Some(exchange) => Some(exchange),
+            None => {
+                eprintln!("❌ Unknown exchange: {}", query);
+                return EXIT_USAGE;
+            }
+        },
+        None => None,
+    };
+
+    // A snapshot takes its name, id and fee from --exchange when given, and
+    // is normalized as if that venue had returned it.
+    let exchange: Box<dyn Exchange> = match (&args.input, exchange) {
+        (Some(path), Some(exchange)) => Box::new(NormalizedExchange::new(
+            Box::new(SnapshotExchange::new(
+                path,
+                exchange.name(),
+                exchange.id(),
+                exchange.taker_fee(),
+            )),
+            CurrencyNormalizer::global(),
+        )),
+        (Some(path), None) => Box::new(NormalizedExchange::new(
+            Box::new(SnapshotExchange::new(path, "Snapshot", 0, config.detector.fee_rate)),
+            CurrencyNormalizer::global(),
+        )),
+        (None, Some(exchange)) => exchange,
+        (None, None) => unreachable!("clap requires --exchange or --input"),
+    };
+
+    let min_profit = args.min_profit.unwrap_or(config.detector.default_min_profit);
+    let outcome = scanner::scan_exchange(exchange.as_ref(), config, min_profit, args.min_quote_volume).await;
+
+    if let Some(e) = &outcome.error {
+        eprintln!("❌ Failed to fetch tickers from {}: {}", outcome.exchange, e);
+        if matches!(args.format, OutputFormat::Json) {
+            print_json(&outcome);
+        }
+        return EXIT_FETCH_FAILED;
+    }
+
+    match args.format {
+        OutputFormat::Json => print_json(&outcome),
+        OutputFormat::Csv => {
+            if let Err(e) = print_csv(&outcome, true) {
+                eprintln!("❌ Failed to write CSV: {}", e);
+                return EXIT_FETCH_FAILED;
+            }
+        }
+        OutputFormat::Table => print_table(&outcome, 1, min_profit, config),
+    }
+
+    0
+}
+
+/// Feeds every batch of a recording through the scanner in order, printing
+/// each scan like `scan` does.
+pub async fn replay(config: &Config, args: ReplayArgs) -> i32 {
+    let exchange = match ReplayExchange::open(&args.input, args.speed, config) {
+        Ok(exchange) => exchange,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return EXIT_FETCH_FAILED;
+        }
+    };
+
+    let min_profit = args.min_profit.unwrap_or(config.detector.default_min_profit);
+    let mut cycle: u64 = 0;
+
+    while !exchange.is_finished() {
+        cycle += 1;
+        let outcome = scanner::scan_exchange(&exchange, config, min_profit, args.min_quote_volume).await;
+
+        if let Some(e) = &outcome.error {
+            eprintln!("❌ Failed to read batch {} of {}: {}", cycle, args.input.display(), e);
+            return EXIT_FETCH_FAILED;
+        }
+
+        match args.format {
+            OutputFormat::Json => match serde_json::to_string(&outcome) {
+                Ok(json) => println!("{}", json),
+                Err(e) => eprintln!("❌ Failed to serialize scan: {}", e),
+            },
+            OutputFormat::Csv => {
+                if let Err(e) = print_csv(&outcome, cycle == 1) {
+                    eprintln!("❌ Failed to write CSV: {}", e);
+                    return EXIT_FETCH_FAILED;
+                }
+            }
+            OutputFormat::Table => print_table(&outcome, cycle, min_profit, config),
+        }
+    }
+
+    eprintln!("✅ Replayed {} batch(es) of {} from {}", cycle, exchange.name(), args.input.display());
+    0
+}
+
+/// Simulates trading the opportunities found in one or more recordings and
+/// prints the resulting report.
+pub fn backtest(config: &Config, args: BacktestArgs) -> i32 {
+    if args.notional <= 0.0 || args.latency_ms < 0 {
+        eprintln!("❌ --notional must be positive and --latency-ms non-negative");
+        return EXIT_USAGE;
+    }
+
+    let backtest = Backtest::new(BacktestParams {
+        from: args.from,
+        to: args.to,
+        min_profit: args.min_profit.unwrap_or(config.detector.default_min_profit),
+        min_quote_volume: args.min_quote_volume,
+        fee_rate: args.fee_rate,
+        latency_ms: args.latency_ms,
+        notional: args.notional,
+        max_results: config.detector.max_results,
+    });
+
+    let report = match backtest.run(config, &args.input) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return EXIT_FETCH_FAILED;
+        }
+    };
+
+    match args.format {
+        ReportFormat::Json => match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("❌ Failed to serialize report: {}", e),
+        },
+        ReportFormat::Table => print_report(&report),
+    }
+
+    0
+}
+
+fn print_report(report: &BacktestReport) {
+    let span = match (report.first_batch, report.last_batch) {
+        (Some(first), Some(last)) => format!("{} → {}", first.format("%Y-%m-%d %H:%M:%S"), last.format("%Y-%m-%d %H:%M:%S")),
+        _ => "no batches in range".to_string(),
+    };
+    let fee = report
+        .params
+        .fee_rate
+        .map_or("exchange taker fee".to_string(), |fee| format!("{:.4}%", fee));
+
+    let exchanges = if report.exchanges.is_empty() { "-".to_string() } else { report.exchanges.join(", ") };
+    println!("📊 Backtest of {}", exchanges);
+    println!("   {} ({} batches)", span, report.batches);
+    println!(
+        "   min profit {:.4}%, fee {}, latency {}ms, notional {}",
+        report.params.min_profit, fee, report.params.latency_ms, report.params.notional
+    );
+    println!();
+    println!("🔍 {} detections in {} opportunity episodes", report.detections, report.episodes);
+    println!(
+        "💱 {} trades, {} profitable (hit rate {:.1}%), mean fill {}",
+        report.trades,
+        report.profitable_trades,
+        report.hit_rate * 100.0,
+        report.mean_fill_ratio.map_or("-".to_string(), |r| format!("{:.1}%", r * 100.0))
+    );
+    for (currency, pnl) in &report.pnl_by_currency {
+        println!("💰 PnL {:>+16.8} {}", pnl, currency);
+    }
+    println!();
+    println!("{:<18} {:>7} {:>12} {:>12} {:>12} {:>12} {:>12}", "Distribution", "count", "min", "p50", "p90", "p99", "max");
+    print_distribution("return %", &report.trade_return_pct);
+    print_distribution("size", &report.opportunity_size);
+    print_distribution("duration (s)", &report.opportunity_duration_secs);
+}
+
+fn print_distribution(label: &str, distribution: &Distribution) {
+    let cell = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{:.4}", v));
+    println!(
+        "{:<18} {:>7} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        label,
+        distribution.count,
+        cell(distribution.min),
+        cell(distribution.p50),
+        cell(distribution.p90),
+        cell(distribution.p99),
+        cell(distribution.max)
+    );
+}
+
+fn print_table(outcome: &ScanOutcome, cycle: u64, min_profit: f64, config: &Config) {
+    UserInterface::show_cycle_header(cycle, outcome);
+    if outcome.opportunities.is_empty() {
+        UserInterface::show_no_opportunities(min_profit);
+    } else {
+        UserInterface::show_opportunities(&outcome.opportunities, config.alerts.high_profit_threshold);
+    }
+}
+
+fn print_csv(outcome: &ScanOutcome, with_headers: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let rows: Vec<CsvRow> = outcome.opportunities.iter().map(CsvRow::from_opportunity).collect();
+    let csv = CsvExporter::to_csv(&rows)?;
+
+    if with_headers {
+        print!("{}", csv);
+    } else if let Some((_, body)) = csv.split_once('\n') {
+        print!("{}", body);
+    }
+    Ok(())
+}
+
+fn print_json(outcome: &ScanOutcome) {
+    match serde_json::to_string_pretty(outcome) {
+        Ok(json) => println!("{}", json),
+        Err(e) => eprintln!("❌ Failed to serialize scan: {}", e),
+    }
+}
@@ -0,0 +1,697 @@
+use crate::models::{LegMode, TimeInForce};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+pub mod reload;
+
+pub use reload::SharedConfig;
+
+pub const DEFAULT_CONFIG_FILE: &str = "config.toml";
+
+// Keys accepted under `[exchanges.<key>]`; also the `<KEY>` in
+// `ARB_EXCHANGES_<KEY>_*` environment overrides.
+pub const EXCHANGE_KEYS: [&str; 7] = ["binance", "bybit", "kucoin", "gateio", "kraken", "coinbase", "mexc"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub exchanges: HashMap<String, ExchangeConfig>,
+    pub detector: DetectorConfig,
+    pub alerts: AlertConfig,
+    pub export: ExportConfig,
+    pub logging: LoggingConfig,
+    pub storage: StorageConfig,
+    pub recorder: RecorderConfig,
+    pub paper: PaperConfig,
+    pub execution: ExecutionConfig,
+    pub funding: FundingConfig,
+    pub risk: RiskConfig,
+    pub scanner: ScannerConfig,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub bind: String,
+    pub port: u16,
+    // Bearer token for the guarded routes (see `guard`); they refuse every
+    // request while it is unset.
+    pub admin_token: Option<String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind: "0.0.0.0".to_string(),
+            port: 8080,
+            admin_token: None,
+        }
+    }
+}
+
+// Same rule as ExchangeConfig: the token never reaches logs.
+impl std::fmt::Debug for ServerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ServerConfig")
+            .field("bind", &self.bind)
+            .field("port", &self.port)
+            .field("admin_token", &self.admin_token.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExchangeConfig {
+    pub enabled: Option<bool>, // unset keeps the adapter's default: on, except Coinbase
+    pub api_url: Option<String>,
+    pub taker_fee: Option<f64>, // percent per trade; falls back to `detector.fee_rate`
+    pub api_key: Option<String>,
+    pub api_secret: Option<String>,
+    pub passphrase: Option<String>,
+}
+
+// Credentials must never reach logs, so Debug only reports whether they are set.
+impl std::fmt::Debug for ExchangeConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let redact = |value: &Option<String>| if value.is_some() { "<redacted>" } else { "<unset>" };
+        f.debug_struct("ExchangeConfig")
+            .field("enabled", &self.enabled)
+            .field("api_url", &self.api_url)
+            .field("taker_fee", &self.taker_fee)
+            .field("api_key", &redact(&self.api_key))
+            .field("api_secret", &redact(&self.api_secret))
+            .field("passphrase", &redact(&self.passphrase))
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DetectorConfig {
+    pub fee_rate: f64, // percent per trade
+    pub max_results: usize,
+    pub default_min_profit: f64,
+}
+
+impl Default for DetectorConfig {
+    fn default() -> Self {
+        Self {
+            fee_rate: 0.1,
+            max_results: 20,
+            default_min_profit: 0.1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AlertConfig {
+    pub high_profit_threshold: f64, // 0 disables alerts
+    pub enable_sound: bool,
+    pub cooldown_cycles: u64, // scans of the same exchange before a path can alert again
+    pub webhook_url: Option<String>,
+    pub discord_webhook_url: Option<String>,
+    pub slack_webhook_url: Option<String>,
+    pub telegram: Option<TelegramConfig>,
+    pub rules_file: String, // JSON file holding the rules managed through /api/v1/alerts
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            high_profit_threshold: 1.0,
+            enable_sound: false,
+            cooldown_cycles: 5,
+            webhook_url: None,
+            discord_webhook_url: None,
+            slack_webhook_url: None,
+            telegram: None,
+            rules_file: "alert_rules.json".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TelegramConfig {
+    pub bot_token: String,
+    pub chat_id: String,
+    #[serde(default = "default_telegram_api_url")]
+    pub api_url: String,
+}
+
+impl TelegramConfig {
+    pub const DEFAULT_API_URL: &'static str = "https://api.telegram.org";
+}
+
+fn default_telegram_api_url() -> String {
+    TelegramConfig::DEFAULT_API_URL.to_string()
+}
+
+// The bot token grants full control of the bot, so keep it out of logs.
+impl std::fmt::Debug for TelegramConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TelegramConfig")
+            .field("bot_token", &"<redacted>")
+            .field("chat_id", &self.chat_id)
+            .field("api_url", &self.api_url)
+            .finish()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExportConfig {
+    pub csv_enabled: bool, // append every web scan to the CSV export (off by default)
+    pub directory: String,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            csv_enabled: false,
+            directory: "exports".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoggingConfig {
+    pub scan_log_enabled: bool, // write a JSON line per web scan
+    pub directory: String,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            scan_log_enabled: true,
+            directory: "logs".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageConfig {
+    pub enabled: bool, // record scans, opportunities and alerts in SQLite
+    pub path: String,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: "data/arbitrage.db".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RecorderConfig {
+    pub enabled: bool, // write every fetched ticker batch for later replay
+    pub directory: String,
+}
+
+impl Default for RecorderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            directory: "recordings".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PaperConfig {
+    pub enabled: bool, // simulate trading opportunities found by the background scans
+    pub exchanges: Vec<String>, // names or config keys; empty means every exchange
+    pub min_net_profit: f64,
+    pub min_notional: f64,
+    pub latency_ms: u64, // fill against the first later round at least this long after detection
+    pub trade_fraction: f64, // share of the starting asset's balance put into one trade
+    pub starting_balances: BTreeMap<String, f64>, // per asset, given to each exchange
+}
+
+impl Default for PaperConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            exchanges: Vec::new(),
+            min_net_profit: 0.5,
+            min_notional: 0.0,
+            latency_ms: 250,
+            trade_fraction: 0.1,
+            starting_balances: BTreeMap::from([
+                ("USDT".to_string(), 10_000.0),
+                ("BTC".to_string(), 0.25),
+                ("ETH".to_string(), 5.0),
+            ]),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ExecutionConfig {
+    pub dry_run: bool, // simulate orders against a fresh book instead of sending them
+    pub mode: LegMode,
+    pub time_in_force: TimeInForce,
+    pub slippage_bps: f64, // how far past the touch leg orders are priced
+    pub unwind_slippage_bps: f64,
+    pub recv_window_ms: u64, // validity window for signed requests
+}
+
+impl Default for ExecutionConfig {
+    fn default() -> Self {
+        Self {
+            dry_run: true,
+            mode: LegMode::Sequential,
+            time_in_force: TimeInForce::Ioc,
+            slippage_bps: 5.0,
+            unwind_slippage_bps: 50.0,
+            recv_window_ms: 5000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FundingConfig {
+    pub enabled: bool, // size /api/v1/scan opportunities by account balances (needs API keys)
+    pub refresh_secs: u64, // how long fetched balances are reused
+}
+
+impl Default for FundingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            refresh_secs: 30,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScannerConfig {
+    pub fetch_timeout_ms: u64, // per exchange in /api/v1/scan, balances included
+    pub background: bool, // scan enabled exchanges on a timer and serve /api/v1/scan from the results
+    pub interval_secs: u64,
+    pub min_profit: f64, // threshold of the background scans; requests below it scan live
+}
+
+impl Default for ScannerConfig {
+    fn default() -> Self {
+        Self {
+            fetch_timeout_ms: 10_000,
+            background: true,
+            interval_secs: 10,
+            min_profit: 0.0,
+        }
+    }
+}
+
+// Limits are per asset; an asset missing from a table has no limit of that
+// kind.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RiskConfig {
+    pub max_notional: BTreeMap<String, f64>, // per execution, in the path's first currency
+    pub max_daily_loss: BTreeMap<String, f64>, // realized over the UTC day
+    pub max_open_exposure: BTreeMap<String, f64>, // in flight plus left over from unwinding
+    pub max_consecutive_failures: u32, // failed legs before an exchange is blocked; 0 disables
+}
+
+impl Default for RiskConfig {
+    fn default() -> Self {
+        let limits = |usdt: f64, btc: f64, eth: f64| {
+            BTreeMap::from([
+                ("USDT".to_string(), usdt),
+                ("BTC".to_string(), btc),
+                ("ETH".to_string(), eth),
+            ])
+        };
+        Self {
+            max_notional: limits(100.0, 0.002, 0.05),
+            max_daily_loss: limits(20.0, 0.0005, 0.01),
+            max_open_exposure: limits(500.0, 0.01, 0.2),
+            max_consecutive_failures: 3,
+        }
+    }
+}
+
+impl Config {
+    /// Loads `path`, or `config.toml` when no path is given and it exists,
+    /// then applies `ARB_*` environment overrides and validates the result.
+    pub fn load(path: Option<&Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config = match path {
+            Some(path) => Self::from_file(path)?,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => Self::from_file(Path::new(DEFAULT_CONFIG_FILE))?,
+            None => Self::default(),
+        };
+
+        config.apply_env_overrides()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn from_file(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| format!("Cannot read config file {}: {}", path.display(), e))?;
+        let config: Config = toml::from_str(&contents)
+            .map_err(|e| format!("Invalid config file {}: {}", path.display(), e))?;
+        Ok(config)
+    }
+
+    fn apply_env_overrides(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(bind) = env_var("ARB_SERVER_BIND") {
+            self.server.bind = bind;
+        }
+        if let Some(port) = env_parse("ARB_SERVER_PORT")? {
+            self.server.port = port;
+        }
+        if let Some(admin_token) = env_var("ARB_SERVER_ADMIN_TOKEN") {
+            self.server.admin_token = Some(admin_token);
+        }
+        if let Some(fee_rate) = env_parse("ARB_DETECTOR_FEE_RATE")? {
+            self.detector.fee_rate = fee_rate;
+        }
+        if let Some(max_results) = env_parse("ARB_DETECTOR_MAX_RESULTS")? {
+            self.detector.max_results = max_results;
+        }
+        if let Some(min_profit) = env_parse("ARB_DETECTOR_DEFAULT_MIN_PROFIT")? {
+            self.detector.default_min_profit = min_profit;
+        }
+        if let Some(threshold) = env_parse("ARB_ALERTS_HIGH_PROFIT_THRESHOLD")? {
+            self.alerts.high_profit_threshold = threshold;
+        }
+        if let Some(enable_sound) = env_parse("ARB_ALERTS_ENABLE_SOUND")? {
+            self.alerts.enable_sound = enable_sound;
+        }
+        if let Some(cooldown_cycles) = env_parse("ARB_ALERTS_COOLDOWN_CYCLES")? {
+            self.alerts.cooldown_cycles = cooldown_cycles;
+        }
+        if let Some(webhook_url) = env_var("ARB_ALERTS_WEBHOOK_URL") {
+            self.alerts.webhook_url = Some(webhook_url);
+        }
+        if let Some(webhook_url) = env_var("ARB_ALERTS_DISCORD_WEBHOOK_URL") {
+            self.alerts.discord_webhook_url = Some(webhook_url);
+        }
+        if let Some(webhook_url) = env_var("ARB_ALERTS_SLACK_WEBHOOK_URL") {
+            self.alerts.slack_webhook_url = Some(webhook_url);
+        }
+        if let (Some(bot_token), Some(chat_id)) = (
+            env_var("ARB_ALERTS_TELEGRAM_BOT_TOKEN"),
+            env_var("ARB_ALERTS_TELEGRAM_CHAT_ID"),
+        ) {
+            self.alerts.telegram = Some(TelegramConfig {
+                bot_token,
+                chat_id,
+                api_url: env_var("ARB_ALERTS_TELEGRAM_API_URL").unwrap_or_else(default_telegram_api_url),
+            });
+        }
+        if let Some(rules_file) = env_var("ARB_ALERTS_RULES_FILE") {
+            self.alerts.rules_file = rules_file;
+        }
+        if let Some(csv_enabled) = env_parse("ARB_EXPORT_CSV_ENABLED")? {
+            self.export.csv_enabled = csv_enabled;
+        }
+        if let Some(directory) = env_var("ARB_EXPORT_DIRECTORY") {
+            self.export.directory = directory;
+        }
+        if let Some(scan_log_enabled) = env_parse("ARB_LOGGING_SCAN_LOG_ENABLED")? {
+            self.logging.scan_log_enabled = scan_log_enabled;
+        }
+        if let Some(directory) = env_var("ARB_LOGGING_DIRECTORY") {
+            self.logging.directory = directory;
+        }
+        if let Some(enabled) = env_parse("ARB_STORAGE_ENABLED")? {
+            self.storage.enabled = enabled;
+        }
+        if let Some(path) = env_var("ARB_STORAGE_PATH") {
+            self.storage.path = path;
+        }
+        if let Some(enabled) = env_parse("ARB_RECORDER_ENABLED")? {
+            self.recorder.enabled = enabled;
+        }
+        if let Some(directory) = env_var("ARB_RECORDER_DIRECTORY") {
+            self.recorder.directory = directory;
+        }
+        if let Some(enabled) = env_parse("ARB_PAPER_ENABLED")? {
+            self.paper.enabled = enabled;
+        }
+        if let Some(min_net_profit) = env_parse("ARB_PAPER_MIN_NET_PROFIT")? {
+            self.paper.min_net_profit = min_net_profit;
+        }
+        if let Some(latency_ms) = env_parse("ARB_PAPER_LATENCY_MS")? {
+            self.paper.latency_ms = latency_ms;
+        }
+        if let Some(trade_fraction) = env_parse("ARB_PAPER_TRADE_FRACTION")? {
+            self.paper.trade_fraction = trade_fraction;
+        }
+        if let Some(dry_run) = env_parse("ARB_EXECUTION_DRY_RUN")? {
+            self.execution.dry_run = dry_run;
+        }
+        if let Some(enabled) = env_parse("ARB_FUNDING_ENABLED")? {
+            self.funding.enabled = enabled;
+        }
+        if let Some(refresh_secs) = env_parse("ARB_FUNDING_REFRESH_SECS")? {
+            self.funding.refresh_secs = refresh_secs;
+        }
+        if let Some(max_failures) = env_parse("ARB_RISK_MAX_CONSECUTIVE_FAILURES")? {
+            self.risk.max_consecutive_failures = max_failures;
+        }
+        if let Some(fetch_timeout_ms) = env_parse("ARB_SCANNER_FETCH_TIMEOUT_MS")? {
+            self.scanner.fetch_timeout_ms = fetch_timeout_ms;
+        }
+        if let Some(background) = env_parse("ARB_SCANNER_BACKGROUND")? {
+            self.scanner.background = background;
+        }
+        if let Some(interval_secs) = env_parse("ARB_SCANNER_INTERVAL_SECS")? {
+            self.scanner.interval_secs = interval_secs;
+        }
+        if let Some(min_profit) = env_parse("ARB_SCANNER_MIN_PROFIT")? {
+            self.scanner.min_profit = min_profit;
+        }
+
+        for key in EXCHANGE_KEYS {
+            let prefix = format!("ARB_EXCHANGES_{}", key.to_uppercase());
+            let has_override = std::env::vars().any(|(name, _)| name.starts_with(&prefix));
+            if !has_override {
+                continue;
+            }
+
+            let exchange = self.exchanges.entry(key.to_string()).or_default();
+            if let Some(enabled) = env_parse(&format!("{}_ENABLED", prefix))? {
+                exchange.enabled = Some(enabled);
+            }
+            if let Some(api_url) = env_var(&format!("{}_API_URL", prefix)) {
+                exchange.api_url = Some(api_url);
+            }
+            if let Some(taker_fee) = env_parse(&format!("{}_TAKER_FEE", prefix))? {
+                exchange.taker_fee = Some(taker_fee);
+            }
+            if let Some(api_key) = env_var(&format!("{}_API_KEY", prefix)) {
+                exchange.api_key = Some(api_key);
+            }
+            if let Some(api_secret) = env_var(&format!("{}_API_SECRET", prefix)) {
+                exchange.api_secret = Some(api_secret);
+            }
+            if let Some(passphrase) = env_var(&format!("{}_PASSPHRASE", prefix)) {
+                exchange.passphrase = Some(passphrase);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let mut errors = Vec::new();
+
+        if self.server.bind.trim().is_empty() {
+            errors.push("server.bind must not be empty".to_string());
+        }
+        if self.server.port == 0 {
+            errors.push("server.port must be between 1 and 65535".to_string());
+        }
+        if self.server.admin_token.as_deref().is_some_and(|token| token.trim().is_empty()) {
+            errors.push("server.admin_token must not be empty".to_string());
+        }
+
+        for (key, exchange) in &self.exchanges {
+            if !EXCHANGE_KEYS.contains(&key.as_str()) {
+                errors.push(format!(
+                    "exchanges.{} is not a known exchange (expected one of: {})",
+                    key,
+                    EXCHANGE_KEYS.join(", ")
+                ));
+            }
+            if let Some(api_url) = &exchange.api_url {
+                if !api_url.starts_with("http://") && !api_url.starts_with("https://") {
+                    errors.push(format!("exchanges.{}.api_url must start with http:// or https://", key));
+                }
+            }
+            if let Some(taker_fee) = exchange.taker_fee {
+                if !(0.0..=10.0).contains(&taker_fee) {
+                    errors.push(format!("exchanges.{}.taker_fee must be between 0 and 10 (percent)", key));
+                }
+            }
+            if exchange.api_key.is_some() != exchange.api_secret.is_some() {
+                errors.push(format!("exchanges.{} needs both api_key and api_secret", key));
+            }
+        }
+
+        if !(0.0..=10.0).contains(&self.detector.fee_rate) {
+            errors.push("detector.fee_rate must be between 0 and 10 (percent)".to_string());
+        }
+        if self.detector.max_results == 0 {
+            errors.push("detector.max_results must be at least 1".to_string());
+        }
+        let min_profits = [
+            ("detector.default_min_profit", self.detector.default_min_profit),
+            ("scanner.min_profit", self.scanner.min_profit),
+        ];
+        for (name, min_profit) in min_profits {
+            if !(min_profit.is_finite() && min_profit >= 0.0) {
+                errors.push(format!("{} must be a number and not negative (percent)", name));
+            }
+        }
+        if self.alerts.rules_file.trim().is_empty() {
+            errors.push("alerts.rules_file must not be empty".to_string());
+        }
+        if self.export.directory.trim().is_empty() {
+            errors.push("export.directory must not be empty".to_string());
+        }
+        if self.logging.directory.trim().is_empty() {
+            errors.push("logging.directory must not be empty".to_string());
+        }
+        if self.storage.enabled && self.storage.path.trim().is_empty() {
+            errors.push("storage.path must not be empty".to_string());
+        }
+        if self.recorder.enabled && self.recorder.directory.trim().is_empty() {
+            errors.push("recorder.directory must not be empty".to_string());
+        }
+        if !(self.paper.trade_fraction > 0.0 && self.paper.trade_fraction <= 1.0) {
+            errors.push("paper.trade_fraction must be greater than 0 and at most 1".to_string());
+        }
+        if self.paper.starting_balances.values().any(|balance| *balance < 0.0) {
+            errors.push("paper.starting_balances must not be negative".to_string());
+        }
+        if self.scanner.fetch_timeout_ms == 0 {
+            errors.push("scanner.fetch_timeout_ms must be greater than 0".to_string());
+        }
+        if self.scanner.interval_secs == 0 {
+            errors.push("scanner.interval_secs must be at least 1".to_string());
+        }
+        let risk_tables = [
+            ("max_notional", &self.risk.max_notional),
+            ("max_daily_loss", &self.risk.max_daily_loss),
+            ("max_open_exposure", &self.risk.max_open_exposure),
+        ];
+        for (name, limits) in risk_tables {
+            if limits.values().any(|limit| !(limit.is_finite() && *limit >= 0.0)) {
+                errors.push(format!("risk.{} limits must be finite and not negative", name));
+            }
+        }
+        if !(0.0..=1000.0).contains(&self.execution.slippage_bps)
+            || !(0.0..=1000.0).contains(&self.execution.unwind_slippage_bps)
+        {
+            errors.push("execution.slippage_bps and execution.unwind_slippage_bps must be between 0 and 1000".to_string());
+        }
+        if self.alerts.high_profit_threshold < 0.0 {
+            errors.push("alerts.high_profit_threshold must not be negative".to_string());
+        }
+        let alert_urls = [
+            ("alerts.webhook_url", self.alerts.webhook_url.as_ref()),
+            ("alerts.discord_webhook_url", self.alerts.discord_webhook_url.as_ref()),
+            ("alerts.slack_webhook_url", self.alerts.slack_webhook_url.as_ref()),
+            ("alerts.telegram.api_url", self.alerts.telegram.as_ref().map(|t| &t.api_url)),
+        ];
+        for (name, url) in alert_urls {
+            if let Some(url) = url {
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    errors.push(format!("{} must start with http:// or https://", name));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.join("; ").into())
+        }
+    }
+
+    /// Settings for one exchange, with defaults filled in for anything the
+    /// config leaves out.
+    pub fn exchange(&self, key: &str) -> ExchangeConfig {
+        let mut exchange = self.exchanges.get(key).cloned().unwrap_or_default();
+        if exchange.taker_fee.is_none() {
+            exchange.taker_fee = Some(self.detector.fee_rate);
+        }
+        exchange
+    }
+
+    pub fn bind_address(&self) -> (String, u16) {
+        (self.server.bind.clone(), self.server.port)
+    }
+}
+
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+fn env_parse<T>(name: &str) -> Result<Option<T>, Box<dyn std::error::Error>>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+{
+    match env_var(name) {
+        Some(value) => value
+            .parse::<T>()
+            .map(Some)
+            .map_err(|e| format!("Invalid value for {}: {} ({})", name, value, e).into()),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejects(change: impl FnOnce(&mut Config), expected: &str) {
+        let mut config = Config::default();
+        change(&mut config);
+        let error = config.validate().unwrap_err().to_string();
+        assert!(error.contains(expected), "{}", error);
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        Config::default().validate().unwrap();
+    }
+
+    #[test]
+    fn min_profits_must_be_numbers_and_not_negative() {
+        rejects(|c| c.detector.default_min_profit = -0.1, "detector.default_min_profit");
+        rejects(|c| c.detector.default_min_profit = f64::NAN, "detector.default_min_profit");
+        rejects(|c| c.scanner.min_profit = -1.0, "scanner.min_profit");
+        rejects(|c| c.scanner.min_profit = f64::INFINITY, "scanner.min_profit");
+    }
+
+    #[test]
+    fn trade_fraction_is_a_share() {
+        rejects(|c| c.paper.trade_fraction = 0.0, "paper.trade_fraction");
+        rejects(|c| c.paper.trade_fraction = 1.5, "paper.trade_fraction");
+        rejects(|c| c.paper.trade_fraction = f64::NAN, "paper.trade_fraction");
+
+        let mut config = Config::default();
+        config.paper.trade_fraction = 1.0;
+        config.validate().unwrap();
+    }
+}
@@ -0,0 +1,114 @@
+use crate::config::{Config, DEFAULT_CONFIG_FILE};
+use crate::currency::CurrencyNormalizer;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+const WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The live configuration. Readers take a cheap `Arc` snapshot per request,
+/// and a reload swaps the whole `Config` at once, so a scan never sees a mix
+/// of old and new settings.
+pub struct SharedConfig {
+    current: RwLock<Arc<Config>>,
+    path: Option<PathBuf>,
+}
+
+impl SharedConfig {
+    pub fn new(config: Config, path: Option<PathBuf>) -> Self {
+        Self {
+            current: RwLock::new(Arc::new(config)),
+            path,
+        }
+    }
+
+    pub fn current(&self) -> Arc<Config> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Re-reads the config file, environment and currency alias file,
+    /// validates the result and swaps it in. On error the running config and
+    /// aliases are left untouched.
+    pub fn reload(&self) -> Result<Arc<Config>, Box<dyn std::error::Error>> {
+        let config = Arc::new(Config::load(self.path.as_deref())?);
+        let normalizer = CurrencyNormalizer::from_env()?;
+        CurrencyNormalizer::set_global(normalizer);
+        let previous = std::mem::replace(&mut *self.current.write().unwrap(), config.clone());
+
+        for setting in restart_required(&previous, &config) {
+            log::warn!("{} changed; the new value takes effect after a restart", setting);
+        }
+        log::info!("configuration reloaded");
+
+        Ok(config)
+    }
+
+    pub fn watched_path(&self) -> PathBuf {
+        self.path
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(DEFAULT_CONFIG_FILE))
+    }
+}
+
+// Settings the server reads once at startup, which a reload can't change.
+fn restart_required(previous: &Config, config: &Config) -> Vec<&'static str> {
+    [
+        ("server.bind/server.port", previous.bind_address() != config.bind_address()),
+        ("export.directory", previous.export.directory != config.export.directory),
+        ("logging.directory", previous.logging.directory != config.logging.directory),
+        ("storage.enabled", previous.storage.enabled != config.storage.enabled),
+        ("storage.path", previous.storage.path != config.storage.path),
+        ("alerts.rules_file", previous.alerts.rules_file != config.alerts.rules_file),
+        ("recorder.directory", previous.recorder.directory != config.recorder.directory),
+    ]
+    .into_iter()
+    .filter(|(_, changed)| *changed)
+    .map(|(setting, _)| setting)
+    .collect()
+}
+
+/// Polls the config file's modification time and reloads whenever it changes.
+pub async fn watch(shared: Arc<SharedConfig>) {
+    let path = shared.watched_path();
+    let mut last_modified = modified(&path);
+    let mut interval = tokio::time::interval(WATCH_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let modified = modified(&path);
+        if modified == last_modified {
+            continue;
+        }
+        last_modified = modified;
+
+        if let Err(e) = shared.reload() {
+            log::error!("config reload from {} rejected: {}", path.display(), e);
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn startup_settings_are_reported() {
+        let previous = Config::default();
+        let mut config = Config::default();
+        config.scanner.interval_secs += 1;
+        assert!(restart_required(&previous, &config).is_empty());
+
+        config.server.port += 1;
+        config.storage.path = "other.db".to_string();
+        config.alerts.rules_file = "other.json".to_string();
+        assert_eq!(
+            restart_required(&previous, &config),
+            ["server.bind/server.port", "storage.path", "alerts.rules_file"]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use crate::models::Ticker;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+
+const DEFAULT_ALIAS_FILE: &str = "aliases.json";
+
+static GLOBAL_NORMALIZER: OnceLock<RwLock<Arc<CurrencyNormalizer>>> = OnceLock::new();
+
+// Layout of the alias file: `global` applies to every venue, `venues` is keyed
+// by lower-cased exchange name and wins over `global`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AliasTable {
+    #[serde(default)]
+    pub global: HashMap<String, String>,
+    #[serde(default)]
+    pub venues: HashMap<String, HashMap<String, String>>,
+}
+
+pub struct CurrencyNormalizer {
+    global: HashMap<String, String>,
+    venues: HashMap<String, HashMap<String, String>>,
+    collisions: Mutex<HashSet<(String, String)>>, // (venue, symbol) already warned about
+}
+
+impl CurrencyNormalizer {
+    pub fn builtin() -> Self {
+        let global = [
+            ("XBT", "BTC"),
+            ("XXBT", "BTC"),
+            ("XDG", "DOGE"),
+            ("XXDG", "DOGE"),
+            ("BCHABC", "BCH"),
+            ("BCC", "BCH"),
+            ("MIOTA", "IOTA"),
+        ]
+        .iter()
+        .map(|(from, to)| (from.to_string(), to.to_string()))
+        .collect();
+
+        Self {
+            global,
+            venues: HashMap::new(),
+            collisions: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub fn from_table(table: AliasTable) -> Self {
+        let mut normalizer = Self::builtin();
+
+        for (from, to) in table.global {
+            normalizer.global.insert(from.to_uppercase(), to.to_uppercase());
+        }
+
+        for (venue, aliases) in table.venues {
+            let venue_aliases = normalizer.venues.entry(venue.to_lowercase()).or_default();
+            for (from, to) in aliases {
+                venue_aliases.insert(from.to_uppercase(), to.to_uppercase());
+            }
+        }
+
+        normalizer
+    }
+
+    pub fn load(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let contents = std::fs::read_to_string(path)?;
+        let table: AliasTable = serde_json::from_str(&contents)
+            .map_err(|e| format!("Invalid alias file {}: {}", path, e))?;
+        Ok(Self::from_table(table))
+    }
+
+    /// Built-in table plus the alias file named by `CURRENCY_ALIASES`, or
+    /// `aliases.json` when present.
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = std::env::var("CURRENCY_ALIASES").unwrap_or_else(|_| DEFAULT_ALIAS_FILE.to_string());
+        if !std::path::Path::new(&path).exists() {
+            return Ok(Self::builtin());
+        }
+        Self::load(&path)
+    }
+
+    /// Process-wide normalizer, loaded by `from_env` on first use and
+    /// replaced by `set_global` when the config is reloaded. Callers hold
+    /// the snapshot they got, so one scan never mixes two tables.
+    pub fn global() -> Arc<CurrencyNormalizer> {
+        Self::global_lock().read().unwrap().clone()
+    }
+
+    pub fn set_global(normalizer: CurrencyNormalizer) {
+        *Self::global_lock().write().unwrap() = Arc::new(normalizer);
+    }
+
+    fn global_lock() -> &'static RwLock<Arc<CurrencyNormalizer>> {
+        GLOBAL_NORMALIZER.get_or_init(|| {
+            let normalizer = Self::from_env().unwrap_or_else(|e| {
+                log::warn!("{}; using built-in currency aliases", e);
+                Self::builtin()
+            });
+            RwLock::new(Arc::new(normalizer))
+        })
+    }
+
+    pub fn canonical(&self, venue: &str, code: &str) -> String {
+        let code = code.to_uppercase();
+
+        if let Some(canonical) = self
+            .venues
+            .get(&venue.to_lowercase())
+            .and_then(|aliases| aliases.get(&code))
+        {
+            return canonical.clone();
+        }
+
+        self.global.get(&code).cloned().unwrap_or(code)
+    }
+
+    /// Rewrites base/quote to canonical ids and rebuilds `symbol` from them so
+    /// the detector's symbol lookups line up across venues. The venue's own
+    /// codes stay in the `venue_*` fields. Pairs that collapse onto a single
+    /// currency (e.g. WBTC/BTC with WBTC aliased to BTC) are dropped, and so
+    /// is an aliased pair that lands on a symbol the venue already lists
+    /// (WBTC/USDT onto BTC/USDT): the two are different books.
+    pub fn normalize_tickers(&self, venue: &str, tickers: &mut Vec<Ticker>) {
+        let mut aliased = Vec::with_capacity(tickers.len());
+        for ticker in tickers.iter_mut() {
+            let base = self.canonical(venue, &ticker.base_currency);
+            let quote = self.canonical(venue, &ticker.quote_currency);
+            aliased.push(base != ticker.base_currency.to_uppercase() || quote != ticker.quote_currency.to_uppercase());
+            ticker.base_currency = base;
+            ticker.quote_currency = quote;
+            ticker.symbol = format!("{}{}", ticker.base_currency, ticker.quote_currency);
+        }
+
+        let listed: HashSet<String> = tickers
+            .iter()
+            .zip(&aliased)
+            .filter(|(_, aliased)| !**aliased)
+            .map(|(t, _)| t.symbol.clone())
+            .collect();
+        let mut kept = HashSet::new();
+        let mut aliased = aliased.into_iter();
+        tickers.retain(|t| {
+            let aliased = aliased.next().unwrap_or_default();
+            if t.base_currency == t.quote_currency {
+                return false;
+            }
+            if (aliased && listed.contains(&t.symbol)) || !kept.insert(t.symbol.clone()) {
+                self.warn_collision(venue, t);
+                return false;
+            }
+            true
+        });
+    }
+
+    // Once per venue and symbol; the same listing collides on every scan.
+    fn warn_collision(&self, venue: &str, ticker: &Ticker) {
+        let key = (venue.to_string(), ticker.symbol.clone());
+        if self.collisions.lock().unwrap().insert(key) {
+            log::warn!(
+                "{}: dropping {} normalized to {}, which is already listed; check the currency aliases",
+                venue,
+                ticker.venue_symbol,
+                ticker.symbol
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::ticker;
+
+    fn normalizer(global: &[(&str, &str)]) -> CurrencyNormalizer {
+        CurrencyNormalizer::from_table(AliasTable {
+            global: global.iter().map(|(a, b)| (a.to_string(), b.to_string())).collect(),
+            venues: HashMap::new(),
+        })
+    }
+
+    #[test]
+    fn aliased_pair_does_not_replace_a_listed_one() {
+        let normalizer = normalizer(&[("WBTC", "BTC")]);
+        let mut tickers = vec![
+            ticker("WBTC", "USDT", 1.0, 1.1, 1.0),
+            ticker("BTC", "USDT", 1.0, 1.1, 1.0),
+            ticker("WBTC", "BTC", 1.0, 1.1, 1.0),
+        ];
+        normalizer.normalize_tickers("Binance", &mut tickers);
+
+        assert_eq!(tickers.len(), 1);
+        assert_eq!(tickers[0].venue_symbol, "BTCUSDT");
+    }
+
+    #[test]
+    fn aliases_rewrite_unlisted_pairs() {
+        let normalizer = normalizer(&[]);
+        let mut tickers = vec![ticker("XBT", "USDT", 1.0, 1.1, 1.0), ticker("ETH", "XBT", 1.0, 1.1, 1.0)];
+        normalizer.normalize_tickers("Kraken", &mut tickers);
+
+        let symbols: Vec<&str> = tickers.iter().map(|t| t.symbol.as_str()).collect();
+        assert_eq!(symbols, ["BTCUSDT", "ETHBTC"]);
+    }
+
+    #[test]
+    fn two_aliases_onto_one_symbol_keep_the_first() {
+        let normalizer = normalizer(&[("USDT.E", "USDT"), ("USDTE", "USDT")]);
+        let mut tickers = vec![ticker("BTC", "USDT.E", 1.0, 1.1, 1.0), ticker("BTC", "USDTE", 1.0, 1.1, 1.0)];
+        normalizer.normalize_tickers("Gate.io", &mut tickers);
+
+        assert_eq!(tickers.len(), 1);
+        assert_eq!(tickers[0].venue_symbol, "BTCUSDT.E");
+    }
+}
@@ -1,4 +1,5 @@
-use crate::exchanges::Exchange;
+use crate::config::ExchangeConfig;
+use crate::exchanges::{apply_config, Exchange, DEFAULT_TAKER_FEE};
 use crate::models::Ticker;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -22,6 +23,7 @@ pub struct BybitExchange {
     api_url: String,
     id: u32,
     enabled: bool,
+    taker_fee: f64,
 }
 
 impl BybitExchange {
@@ -31,8 +33,15 @@ impl BybitExchange {
             api_url: "https://api.bybit.com".to_string(),
             id: 2,
             enabled: true,
+            taker_fee: DEFAULT_TAKER_FEE,
         }
     }
+
+    pub fn from_config(config: &ExchangeConfig) -> Self {
+        let mut exchange = Self::new();
+        apply_config(config, &mut exchange.enabled, &mut exchange.api_url, &mut exchange.taker_fee);
+        exchange
+    }
 }
 
 #[async_trait]
@@ -73,13 +82,17 @@ impl Exchange for BybitExchange {
                             ) {
                                 let ticker = Ticker {
                                     symbol: bt.symbol.clone(),
-                                    base_currency: base,
-                                    quote_currency: quote,
+                                    base_currency: base.clone(),
+                                    quote_currency: quote.clone(),
                                     bid_price: bid,
                                     ask_price: ask,
                                     bid_qty,
                                     ask_qty,
+                                    quote_volume: None,
                                     timestamp: chrono::Utc::now().timestamp_millis() as u64,
+                                    venue_symbol: bt.symbol.clone(),
+                                    venue_base_currency: base,
+                                    venue_quote_currency: quote,
                                 };
                                 tickers.push(ticker);
                             }
@@ -95,6 +108,10 @@ impl Exchange for BybitExchange {
     fn is_enabled(&self) -> bool {
         self.enabled
     }
+
+    fn taker_fee(&self) -> f64 {
+        self.taker_fee
+    }
 }
 
 fn parse_symbol(symbol: &str) -> (String, String) {
@@ -0,0 +1,254 @@
+use crate::config::ExchangeConfig;
+use crate::exchanges::{apply_config, Exchange, DEFAULT_TAKER_FEE};
+use crate::models::Ticker;
+use async_trait::async_trait;
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+
+// Coinbase has no public bulk top-of-book endpoint, so books are fetched per
+// product in batches of this size. That is one request per product per scan,
+// so the adapter is off unless `[exchanges.coinbase] enabled = true`.
+const MAX_CONCURRENT_BOOK_REQUESTS: usize = 8;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CoinbaseProducts {
+    products: Vec<CoinbaseProduct>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CoinbaseProduct {
+    product_id: String,
+    base_currency_id: String,
+    quote_currency_id: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    trading_disabled: bool,
+    #[serde(default)]
+    approximate_quote_24h_volume: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CoinbaseProductBook {
+    pricebook: CoinbasePriceBook,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CoinbasePriceBook {
+    bids: Vec<CoinbaseBookLevel>,
+    asks: Vec<CoinbaseBookLevel>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CoinbaseBookLevel {
+    price: String,
+    size: String,
+}
+
+pub struct CoinbaseExchange {
+    name: String,
+    api_url: String,
+    id: u32,
+    enabled: bool,
+    taker_fee: f64,
+}
+
+impl CoinbaseExchange {
+    pub fn new() -> Self {
+        Self {
+            name: "Coinbase".to_string(),
+            api_url: "https://api.coinbase.com".to_string(),
+            id: 6,
+            enabled: false,
+            taker_fee: DEFAULT_TAKER_FEE,
+        }
+    }
+
+    pub fn from_config(config: &ExchangeConfig) -> Self {
+        let mut exchange = Self::new();
+        apply_config(config, &mut exchange.enabled, &mut exchange.api_url, &mut exchange.taker_fee);
+        exchange
+    }
+
+    async fn fetch_products(&self) -> Result<Vec<CoinbaseProduct>, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v3/brokerage/market/products?product_type=SPOT", self.api_url);
+
+        let response = reqwest::get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP Error: {}", response.status()).into());
+        }
+
+        let products: CoinbaseProducts = response.json().await?;
+
+        Ok(products
+            .products
+            .into_iter()
+            .filter(|p| p.status == "online" && !p.trading_disabled)
+            .collect())
+    }
+
+    async fn fetch_book(&self, product: &CoinbaseProduct) -> Option<Ticker> {
+        let url = format!(
+            "{}/api/v3/brokerage/market/product_book?product_id={}&limit=1",
+            self.api_url, product.product_id
+        );
+
+        let response = reqwest::get(&url).await.ok()?;
+
+        if !response.status().is_success() {
+            return None;
+        }
+
+        let book: CoinbaseProductBook = response.json().await.ok()?;
+        parse_book(product, &book)
+    }
+}
+
+#[async_trait]
+impl Exchange for CoinbaseExchange {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    async fn fetch_tickers(&self) -> Result<Vec<Ticker>, Box<dyn std::error::Error>> {
+        let products = self.fetch_products().await?;
+
+        let mut tickers = Vec::new();
+
+        for batch in products.chunks(MAX_CONCURRENT_BOOK_REQUESTS) {
+            let books = join_all(batch.iter().map(|product| self.fetch_book(product))).await;
+            tickers.extend(books.into_iter().flatten());
+        }
+
+        if tickers.is_empty() && !products.is_empty() {
+            return Err("Failed to fetch any Coinbase order books".into());
+        }
+
+        Ok(tickers)
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn taker_fee(&self) -> f64 {
+        self.taker_fee
+    }
+}
+
+fn parse_book(product: &CoinbaseProduct, book: &CoinbaseProductBook) -> Option<Ticker> {
+    let best_bid = book.pricebook.bids.first()?;
+    let best_ask = book.pricebook.asks.first()?;
+
+    if let (Ok(bid), Ok(ask), Ok(bid_qty), Ok(ask_qty)) = (
+        best_bid.price.parse::<f64>(),
+        best_ask.price.parse::<f64>(),
+        best_bid.size.parse::<f64>(),
+        best_ask.size.parse::<f64>(),
+    ) {
+        Some(Ticker {
+            symbol: format!("{}{}", product.base_currency_id, product.quote_currency_id),
+            base_currency: product.base_currency_id.clone(),
+            quote_currency: product.quote_currency_id.clone(),
+            bid_price: bid,
+            ask_price: ask,
+            bid_qty,
+            ask_qty,
+            quote_volume: product
+                .approximate_quote_24h_volume
+                .as_deref()
+                .and_then(|v| v.parse::<f64>().ok()),
+            timestamp: chrono::Utc::now().timestamp_millis() as u64,
+            venue_symbol: product.product_id.clone(),
+            venue_base_currency: product.base_currency_id.clone(),
+            venue_quote_currency: product.quote_currency_id.clone(),
+        })
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::serve_once;
+
+    const PRODUCTS: &str = r#"{"products":[
+        {"product_id":"BTC-USD","base_currency_id":"BTC","quote_currency_id":"USD","status":"online","approximate_quote_24h_volume":"1234567.8"},
+        {"product_id":"ETH-BTC","base_currency_id":"ETH","quote_currency_id":"BTC","status":"online","trading_disabled":true},
+        {"product_id":"SOL-USD","base_currency_id":"SOL","quote_currency_id":"USD","status":"delisted"},
+        {"product_id":"ETH-USD","base_currency_id":"ETH","quote_currency_id":"USD","status":"online"}
+    ]}"#;
+
+    fn product(product_id: &str, volume: Option<&str>) -> CoinbaseProduct {
+        let (base, quote) = product_id.split_once('-').unwrap();
+        CoinbaseProduct {
+            product_id: product_id.to_string(),
+            base_currency_id: base.to_string(),
+            quote_currency_id: quote.to_string(),
+            status: "online".to_string(),
+            trading_disabled: false,
+            approximate_quote_24h_volume: volume.map(str::to_string),
+        }
+    }
+
+    fn book(json: &str) -> CoinbaseProductBook {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn keeps_online_tradable_products() {
+        let (url, handle) = serve_once(PRODUCTS);
+        let exchange = CoinbaseExchange::from_config(&ExchangeConfig { api_url: Some(url), ..Default::default() });
+        let products = exchange.fetch_products().await.unwrap();
+        assert!(handle
+            .join()
+            .unwrap()
+            .starts_with("GET /api/v3/brokerage/market/products?product_type=SPOT "));
+
+        let ids: Vec<&str> = products.iter().map(|p| p.product_id.as_str()).collect();
+        assert_eq!(ids, ["BTC-USD", "ETH-USD"]);
+    }
+
+    #[test]
+    fn parses_the_best_levels() {
+        let ticker = parse_book(
+            &product("BTC-USD", Some("1234567.8")),
+            &book(r#"{"pricebook":{"product_id":"BTC-USD",
+                "bids":[{"price":"60000.10","size":"0.5"},{"price":"59999","size":"9"}],
+                "asks":[{"price":"60000.20","size":"0.25"}]}}"#),
+        )
+        .unwrap();
+
+        assert_eq!(ticker.symbol, "BTCUSD");
+        assert_eq!(ticker.venue_symbol, "BTC-USD");
+        assert_eq!((ticker.base_currency.as_str(), ticker.quote_currency.as_str()), ("BTC", "USD"));
+        assert_eq!((ticker.bid_price, ticker.ask_price, ticker.bid_qty, ticker.ask_qty), (60000.1, 60000.2, 0.5, 0.25));
+        assert_eq!(ticker.quote_volume, Some(1234567.8));
+    }
+
+    #[test]
+    fn an_empty_side_or_bad_level_has_no_ticker() {
+        let product = product("ETH-USD", None);
+        assert!(parse_book(&product, &book(r#"{"pricebook":{"bids":[],"asks":[{"price":"3001","size":"1"}]}}"#)).is_none());
+        assert!(parse_book(&product, &book(r#"{"pricebook":{"bids":[{"price":"3000","size":"1"}],"asks":[]}}"#)).is_none());
+        assert!(parse_book(
+            &product,
+            &book(r#"{"pricebook":{"bids":[{"price":"","size":"1"}],"asks":[{"price":"3001","size":"1"}]}}"#)
+        )
+        .is_none());
+
+        // A zero size is passed through; it caps the route's `max_notional` at zero.
+        let ticker = parse_book(
+            &product,
+            &book(r#"{"pricebook":{"bids":[{"price":"3000","size":"0"}],"asks":[{"price":"3001","size":"2"}]}}"#),
+        )
+        .unwrap();
+        assert_eq!((ticker.bid_qty, ticker.ask_qty, ticker.quote_volume), (0.0, 2.0, None));
+    }
+}
@@ -1,4 +1,5 @@
-use crate::exchanges::Exchange;
+use crate::config::ExchangeConfig;
+use crate::exchanges::{apply_config, Exchange, DEFAULT_TAKER_FEE};
 use crate::models::Ticker;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -22,6 +23,7 @@ pub struct GateIoExchange {
     api_url: String,
     id: u32,
     enabled: bool,
+    taker_fee: f64,
 }
 
 impl GateIoExchange {
@@ -31,8 +33,15 @@ impl GateIoExchange {
             api_url: "https://api.gateio.ws".to_string(),
             id: 4,
             enabled: true,
+            taker_fee: DEFAULT_TAKER_FEE,
         }
     }
+
+    pub fn from_config(config: &ExchangeConfig) -> Self {
+        let mut exchange = Self::new();
+        apply_config(config, &mut exchange.enabled, &mut exchange.api_url, &mut exchange.taker_fee);
+        exchange
+    }
 }
 
 #[async_trait]
@@ -69,13 +78,17 @@ impl Exchange for GateIoExchange {
             ) {
                 let ticker = Ticker {
                     symbol: gt.symbol.clone(),
-                    base_currency: base,
-                    quote_currency: quote,
+                    base_currency: base.clone(),
+                    quote_currency: quote.clone(),
                     bid_price: bid,
                     ask_price: ask,
                     bid_qty,
                     ask_qty,
+                    quote_volume: None,
                     timestamp: chrono::Utc::now().timestamp_millis() as u64,
+                    venue_symbol: gt.symbol.clone(),
+                    venue_base_currency: base,
+                    venue_quote_currency: quote,
                 };
                 tickers.push(ticker);
             }
@@ -87,6 +100,10 @@ impl Exchange for GateIoExchange {
     fn is_enabled(&self) -> bool {
         self.enabled
     }
+
+    fn taker_fee(&self) -> f64 {
+        self.taker_fee
+    }
 }
 
 fn parse_symbol(symbol: &str) -> (String, String) {
@@ -0,0 +1,241 @@
+use crate::config::ExchangeConfig;
+use crate::exchanges::{apply_config, Exchange, DEFAULT_TAKER_FEE};
+use crate::models::Ticker;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KrakenResponse<T> {
+    error: Vec<String>,
+    result: Option<T>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KrakenAssetPair {
+    altname: String,
+    wsname: Option<String>,
+    base: String,
+    quote: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KrakenTicker {
+    // [price, whole lot volume, lot volume]
+    #[serde(rename = "a")]
+    ask: Vec<String>,
+    #[serde(rename = "b")]
+    bid: Vec<String>,
+}
+
+pub struct KrakenExchange {
+    name: String,
+    api_url: String,
+    id: u32,
+    enabled: bool,
+    taker_fee: f64,
+}
+
+impl KrakenExchange {
+    pub fn new() -> Self {
+        Self {
+            name: "Kraken".to_string(),
+            api_url: "https://api.kraken.com".to_string(),
+            id: 5,
+            enabled: true,
+            taker_fee: DEFAULT_TAKER_FEE,
+        }
+    }
+
+    pub fn from_config(config: &ExchangeConfig) -> Self {
+        let mut exchange = Self::new();
+        apply_config(config, &mut exchange.enabled, &mut exchange.api_url, &mut exchange.taker_fee);
+        exchange
+    }
+
+    async fn fetch_asset_pairs(&self) -> Result<HashMap<String, KrakenAssetPair>, Box<dyn std::error::Error>> {
+        let url = format!("{}/0/public/AssetPairs", self.api_url);
+
+        let response = reqwest::get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP Error: {}", response.status()).into());
+        }
+
+        let pairs: KrakenResponse<HashMap<String, KrakenAssetPair>> = response.json().await?;
+
+        if !pairs.error.is_empty() {
+            return Err(format!("Kraken API Error: {}", pairs.error.join(", ")).into());
+        }
+
+        Ok(pairs.result.unwrap_or_default())
+    }
+}
+
+#[async_trait]
+impl Exchange for KrakenExchange {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    async fn fetch_tickers(&self) -> Result<Vec<Ticker>, Box<dyn std::error::Error>> {
+        let asset_pairs = self.fetch_asset_pairs().await?;
+
+        let url = format!("{}/0/public/Ticker", self.api_url);
+
+        let response = reqwest::get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP Error: {}", response.status()).into());
+        }
+
+        let ticker_response: KrakenResponse<HashMap<String, KrakenTicker>> = response.json().await?;
+
+        if !ticker_response.error.is_empty() {
+            return Err(format!("Kraken API Error: {}", ticker_response.error.join(", ")).into());
+        }
+
+        Ok(parse_tickers(&asset_pairs, ticker_response.result.unwrap_or_default()))
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn taker_fee(&self) -> f64 {
+        self.taker_fee
+    }
+}
+
+fn parse_tickers(asset_pairs: &HashMap<String, KrakenAssetPair>, result: HashMap<String, KrakenTicker>) -> Vec<Ticker> {
+    let mut tickers = Vec::new();
+
+    for (pair_name, kt) in result {
+        // The ticker endpoint keys by the pair name, but some legacy pairs
+        // are keyed by their altname instead.
+        let pair = match asset_pairs.get(&pair_name) {
+            Some(pair) => pair,
+            None => match asset_pairs.values().find(|p| p.altname == pair_name) {
+                Some(pair) => pair,
+                None => continue,
+            },
+        };
+
+        let (base, quote) = parse_pair(pair);
+
+        if let (Some(bid), Some(ask), Some(bid_qty), Some(ask_qty)) = (
+            kt.bid.first().and_then(|v| v.parse::<f64>().ok()),
+            kt.ask.first().and_then(|v| v.parse::<f64>().ok()),
+            kt.bid.get(2).and_then(|v| v.parse::<f64>().ok()),
+            kt.ask.get(2).and_then(|v| v.parse::<f64>().ok()),
+        ) {
+            let ticker = Ticker {
+                symbol: format!("{}{}", base, quote),
+                base_currency: base,
+                quote_currency: quote,
+                bid_price: bid,
+                ask_price: ask,
+                bid_qty,
+                ask_qty,
+                quote_volume: None,
+                timestamp: chrono::Utc::now().timestamp_millis() as u64,
+                venue_symbol: pair_name.clone(),
+                venue_base_currency: pair.base.clone(),
+                venue_quote_currency: pair.quote.clone(),
+            };
+            tickers.push(ticker);
+        }
+    }
+
+    tickers
+}
+
+// Asset codes as Kraken spells them; XBT, XDG and the like are mapped to
+// canonical codes by the currency normalizer.
+fn parse_pair(pair: &KrakenAssetPair) -> (String, String) {
+    // `wsname` ("XBT/USD") carries the short asset codes; fall back to the
+    // legacy `base`/`quote` codes ("XXBT", "ZUSD") when it is missing.
+    if let Some((base, quote)) = pair.wsname.as_deref().and_then(|wsname| wsname.split_once('/')) {
+        return (base.to_string(), quote.to_string());
+    }
+
+    (strip_legacy_prefix(&pair.base), strip_legacy_prefix(&pair.quote))
+}
+
+// Kraken's older assets carry a class prefix on a three-letter code: X for
+// crypto ("XXBT", "XETH"), Z for fiat ("ZUSD"). Newer assets have none.
+fn strip_legacy_prefix(asset: &str) -> String {
+    match asset.strip_prefix(['X', 'Z']) {
+        Some(code) if asset.len() == 4 => code.to_string(),
+        _ => asset.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::currency::CurrencyNormalizer;
+
+    const ASSET_PAIRS: &str = r#"{"error":[],"result":{
+        "XXBTZUSD":{"altname":"XBTUSD","wsname":"XBT/USD","base":"XXBT","quote":"ZUSD"},
+        "XETHXXBT":{"altname":"ETHXBT","base":"XETH","quote":"XXBT"},
+        "DOTUSD":{"altname":"DOTUSD","wsname":"DOT/USD","base":"DOT","quote":"ZUSD"},
+        "XXDGZEUR":{"altname":"XDGEUR","wsname":"XDG/EUR","base":"XXDG","quote":"ZEUR"}
+    }}"#;
+
+    const TICKER: &str = r#"{"error":[],"result":{
+        "XXBTZUSD":{"a":["60010.5","1","1.500"],"b":["60000.1","2","2.250"],"c":["60005.0","0.1"]},
+        "ETHXBT":{"a":["0.0501","3","3.000"],"b":["0.0500","4","4.000"]},
+        "DOTUSD":{"a":["5.1"],"b":["5.0","10","10.0"]},
+        "XXDGZEUR":{"a":["0.15","100","100"],"b":["abc","100","100"]},
+        "UNKNOWN":{"a":["1","1","1"],"b":["1","1","1"]}
+    }}"#;
+
+    fn tickers() -> Vec<Ticker> {
+        let pairs: KrakenResponse<HashMap<String, KrakenAssetPair>> = serde_json::from_str(ASSET_PAIRS).unwrap();
+        let response: KrakenResponse<HashMap<String, KrakenTicker>> = serde_json::from_str(TICKER).unwrap();
+        let mut tickers = parse_tickers(&pairs.result.unwrap(), response.result.unwrap());
+        tickers.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        tickers
+    }
+
+    #[test]
+    fn parses_wsname_altname_and_the_ticker_arrays() {
+        let tickers = tickers();
+        // DOT has no ask quantity, XDG an unparsable bid, UNKNOWN no pair.
+        let symbols: Vec<&str> = tickers.iter().map(|t| t.symbol.as_str()).collect();
+        assert_eq!(symbols, ["ETHXBT", "XBTUSD"]);
+
+        let btc = &tickers[1];
+        assert_eq!((btc.base_currency.as_str(), btc.quote_currency.as_str()), ("XBT", "USD"));
+        assert_eq!((btc.bid_price, btc.bid_qty, btc.ask_price, btc.ask_qty), (60000.1, 2.25, 60010.5, 1.5));
+        assert_eq!(btc.venue_symbol, "XXBTZUSD");
+        assert_eq!((btc.venue_base_currency.as_str(), btc.venue_quote_currency.as_str()), ("XXBT", "ZUSD"));
+
+        // Keyed by altname and without a wsname: the legacy codes lose
+        // their prefix.
+        let eth = &tickers[0];
+        assert_eq!((eth.base_currency.as_str(), eth.quote_currency.as_str()), ("ETH", "XBT"));
+        assert_eq!(eth.venue_symbol, "ETHXBT");
+    }
+
+    #[test]
+    fn normalizer_maps_kraken_codes() {
+        let mut tickers = tickers();
+        CurrencyNormalizer::builtin().normalize_tickers("Kraken", &mut tickers);
+        let symbols: Vec<&str> = tickers.iter().map(|t| t.symbol.as_str()).collect();
+        assert_eq!(symbols, ["ETHBTC", "BTCUSD"]);
+    }
+
+    #[test]
+    fn only_legacy_codes_lose_their_prefix() {
+        assert_eq!(strip_legacy_prefix("XXBT"), "XBT");
+        assert_eq!(strip_legacy_prefix("ZUSD"), "USD");
+        assert_eq!(strip_legacy_prefix("XTZ"), "XTZ");
+        assert_eq!(strip_legacy_prefix("DOT"), "DOT");
+    }
+}
@@ -0,0 +1,215 @@
+use crate::config::ExchangeConfig;
+use crate::exchanges::{apply_config, Exchange, DEFAULT_TAKER_FEE};
+use crate::models::Ticker;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MexcExchangeInfo {
+    symbols: Vec<MexcSymbol>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MexcSymbol {
+    symbol: String,
+    status: String,
+    #[serde(rename = "baseAsset")]
+    base_asset: String,
+    #[serde(rename = "quoteAsset")]
+    quote_asset: String,
+    #[serde(rename = "isSpotTradingAllowed", default)]
+    is_spot_trading_allowed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct MexcTicker {
+    #[serde(rename = "symbol")]
+    symbol: String,
+    #[serde(rename = "bidPrice")]
+    bid_price: Option<String>,
+    #[serde(rename = "askPrice")]
+    ask_price: Option<String>,
+    #[serde(rename = "bidQty")]
+    bid_qty: Option<String>,
+    #[serde(rename = "askQty")]
+    ask_qty: Option<String>,
+    #[serde(rename = "quoteVolume")]
+    quote_volume: Option<String>,
+}
+
+pub struct MexcExchange {
+    name: String,
+    api_url: String,
+    id: u32,
+    enabled: bool,
+    taker_fee: f64,
+}
+
+impl MexcExchange {
+    pub fn new() -> Self {
+        Self {
+            name: "MEXC".to_string(),
+            api_url: "https://api.mexc.com".to_string(),
+            id: 7,
+            enabled: true,
+            taker_fee: DEFAULT_TAKER_FEE,
+        }
+    }
+
+    pub fn from_config(config: &ExchangeConfig) -> Self {
+        let mut exchange = Self::new();
+        apply_config(config, &mut exchange.enabled, &mut exchange.api_url, &mut exchange.taker_fee);
+        exchange
+    }
+
+    async fn fetch_symbols(&self) -> Result<HashMap<String, MexcSymbol>, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v3/exchangeInfo", self.api_url);
+
+        let response = reqwest::get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP Error: {}", response.status()).into());
+        }
+
+        let info: MexcExchangeInfo = response.json().await?;
+
+        // MEXC reports an enabled symbol as status "1" (older responses use "ENABLED").
+        Ok(info
+            .symbols
+            .into_iter()
+            .filter(|s| s.is_spot_trading_allowed && (s.status == "1" || s.status == "ENABLED"))
+            .map(|s| (s.symbol.clone(), s))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl Exchange for MexcExchange {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    async fn fetch_tickers(&self) -> Result<Vec<Ticker>, Box<dyn std::error::Error>> {
+        let symbols = self.fetch_symbols().await?;
+
+        // The 24hr ticker carries the top of book as well as the quote volume,
+        // so a single request covers both.
+        let url = format!("{}/api/v3/ticker/24hr", self.api_url);
+
+        let response = reqwest::get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP Error: {}", response.status()).into());
+        }
+
+        let mexc_tickers: Vec<MexcTicker> = response.json().await?;
+
+        Ok(parse_tickers(&symbols, mexc_tickers))
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn taker_fee(&self) -> f64 {
+        self.taker_fee
+    }
+}
+
+fn parse_tickers(symbols: &HashMap<String, MexcSymbol>, mexc_tickers: Vec<MexcTicker>) -> Vec<Ticker> {
+    let mut tickers = Vec::new();
+
+    for mt in mexc_tickers {
+        let symbol_info = match symbols.get(&mt.symbol) {
+            Some(info) => info,
+            None => continue,
+        };
+
+        if let (Some(bid), Some(ask), Some(bid_qty), Some(ask_qty)) = (
+            mt.bid_price.as_deref().and_then(|v| v.parse::<f64>().ok()),
+            mt.ask_price.as_deref().and_then(|v| v.parse::<f64>().ok()),
+            mt.bid_qty.as_deref().and_then(|v| v.parse::<f64>().ok()),
+            mt.ask_qty.as_deref().and_then(|v| v.parse::<f64>().ok()),
+        ) {
+            let ticker = Ticker {
+                symbol: mt.symbol.clone(),
+                base_currency: symbol_info.base_asset.clone(),
+                quote_currency: symbol_info.quote_asset.clone(),
+                bid_price: bid,
+                ask_price: ask,
+                bid_qty,
+                ask_qty,
+                quote_volume: mt.quote_volume.as_deref().and_then(|v| v.parse::<f64>().ok()),
+                timestamp: chrono::Utc::now().timestamp_millis() as u64,
+                venue_symbol: mt.symbol.clone(),
+                venue_base_currency: symbol_info.base_asset.clone(),
+                venue_quote_currency: symbol_info.quote_asset.clone(),
+            };
+            tickers.push(ticker);
+        }
+    }
+
+    tickers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::serve_once;
+
+    const EXCHANGE_INFO: &str = r#"{"symbols":[
+        {"symbol":"BTCUSDT","status":"1","baseAsset":"BTC","quoteAsset":"USDT","isSpotTradingAllowed":true},
+        {"symbol":"ETHUSDT","status":"ENABLED","baseAsset":"ETH","quoteAsset":"USDT","isSpotTradingAllowed":true},
+        {"symbol":"ETHBTC","status":"1","baseAsset":"ETH","quoteAsset":"BTC","isSpotTradingAllowed":true},
+        {"symbol":"XRPUSDT","status":"2","baseAsset":"XRP","quoteAsset":"USDT","isSpotTradingAllowed":true},
+        {"symbol":"DOGEUSDT","status":"1","baseAsset":"DOGE","quoteAsset":"USDT"}
+    ]}"#;
+
+    const TICKERS: &str = r#"[
+        {"symbol":"BTCUSDT","bidPrice":"60000.1","askPrice":"60000.2","bidQty":"1.5","askQty":"2.5","quoteVolume":"123456.7"},
+        {"symbol":"ETHUSDT","bidPrice":"3000","askPrice":"3001","bidQty":"0","askQty":"4","quoteVolume":null},
+        {"symbol":"ETHBTC","bidPrice":null,"askPrice":"0.05","bidQty":"1","askQty":"1"},
+        {"symbol":"XRPUSDT","bidPrice":"0.5","askPrice":"0.51","bidQty":"1","askQty":"1"},
+        {"symbol":"DOGEUSDT","bidPrice":"0.1","askPrice":"0.11","bidQty":"1","askQty":"1"},
+        {"symbol":"LTCUSDT","bidPrice":"80","askPrice":"81","bidQty":"1","askQty":"1"}
+    ]"#;
+
+    #[tokio::test]
+    async fn keeps_enabled_spot_symbols() {
+        let (url, handle) = serve_once(EXCHANGE_INFO);
+        let exchange = MexcExchange::from_config(&ExchangeConfig { api_url: Some(url), ..Default::default() });
+        let symbols = exchange.fetch_symbols().await.unwrap();
+        assert!(handle.join().unwrap().starts_with("GET /api/v3/exchangeInfo "));
+
+        let mut names: Vec<&str> = symbols.keys().map(String::as_str).collect();
+        names.sort();
+        assert_eq!(names, ["BTCUSDT", "ETHBTC", "ETHUSDT"]);
+    }
+
+    #[test]
+    fn parses_the_book_and_drops_incomplete_tickers() {
+        let info: MexcExchangeInfo = serde_json::from_str(EXCHANGE_INFO).unwrap();
+        let symbols = info.symbols.into_iter().map(|s| (s.symbol.clone(), s)).collect();
+        let mut tickers = parse_tickers(&symbols, serde_json::from_str(TICKERS).unwrap());
+        tickers.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+
+        // ETHBTC has no bid; LTCUSDT is not a known symbol.
+        let names: Vec<&str> = tickers.iter().map(|t| t.symbol.as_str()).collect();
+        assert_eq!(names, ["BTCUSDT", "DOGEUSDT", "ETHUSDT", "XRPUSDT"]);
+
+        let btc = &tickers[0];
+        assert_eq!((btc.base_currency.as_str(), btc.quote_currency.as_str()), ("BTC", "USDT"));
+        assert_eq!((btc.bid_price, btc.ask_price, btc.bid_qty, btc.ask_qty), (60000.1, 60000.2, 1.5, 2.5));
+        assert_eq!(btc.quote_volume, Some(123456.7));
+
+        // An empty side is passed through; it caps the route's `max_notional` at zero.
+        let eth = &tickers[2];
+        assert_eq!((eth.bid_qty, eth.ask_qty), (0.0, 4.0));
+        assert_eq!(eth.quote_volume, None);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::config::{Config, ExchangeConfig};
+use crate::currency::CurrencyNormalizer;
+use crate::recorder::{Recorder, RecordingExchange};
+use crate::models::Ticker;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+#[async_trait]
+pub trait Exchange: Send + Sync {
+    fn name(&self) -> &str;
+    fn id(&self) -> u32;
+    async fn fetch_tickers(&self) -> Result<Vec<Ticker>, Box<dyn std::error::Error>>;
+    fn is_enabled(&self) -> bool;
+    fn taker_fee(&self) -> f64; // percent per trade
+}
+
+/// Percent per trade, until `exchanges.<key>.taker_fee` says otherwise.
+pub const DEFAULT_TAKER_FEE: f64 = 0.1;
+
+/// Applies the `[exchanges.<key>]` settings every adapter shares.
+pub(crate) fn apply_config(config: &ExchangeConfig, enabled: &mut bool, api_url: &mut String, taker_fee: &mut f64) {
+    if let Some(on) = config.enabled {
+        *enabled = on;
+    }
+    if let Some(url) = &config.api_url {
+        *api_url = url.trim_end_matches('/').to_string();
+    }
+    if let Some(fee) = config.taker_fee {
+        *taker_fee = fee;
+    }
+}
+
+pub mod binance;
+pub mod bybit;
+pub mod kucoin;
+pub mod gateio;
+pub mod kraken;
+pub mod coinbase;
+pub mod mexc;
+pub mod replay;
+pub mod snapshot;
+
+/// Wraps an adapter so every ticker batch it returns carries canonical
+/// currency ids.
+pub struct NormalizedExchange {
+    inner: Box<dyn Exchange>,
+    normalizer: Arc<CurrencyNormalizer>,
+}
+
+impl NormalizedExchange {
+    pub fn new(inner: Box<dyn Exchange>, normalizer: Arc<CurrencyNormalizer>) -> Self {
+        Self { inner, normalizer }
+    }
+}
+
+#[async_trait]
+impl Exchange for NormalizedExchange {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn id(&self) -> u32 {
+        self.inner.id()
+    }
+
+    async fn fetch_tickers(&self) -> Result<Vec<Ticker>, Box<dyn std::error::Error>> {
+        let mut tickers = self.inner.fetch_tickers().await?;
+        self.normalizer.normalize_tickers(self.inner.name(), &mut tickers);
+        Ok(tickers)
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.inner.is_enabled()
+    }
+
+    fn taker_fee(&self) -> f64 {
+        self.inner.taker_fee()
+    }
+}
+
+/// Builds every adapter from `config`. `recorder` is the process's one
+/// recorder; it sees the fetched batches while `[recorder] enabled` is set.
+/// Lookups that never fetch pass `None`.
+pub fn build_exchanges(config: &Config, recorder: Option<&Arc<Recorder>>) -> Vec<Box<dyn Exchange>> {
+    let adapters: Vec<Box<dyn Exchange>> = vec![
+        Box::new(binance::BinanceExchange::from_config(&config.exchange("binance"))),
+        Box::new(bybit::BybitExchange::from_config(&config.exchange("bybit"))),
+        Box::new(kucoin::KucoinExchange::from_config(&config.exchange("kucoin"))),
+        Box::new(gateio::GateIoExchange::from_config(&config.exchange("gateio"))),
+        Box::new(kraken::KrakenExchange::from_config(&config.exchange("kraken"))),
+        Box::new(coinbase::CoinbaseExchange::from_config(&config.exchange("coinbase"))),
+        Box::new(mexc::MexcExchange::from_config(&config.exchange("mexc"))),
+    ];
+
+    // Record what the venue returned, before normalization, so a replay
+    // exercises the alias tables too.
+    let recorder = recorder.filter(|_| config.recorder.enabled);
+
+    let normalizer = CurrencyNormalizer::global();
+    adapters
+        .into_iter()
+        .map(|adapter| match recorder {
+            Some(recorder) => Box::new(RecordingExchange::new(adapter, recorder.clone())) as Box<dyn Exchange>,
+            None => adapter,
+        })
+        .map(|adapter| Box::new(NormalizedExchange::new(adapter, normalizer.clone())) as Box<dyn Exchange>)
+        .collect()
+}
+
+pub fn get_exchange_by_id(config: &Config, id: u32, recorder: Option<&Arc<Recorder>>) -> Option<Box<dyn Exchange>> {
+    let exchanges = build_exchanges(config, recorder);
+    exchanges.into_iter().find(|e| e.id() == id)
+}
+
+/// Lowercase alphanumeric form of an exchange name, so "Gate.io" and the
+/// config key "gateio" compare equal.
+pub fn exchange_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+/// Looks an exchange up by numeric id, name or config key.
+pub fn find_exchange(config: &Config, query: &str, recorder: Option<&Arc<Recorder>>) -> Option<Box<dyn Exchange>> {
+    if let Ok(id) = query.trim().parse::<u32>() {
+        return get_exchange_by_id(config, id, recorder);
+    }
+
+    let key = exchange_key(query);
+    build_exchanges(config, recorder)
+        .into_iter()
+        .find(|e| exchange_key(e.name()) == key)
+}
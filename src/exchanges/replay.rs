@@ -0,0 +1,126 @@
+use crate::config::Config;
+use crate::currency::CurrencyNormalizer;
+use crate::exchanges::{find_exchange, Exchange};
+use crate::models::Ticker;
+use crate::recorder::{BatchReader, RecordedBatch};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+struct ReplayState {
+    reader: BatchReader,
+    pending: Option<RecordedBatch>,
+    pending_error: Option<String>, // read error found while peeking, served on the next fetch
+    // Wall-clock instant the first batch was served, and its recorded time.
+    clock: Option<(Instant, DateTime<Utc>)>,
+}
+
+/// Plays a recording back as an exchange: each `fetch_tickers` call returns
+/// the next recorded batch, normalized the way live adapters' batches are.
+/// With a positive `speed` the batches are paced by their recorded
+/// timestamps (2.0 plays twice as fast); otherwise they are served as fast as
+/// they are asked for. Fetching past the end is an error.
+pub struct ReplayExchange {
+    name: String,
+    id: u32,
+    taker_fee: f64,
+    speed: f64,
+    state: Mutex<ReplayState>,
+}
+
+impl ReplayExchange {
+    /// Opens a recording; name, id and fee come from the recorded exchange's
+    /// config when it is known.
+    pub fn open(path: impl AsRef<Path>, speed: f64, config: &Config) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let mut reader = BatchReader::open(path)?;
+        let first = reader
+            .next()
+            .ok_or_else(|| format!("{} contains no ticker batches", path.display()))??;
+
+        let (id, taker_fee) = find_exchange(config, &first.exchange, None)
+            .map(|e| (e.id(), e.taker_fee()))
+            .unwrap_or((0, config.detector.fee_rate));
+
+        Ok(Self {
+            name: first.exchange.clone(),
+            id,
+            taker_fee,
+            speed,
+            state: Mutex::new(ReplayState {
+                reader,
+                pending: Some(first),
+                pending_error: None,
+                clock: None,
+            }),
+        })
+    }
+
+    /// True once every batch has been served.
+    pub fn is_finished(&self) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if state.pending.is_none() && state.pending_error.is_none() {
+            match state.reader.next() {
+                Some(Ok(batch)) => state.pending = Some(batch),
+                Some(Err(e)) => state.pending_error = Some(e.to_string()),
+                None => {}
+            }
+        }
+        state.pending.is_none() && state.pending_error.is_none()
+    }
+
+    fn next_batch(&self) -> Result<(RecordedBatch, Duration), Box<dyn std::error::Error>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(e) = state.pending_error.take() {
+            return Err(e.into());
+        }
+        let batch = match state.pending.take() {
+            Some(batch) => batch,
+            None => state.reader.next().ok_or("Replay finished: no more recorded batches")??,
+        };
+
+        let delay = match state.clock {
+            _ if self.speed <= 0.0 => Duration::ZERO,
+            None => {
+                state.clock = Some((Instant::now(), batch.timestamp));
+                Duration::ZERO
+            }
+            Some((started, first)) => {
+                let offset = (batch.timestamp - first).to_std().unwrap_or_default().div_f64(self.speed);
+                (started + offset).saturating_duration_since(Instant::now())
+            }
+        };
+
+        Ok((batch, delay))
+    }
+}
+
+#[async_trait]
+impl Exchange for ReplayExchange {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    async fn fetch_tickers(&self) -> Result<Vec<Ticker>, Box<dyn std::error::Error>> {
+        let (batch, delay) = self.next_batch()?;
+        tokio::time::sleep(delay).await;
+
+        let mut tickers = batch.tickers;
+        CurrencyNormalizer::global().normalize_tickers(&self.name, &mut tickers);
+        Ok(tickers)
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    fn taker_fee(&self) -> f64 {
+        self.taker_fee
+    }
+}
@@ -0,0 +1,53 @@
+use crate::exchanges::Exchange;
+use crate::models::Ticker;
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// Serves tickers from a saved JSON array of `Ticker` instead of a venue, so
+/// a snapshot can be scanned offline. Name, id and fee are borrowed from the
+/// exchange the snapshot was taken on when known.
+pub struct SnapshotExchange {
+    name: String,
+    id: u32,
+    path: PathBuf,
+    taker_fee: f64,
+}
+
+impl SnapshotExchange {
+    pub fn new(path: impl Into<PathBuf>, name: &str, id: u32, taker_fee: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            id,
+            path: path.into(),
+            taker_fee,
+        }
+    }
+}
+
+#[async_trait]
+impl Exchange for SnapshotExchange {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn id(&self) -> u32 {
+        self.id
+    }
+
+    async fn fetch_tickers(&self) -> Result<Vec<Ticker>, Box<dyn std::error::Error>> {
+        let contents = tokio::fs::read_to_string(&self.path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", self.path.display(), e))?;
+        let tickers: Vec<Ticker> = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse {}: {}", self.path.display(), e))?;
+        Ok(tickers)
+    }
+
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    fn taker_fee(&self) -> f64 {
+        self.taker_fee
+    }
+}
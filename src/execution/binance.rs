@@ -0,0 +1,164 @@
+use crate::auth::{self, Signer};
+use crate::config::{ExchangeConfig, ExecutionConfig};
+use crate::execution::{Executor, MarketRules, OrderRequest, OrderResult};
+use crate::models::{TimeInForce, TradeSide};
+use async_trait::async_trait;
+use reqwest::Method;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct ExchangeInfo {
+    symbols: Vec<SymbolInfo>,
+}
+
+#[derive(Deserialize)]
+struct SymbolInfo {
+    symbol: String,
+    filters: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderResponse {
+    order_id: u64,
+    orig_qty: String,
+    executed_qty: String,
+    cummulative_quote_qty: String,
+}
+
+pub struct BinanceExecutor {
+    name: String,
+    api_url: String,
+    signer: Option<Box<dyn Signer>>,
+    client: reqwest::Client,
+}
+
+impl BinanceExecutor {
+    pub fn from_config(config: &ExchangeConfig, settings: &ExecutionConfig) -> Self {
+        Self {
+            name: "Binance".to_string(),
+            api_url: config
+                .api_url
+                .as_deref()
+                .unwrap_or("https://api.binance.com")
+                .trim_end_matches('/')
+                .to_string(),
+            signer: auth::build_signer("binance", config, settings.recv_window_ms),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl Executor for BinanceExecutor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn market_rules(&self, symbol: &str) -> Result<MarketRules, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v3/exchangeInfo?symbol={}", self.api_url, symbol);
+        let info: ExchangeInfo = auth::binance::send(self.client.get(&url)).await?;
+        let market = info
+            .symbols
+            .into_iter()
+            .find(|s| s.symbol == symbol)
+            .ok_or_else(|| format!("Unknown symbol {}", symbol))?;
+
+        let mut rules = MarketRules {
+            symbol: symbol.to_string(),
+            quantity_step: 0.0,
+            min_quantity: 0.0,
+            min_notional: 0.0,
+            price_tick: 0.0,
+        };
+        let number = |filter: &serde_json::Value, key: &str| {
+            filter
+                .get(key)
+                .and_then(|v| v.as_str())
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or_default()
+        };
+        for filter in &market.filters {
+            match filter.get("filterType").and_then(|t| t.as_str()) {
+                Some("LOT_SIZE") => {
+                    rules.quantity_step = number(filter, "stepSize");
+                    rules.min_quantity = number(filter, "minQty");
+                }
+                Some("PRICE_FILTER") => rules.price_tick = number(filter, "tickSize"),
+                Some("NOTIONAL") | Some("MIN_NOTIONAL") => rules.min_notional = number(filter, "minNotional"),
+                _ => {}
+            }
+        }
+        Ok(rules)
+    }
+
+    async fn place_order(&self, order: &OrderRequest) -> Result<OrderResult, Box<dyn std::error::Error>> {
+        let signer = auth::require(&self.signer, &self.name)?;
+        let query = format!(
+            "symbol={}&side={}&type=LIMIT&timeInForce={}&quantity={}&price={}&newClientOrderId={}&newOrderRespType=RESULT",
+            order.symbol,
+            match order.side {
+                TradeSide::Buy => "BUY",
+                TradeSide::Sell => "SELL",
+            },
+            match order.time_in_force {
+                TimeInForce::Ioc => "IOC",
+                TimeInForce::Fok => "FOK",
+            },
+            order.quantity,
+            order.price,
+            order.client_order_id
+        );
+
+        let request = auth::signed_request(&self.client, signer, Method::POST, &self.api_url, "/api/v3/order", &query, "")?;
+        let placed: OrderResponse = auth::binance::send(request).await?;
+        let requested: f64 = placed.orig_qty.parse()?;
+        let filled: f64 = placed.executed_qty.parse()?;
+        let quote: f64 = placed.cummulative_quote_qty.parse()?;
+        let average_price = if filled > 0.0 { quote / filled } else { 0.0 };
+        Ok(OrderResult::new(placed.order_id.to_string(), requested, filled, average_price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::serve_once;
+
+    #[tokio::test]
+    async fn place_order_signs_and_reads_the_fill() {
+        let (url, server) = serve_once(
+            r#"{"orderId":42,"origQty":"0.5","executedQty":"0.25","cummulativeQuoteQty":"5000"}"#,
+        );
+        let config = ExchangeConfig {
+            api_url: Some(url),
+            api_key: Some("test-key".to_string()),
+            api_secret: Some("test-secret".to_string()),
+            ..ExchangeConfig::default()
+        };
+        let executor = BinanceExecutor::from_config(&config, &ExecutionConfig::default());
+
+        let result = executor
+            .place_order(&OrderRequest {
+                symbol: "BTCUSDT".to_string(),
+                side: TradeSide::Sell,
+                quantity: "0.5".to_string(),
+                price: "20000.00".to_string(),
+                time_in_force: TimeInForce::Ioc,
+                client_order_id: "arbtest".to_string(),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.order_id, "42");
+        assert_eq!(result.status, crate::execution::OrderStatus::PartiallyFilled);
+        assert_eq!(result.filled_quantity, 0.25);
+        assert_eq!(result.average_price, 20000.0);
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /api/v3/order?symbol=BTCUSDT&side=SELL&type=LIMIT&timeInForce=IOC&quantity=0.5&price=20000.00&newClientOrderId=arbtest"));
+        assert!(request.contains("&recvWindow=5000&timestamp="));
+        assert!(request.contains("&signature="));
+        assert!(request.to_ascii_lowercase().contains("x-mbx-apikey: test-key"));
+    }
+}
@@ -0,0 +1,207 @@
+use crate::auth::{self, Signer};
+use crate::config::{ExchangeConfig, ExecutionConfig};
+use crate::execution::{Executor, MarketRules, OrderRequest, OrderResult};
+use crate::models::{TimeInForce, TradeSide};
+use async_trait::async_trait;
+use auth::bybit::send;
+use reqwest::Method;
+use serde::Deserialize;
+use std::time::Duration;
+
+// Order state is read back after placement; IOC/FOK orders normally settle
+// within a few hundred milliseconds.
+const STATUS_POLLS: usize = 10;
+const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Deserialize)]
+struct List<T> {
+    list: Vec<T>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Instrument {
+    symbol: String,
+    lot_size_filter: LotSizeFilter,
+    price_filter: PriceFilter,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LotSizeFilter {
+    base_precision: String,
+    min_order_qty: String,
+    #[serde(default)]
+    min_order_amt: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PriceFilter {
+    tick_size: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Created {
+    order_id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderState {
+    order_status: String,
+    qty: String,
+    cum_exec_qty: String,
+    avg_price: String,
+}
+
+pub struct BybitExecutor {
+    name: String,
+    api_url: String,
+    signer: Option<Box<dyn Signer>>,
+    client: reqwest::Client,
+}
+
+impl BybitExecutor {
+    pub fn from_config(config: &ExchangeConfig, settings: &ExecutionConfig) -> Self {
+        Self {
+            name: "Bybit".to_string(),
+            api_url: config
+                .api_url
+                .as_deref()
+                .unwrap_or("https://api.bybit.com")
+                .trim_end_matches('/')
+                .to_string(),
+            signer: auth::build_signer("bybit", config, settings.recv_window_ms),
+            client: reqwest::Client::new(),
+        }
+    }
+
+}
+
+#[async_trait]
+impl Executor for BybitExecutor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn market_rules(&self, symbol: &str) -> Result<MarketRules, Box<dyn std::error::Error>> {
+        let url = format!("{}/v5/market/instruments-info?category=spot&symbol={}", self.api_url, symbol);
+        let instruments: List<Instrument> = send(self.client.get(&url)).await?;
+        let instrument = instruments
+            .list
+            .into_iter()
+            .find(|i| i.symbol == symbol)
+            .ok_or_else(|| format!("Unknown symbol {}", symbol))?;
+
+        Ok(MarketRules {
+            symbol: symbol.to_string(),
+            quantity_step: instrument.lot_size_filter.base_precision.parse()?,
+            min_quantity: instrument.lot_size_filter.min_order_qty.parse()?,
+            min_notional: instrument.lot_size_filter.min_order_amt.parse().unwrap_or_default(),
+            price_tick: instrument.price_filter.tick_size.parse()?,
+        })
+    }
+
+    async fn place_order(&self, order: &OrderRequest) -> Result<OrderResult, Box<dyn std::error::Error>> {
+        let body = serde_json::json!({
+            "category": "spot",
+            "symbol": order.symbol,
+            "side": match order.side {
+                TradeSide::Buy => "Buy",
+                TradeSide::Sell => "Sell",
+            },
+            "orderType": "Limit",
+            "qty": order.quantity,
+            "price": order.price,
+            "timeInForce": match order.time_in_force {
+                TimeInForce::Ioc => "IOC",
+                TimeInForce::Fok => "FOK",
+            },
+            "orderLinkId": order.client_order_id,
+        })
+        .to_string();
+
+        let signer = auth::require(&self.signer, &self.name)?;
+        let request = auth::signed_request(&self.client, signer, Method::POST, &self.api_url, "/v5/order/create", "", &body)?;
+        let created: Created = send(request).await?;
+
+        let query = format!("category=spot&orderId={}", created.order_id);
+        for _ in 0..STATUS_POLLS {
+            tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+            let request = auth::signed_request(&self.client, signer, Method::GET, &self.api_url, "/v5/order/realtime", &query, "")?;
+            let states: List<OrderState> = send(request).await?;
+            let Some(state) = states.list.into_iter().next() else {
+                continue;
+            };
+            if matches!(state.order_status.as_str(), "New" | "PartiallyFilled" | "Untriggered") {
+                continue;
+            }
+
+            let requested: f64 = state.qty.parse()?;
+            let filled: f64 = state.cum_exec_qty.parse()?;
+            let average_price: f64 = state.avg_price.parse().unwrap_or_default();
+            return Ok(OrderResult::new(created.order_id, requested, filled, average_price));
+        }
+
+        Err(format!("Order {} did not settle", created.order_id).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::OrderStatus;
+    use crate::test_support::{credentials, json_body, order, serve_sequence};
+
+    #[tokio::test]
+    async fn places_an_ioc_order_and_polls_until_it_settles() {
+        let (url, server) = serve_sequence(&[
+            r#"{"retCode":0,"retMsg":"OK","result":{"orderId":"b1"}}"#,
+            r#"{"retCode":0,"retMsg":"OK","result":{"list":[{"orderStatus":"PartiallyFilled","qty":"2","cumExecQty":"0.5","avgPrice":"1.4"}]}}"#,
+            r#"{"retCode":0,"retMsg":"OK","result":{"list":[{"orderStatus":"PartiallyFilledCanceled","qty":"2","cumExecQty":"0.8","avgPrice":"1.45"}]}}"#,
+        ]);
+        let executor = BybitExecutor::from_config(&credentials(url), &ExecutionConfig::default());
+
+        let result = executor.place_order(&order("ETHBTC", TradeSide::Buy, TimeInForce::Ioc)).await.unwrap();
+
+        assert_eq!(result.order_id, "b1");
+        assert_eq!(result.status, OrderStatus::PartiallyFilled);
+        assert_eq!((result.filled_quantity, result.average_price), (0.8, 1.45));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /v5/order/create "));
+        assert!(requests[0].to_ascii_lowercase().contains("x-bapi-api-key: test-key"));
+        assert_eq!(
+            json_body(&requests[0]),
+            serde_json::json!({
+                "category": "spot",
+                "symbol": "ETHBTC",
+                "side": "Buy",
+                "orderType": "Limit",
+                "qty": "2",
+                "price": "1.5",
+                "timeInForce": "IOC",
+                "orderLinkId": "arbtest",
+            })
+        );
+        assert!(requests[2].starts_with("GET /v5/order/realtime?category=spot&orderId=b1 "));
+    }
+
+    #[tokio::test]
+    async fn maps_fok_and_reads_an_unfilled_order() {
+        let (url, server) = serve_sequence(&[
+            r#"{"retCode":0,"retMsg":"OK","result":{"orderId":"b2"}}"#,
+            r#"{"retCode":0,"retMsg":"OK","result":{"list":[{"orderStatus":"Cancelled","qty":"2","cumExecQty":"0","avgPrice":""}]}}"#,
+        ]);
+        let executor = BybitExecutor::from_config(&credentials(url), &ExecutionConfig::default());
+
+        let result = executor.place_order(&order("ETHBTC", TradeSide::Sell, TimeInForce::Fok)).await.unwrap();
+
+        assert_eq!(result.status, OrderStatus::Unfilled);
+        assert_eq!((result.filled_quantity, result.average_price), (0.0, 0.0));
+        let body = json_body(&server.join().unwrap()[0]);
+        assert_eq!((body["side"].as_str(), body["timeInForce"].as_str()), (Some("Sell"), Some("FOK")));
+    }
+}
@@ -0,0 +1,164 @@
+use crate::auth::{self, Signer};
+use crate::config::{ExchangeConfig, ExecutionConfig};
+use crate::execution::{precision_step, Executor, MarketRules, OrderRequest, OrderResult};
+use crate::models::{TimeInForce, TradeSide};
+use async_trait::async_trait;
+use auth::gateio::send;
+use reqwest::Method;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct CurrencyPair {
+    amount_precision: i32,
+    precision: i32,
+    #[serde(default)]
+    min_base_amount: Option<String>,
+    #[serde(default)]
+    min_quote_amount: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PlacedOrder {
+    id: String,
+    amount: String,
+    left: String,
+    #[serde(default)]
+    filled_total: Option<String>,
+}
+
+pub struct GateIoExecutor {
+    name: String,
+    api_url: String,
+    signer: Option<Box<dyn Signer>>,
+    client: reqwest::Client,
+}
+
+impl GateIoExecutor {
+    pub fn from_config(config: &ExchangeConfig, settings: &ExecutionConfig) -> Self {
+        Self {
+            name: "Gate.io".to_string(),
+            api_url: config
+                .api_url
+                .as_deref()
+                .unwrap_or("https://api.gateio.ws")
+                .trim_end_matches('/')
+                .to_string(),
+            signer: auth::build_signer("gateio", config, settings.recv_window_ms),
+            client: reqwest::Client::new(),
+        }
+    }
+
+}
+
+#[async_trait]
+impl Executor for GateIoExecutor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn market_rules(&self, symbol: &str) -> Result<MarketRules, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v4/spot/currency_pairs/{}", self.api_url, symbol);
+        let pair: CurrencyPair = send(self.client.get(&url)).await?;
+
+        Ok(MarketRules {
+            symbol: symbol.to_string(),
+            quantity_step: precision_step(pair.amount_precision),
+            min_quantity: pair.min_base_amount.and_then(|a| a.parse().ok()).unwrap_or_default(),
+            min_notional: pair.min_quote_amount.and_then(|a| a.parse().ok()).unwrap_or_default(),
+            price_tick: precision_step(pair.precision),
+        })
+    }
+
+    async fn place_order(&self, order: &OrderRequest) -> Result<OrderResult, Box<dyn std::error::Error>> {
+        let body = serde_json::json!({
+            "text": format!("t-{}", order.client_order_id),
+            "currency_pair": order.symbol,
+            "type": "limit",
+            "account": "spot",
+            "side": match order.side {
+                TradeSide::Buy => "buy",
+                TradeSide::Sell => "sell",
+            },
+            "amount": order.quantity,
+            "price": order.price,
+            "time_in_force": match order.time_in_force {
+                TimeInForce::Ioc => "ioc",
+                TimeInForce::Fok => "fok",
+            },
+        })
+        .to_string();
+
+        let signer = auth::require(&self.signer, &self.name)?;
+        let request = auth::signed_request(&self.client, signer, Method::POST, &self.api_url, "/api/v4/spot/orders", "", &body)?;
+        let placed: PlacedOrder = send(request).await?;
+
+        // IOC and FOK orders come back already closed or cancelled.
+        let requested: f64 = placed.amount.parse()?;
+        let filled = requested - placed.left.parse::<f64>()?;
+        let total: f64 = placed.filled_total.and_then(|t| t.parse().ok()).unwrap_or_default();
+        let average_price = if filled > 0.0 { total / filled } else { 0.0 };
+        Ok(OrderResult::new(placed.id, requested, filled, average_price))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::OrderStatus;
+    use crate::test_support::{credentials, json_body, order, serve_once, serve_once_with_status};
+
+    #[tokio::test]
+    async fn places_an_ioc_order_and_reads_the_fill() {
+        let (url, server) = serve_once(r#"{"id":"g1","amount":"2","left":"0.5","filled_total":"2.25","status":"cancelled"}"#);
+        let executor = GateIoExecutor::from_config(&credentials(url), &ExecutionConfig::default());
+
+        let result = executor.place_order(&order("ETH_BTC", TradeSide::Buy, TimeInForce::Ioc)).await.unwrap();
+
+        assert_eq!(result.order_id, "g1");
+        assert_eq!(result.status, OrderStatus::PartiallyFilled);
+        assert_eq!((result.filled_quantity, result.average_price), (1.5, 1.5));
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /api/v4/spot/orders "));
+        assert!(request.to_ascii_lowercase().contains("\r\nkey: test-key"));
+        assert_eq!(
+            json_body(&request),
+            serde_json::json!({
+                "text": "t-arbtest",
+                "currency_pair": "ETH_BTC",
+                "type": "limit",
+                "account": "spot",
+                "side": "buy",
+                "amount": "2",
+                "price": "1.5",
+                "time_in_force": "ioc",
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn maps_fok_and_reads_an_unfilled_order() {
+        let (url, server) = serve_once(r#"{"id":"g2","amount":"2","left":"2","status":"cancelled"}"#);
+        let executor = GateIoExecutor::from_config(&credentials(url), &ExecutionConfig::default());
+
+        let result = executor.place_order(&order("ETH_BTC", TradeSide::Sell, TimeInForce::Fok)).await.unwrap();
+
+        assert_eq!(result.status, OrderStatus::Unfilled);
+        assert_eq!((result.filled_quantity, result.average_price), (0.0, 0.0));
+        let body = json_body(&server.join().unwrap());
+        assert_eq!((body["side"].as_str(), body["time_in_force"].as_str()), (Some("sell"), Some("fok")));
+    }
+
+    #[tokio::test]
+    async fn surfaces_the_venue_error() {
+        let (url, _server) = serve_once_with_status(
+            "400 Bad Request",
+            r#"{"label":"BALANCE_NOT_ENOUGH","message":"Not enough balance"}"#,
+        );
+        let executor = GateIoExecutor::from_config(&credentials(url), &ExecutionConfig::default());
+
+        let error = executor.place_order(&order("ETH_BTC", TradeSide::Buy, TimeInForce::Ioc)).await.unwrap_err();
+
+        assert_eq!(error.to_string(), "BALANCE_NOT_ENOUGH: Not enough balance");
+    }
+}
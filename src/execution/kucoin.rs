@@ -0,0 +1,180 @@
+use crate::auth::{self, Signer};
+use crate::config::{ExchangeConfig, ExecutionConfig};
+use crate::execution::{Executor, MarketRules, OrderRequest, OrderResult};
+use crate::models::{TimeInForce, TradeSide};
+use async_trait::async_trait;
+use auth::kucoin::send;
+use reqwest::Method;
+use serde::Deserialize;
+use std::time::Duration;
+
+const STATUS_POLLS: usize = 10;
+const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Symbol {
+    base_increment: String,
+    base_min_size: String,
+    price_increment: String,
+    #[serde(default)]
+    min_funds: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Created {
+    order_id: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderState {
+    size: String,
+    deal_size: String,
+    deal_funds: String,
+    is_active: bool,
+}
+
+pub struct KucoinExecutor {
+    name: String,
+    api_url: String,
+    signer: Option<Box<dyn Signer>>,
+    client: reqwest::Client,
+}
+
+impl KucoinExecutor {
+    pub fn from_config(config: &ExchangeConfig, settings: &ExecutionConfig) -> Self {
+        Self {
+            name: "Kucoin".to_string(),
+            api_url: config
+                .api_url
+                .as_deref()
+                .unwrap_or("https://api.kucoin.com")
+                .trim_end_matches('/')
+                .to_string(),
+            signer: auth::build_signer("kucoin", config, settings.recv_window_ms),
+            client: reqwest::Client::new(),
+        }
+    }
+
+}
+
+#[async_trait]
+impl Executor for KucoinExecutor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn market_rules(&self, symbol: &str) -> Result<MarketRules, Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v2/symbols/{}", self.api_url, symbol);
+        let market: Symbol = send(self.client.get(&url)).await?;
+
+        Ok(MarketRules {
+            symbol: symbol.to_string(),
+            quantity_step: market.base_increment.parse()?,
+            min_quantity: market.base_min_size.parse()?,
+            min_notional: market.min_funds.and_then(|f| f.parse().ok()).unwrap_or_default(),
+            price_tick: market.price_increment.parse()?,
+        })
+    }
+
+    async fn place_order(&self, order: &OrderRequest) -> Result<OrderResult, Box<dyn std::error::Error>> {
+        let body = serde_json::json!({
+            "clientOid": order.client_order_id,
+            "side": match order.side {
+                TradeSide::Buy => "buy",
+                TradeSide::Sell => "sell",
+            },
+            "symbol": order.symbol,
+            "type": "limit",
+            "price": order.price,
+            "size": order.quantity,
+            "timeInForce": match order.time_in_force {
+                TimeInForce::Ioc => "IOC",
+                TimeInForce::Fok => "FOK",
+            },
+        })
+        .to_string();
+
+        let signer = auth::require(&self.signer, &self.name)?;
+        let request = auth::signed_request(&self.client, signer, Method::POST, &self.api_url, "/api/v1/orders", "", &body)?;
+        let created: Created = send(request).await?;
+
+        let path = format!("/api/v1/orders/{}", created.order_id);
+        for _ in 0..STATUS_POLLS {
+            tokio::time::sleep(STATUS_POLL_INTERVAL).await;
+            let request = auth::signed_request(&self.client, signer, Method::GET, &self.api_url, &path, "", "")?;
+            let state: OrderState = send(request).await?;
+            if state.is_active {
+                continue;
+            }
+
+            let requested: f64 = state.size.parse()?;
+            let filled: f64 = state.deal_size.parse()?;
+            let funds: f64 = state.deal_funds.parse()?;
+            let average_price = if filled > 0.0 { funds / filled } else { 0.0 };
+            return Ok(OrderResult::new(created.order_id, requested, filled, average_price));
+        }
+
+        Err(format!("Order {} did not settle", created.order_id).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::OrderStatus;
+    use crate::test_support::{credentials, json_body, order, serve_sequence};
+
+    #[tokio::test]
+    async fn places_an_ioc_order_and_polls_until_it_settles() {
+        let (url, server) = serve_sequence(&[
+            r#"{"code":"200000","data":{"orderId":"k1"}}"#,
+            r#"{"code":"200000","data":{"size":"2","dealSize":"1","dealFunds":"1.4","isActive":true}}"#,
+            r#"{"code":"200000","data":{"size":"2","dealSize":"2","dealFunds":"2.9","isActive":false}}"#,
+        ]);
+        let executor = KucoinExecutor::from_config(&credentials(url), &ExecutionConfig::default());
+
+        let result = executor.place_order(&order("ETH-BTC", TradeSide::Buy, TimeInForce::Ioc)).await.unwrap();
+
+        assert_eq!(result.order_id, "k1");
+        assert_eq!(result.status, OrderStatus::Filled);
+        assert_eq!((result.filled_quantity, result.average_price), (2.0, 1.45));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /api/v1/orders "));
+        let head = requests[0].to_ascii_lowercase();
+        assert!(head.contains("kc-api-key: test-key"));
+        assert!(head.contains("kc-api-key-version: 2"));
+        assert_eq!(
+            json_body(&requests[0]),
+            serde_json::json!({
+                "clientOid": "arbtest",
+                "side": "buy",
+                "symbol": "ETH-BTC",
+                "type": "limit",
+                "price": "1.5",
+                "size": "2",
+                "timeInForce": "IOC",
+            })
+        );
+        assert!(requests[2].starts_with("GET /api/v1/orders/k1 "));
+    }
+
+    #[tokio::test]
+    async fn maps_fok_and_reads_an_unfilled_order() {
+        let (url, server) = serve_sequence(&[
+            r#"{"code":"200000","data":{"orderId":"k2"}}"#,
+            r#"{"code":"200000","data":{"size":"2","dealSize":"0","dealFunds":"0","isActive":false}}"#,
+        ]);
+        let executor = KucoinExecutor::from_config(&credentials(url), &ExecutionConfig::default());
+
+        let result = executor.place_order(&order("ETH-BTC", TradeSide::Sell, TimeInForce::Fok)).await.unwrap();
+
+        assert_eq!(result.status, OrderStatus::Unfilled);
+        assert_eq!((result.filled_quantity, result.average_price), (0.0, 0.0));
+        let body = json_body(&server.join().unwrap()[0]);
+        assert_eq!((body["side"].as_str(), body["timeInForce"].as_str()), (Some("sell"), Some("FOK")));
+    }
+}
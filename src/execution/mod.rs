@@ -0,0 +1,909 @@
+use crate::arbitrage::{start_currency, Route};
+use crate::config::{Config, ExecutionConfig, RiskConfig};
+use crate::exchanges::{exchange_key, Exchange};
+use crate::models::{LegMode, Ticker, TimeInForce, TradeSide, TriangularArbitrageOpportunity};
+use crate::risk::RiskManager;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub mod binance;
+pub mod bybit;
+pub mod gateio;
+pub mod kucoin;
+
+/// Order placement on one venue. Symbols are the venue's own spelling
+/// (`Ticker::venue_symbol`).
+#[async_trait]
+pub trait Executor: Send + Sync {
+    fn name(&self) -> &str;
+    async fn market_rules(&self, symbol: &str) -> Result<MarketRules, Box<dyn std::error::Error>>;
+    /// Places a limit order and returns its final state; IOC and FOK orders
+    /// are done as soon as the venue has matched them.
+    async fn place_order(&self, order: &OrderRequest) -> Result<OrderResult, Box<dyn std::error::Error>>;
+}
+
+/// Executor for `exchange`, or `None` when trading on it isn't supported.
+pub fn build_executor(config: &Config, exchange: &dyn Exchange) -> Option<Box<dyn Executor>> {
+    let key = exchange_key(exchange.name());
+    let venue = config.exchange(&key);
+    let settings = &config.execution;
+    let executor: Box<dyn Executor> = match key.as_str() {
+        "binance" => Box::new(binance::BinanceExecutor::from_config(&venue, settings)),
+        "bybit" => Box::new(bybit::BybitExecutor::from_config(&venue, settings)),
+        "kucoin" => Box::new(kucoin::KucoinExecutor::from_config(&venue, settings)),
+        "gateio" => Box::new(gateio::GateIoExecutor::from_config(&venue, settings)),
+        _ => return None,
+    };
+    Some(executor)
+}
+
+/// Lot and price constraints for one market.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketRules {
+    pub symbol: String,
+    pub quantity_step: f64, // base asset increment
+    pub min_quantity: f64,
+    pub min_notional: f64, // in the quote asset
+    pub price_tick: f64,
+}
+
+impl MarketRules {
+    pub fn round_quantity(&self, quantity: f64) -> f64 {
+        floor_to_step(quantity, self.quantity_step)
+    }
+
+    // Buys round up and sells round down, so the limit never ends up
+    // less aggressive than requested.
+    pub fn round_price(&self, price: f64, side: TradeSide) -> f64 {
+        if self.price_tick <= 0.0 {
+            return price;
+        }
+        let ticks = price / self.price_tick;
+        let ticks = match side {
+            TradeSide::Buy => (ticks - 1e-9).ceil(),
+            TradeSide::Sell => (ticks + 1e-9).floor(),
+        };
+        ticks * self.price_tick
+    }
+
+    pub fn format_quantity(&self, quantity: f64) -> String {
+        format!("{:.*}", step_decimals(self.quantity_step), quantity)
+    }
+
+    pub fn format_price(&self, price: f64) -> String {
+        format!("{:.*}", step_decimals(self.price_tick), price)
+    }
+}
+
+fn floor_to_step(value: f64, step: f64) -> f64 {
+    if step <= 0.0 {
+        return value;
+    }
+    (value / step + 1e-9).floor() * step
+}
+
+// Decimal places in a step such as 0.001; venues reject extra digits.
+fn step_decimals(step: f64) -> usize {
+    if step <= 0.0 {
+        return 8;
+    }
+    let text = format!("{}", step);
+    text.split_once('.').map_or(0, |(_, decimals)| decimals.len())
+}
+
+/// Increment for venues that publish a number of decimal places instead.
+pub(crate) fn precision_step(decimals: i32) -> f64 {
+    10f64.powi(-decimals)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OrderRequest {
+    pub symbol: String,
+    pub side: TradeSide,
+    pub quantity: String, // base asset, already rounded to the lot step
+    pub price: String,    // limit price, already rounded to the tick
+    pub time_in_force: TimeInForce,
+    pub client_order_id: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderStatus {
+    Filled,
+    PartiallyFilled,
+    Unfilled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OrderResult {
+    pub order_id: String,
+    pub status: OrderStatus,
+    pub filled_quantity: f64, // base asset
+    pub average_price: f64,
+}
+
+impl OrderResult {
+    pub(crate) fn new(order_id: String, requested: f64, filled_quantity: f64, average_price: f64) -> Self {
+        let status = if filled_quantity <= 0.0 {
+            OrderStatus::Unfilled
+        } else if filled_quantity + 1e-12 < requested {
+            OrderStatus::PartiallyFilled
+        } else {
+            OrderStatus::Filled
+        };
+        Self {
+            order_id,
+            status,
+            filled_quantity,
+            average_price,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderPurpose {
+    Leg,
+    Unwind,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutedOrder {
+    pub purpose: OrderPurpose,
+    pub request: OrderRequest,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<OrderResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExecutionStatus {
+    Completed, // every leg filled completely
+    Aborted,   // some orders went out but the cycle didn't complete
+    Failed,    // nothing was sent
+    Refused,   // stopped by the risk checks before anything else
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecutionReport {
+    pub id: String,
+    pub exchange: String,
+    pub path: String,
+    pub pairs: String,
+    pub dry_run: bool,
+    pub mode: LegMode,
+    pub time_in_force: TimeInForce,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub status: ExecutionStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub start_currency: String,
+    pub amount_in: f64,
+    pub pnl: f64, // change in the start currency, after fees and unwinding
+    pub orders: Vec<ExecutedOrder>,
+    // Other currencies left over after unwinding, such as amounts below the
+    // venue's minimum order size.
+    pub residuals: BTreeMap<String, f64>,
+}
+
+// One leg, priced and sized against the book fetched for this execution.
+struct PlannedLeg {
+    ticker: Ticker,
+    route: Route,
+    rules: MarketRules,
+}
+
+/// Sends the legs of an opportunity through an `Executor`. Before any
+/// order goes out the venue's book is fetched again, every leg is checked
+/// against it and the market's lot rules, and the cycle is re-priced: it
+/// only goes ahead while `amount` would return more than `min_profit`
+/// percent after fees at the fresh top of book. Legs are limit orders
+/// priced `slippage_bps` past the touch. Whatever the legs leave in other
+/// currencies (a failed or partly filled leg) is sold back to the starting
+/// currency through the cycle's own pairs. In dry-run mode orders fill
+/// against the fetched top of book instead of being sent. Every execution
+/// is admitted by the `RiskManager` first and settled with it afterwards.
+pub struct ExecutionEngine<'a> {
+    exchange: &'a dyn Exchange,
+    executor: &'a dyn Executor,
+    risk: &'a RiskManager,
+    limits: RiskConfig,
+    settings: ExecutionConfig,
+    min_profit: f64, // percent, net of fees
+}
+
+impl<'a> ExecutionEngine<'a> {
+    pub fn new(
+        exchange: &'a dyn Exchange,
+        executor: &'a dyn Executor,
+        risk: &'a RiskManager,
+        limits: RiskConfig,
+        settings: ExecutionConfig,
+        min_profit: f64,
+    ) -> Self {
+        Self {
+            exchange,
+            executor,
+            risk,
+            limits,
+            settings,
+            min_profit,
+        }
+    }
+
+    pub async fn execute(&self, opportunity: &TriangularArbitrageOpportunity, amount: f64) -> ExecutionReport {
+        let mut report = self.report(opportunity, amount);
+        if let Err(reason) = self.risk.admit(
+            &self.limits,
+            &report.exchange,
+            &report.path,
+            amount,
+            !self.settings.dry_run,
+        ) {
+            report.status = ExecutionStatus::Refused;
+            report.reason = Some(format!("Risk check failed: {}", reason));
+            return report;
+        }
+
+        self.run(opportunity, amount, &mut report).await;
+        self.risk.settle(&self.limits, &report);
+        log::info!(
+            "{} execution on {} {}: {:?}, {:+.8} {}{}",
+            if report.dry_run { "Dry-run" } else { "Live" },
+            report.exchange,
+            report.path,
+            report.status,
+            report.pnl,
+            report.start_currency,
+            report.reason.as_deref().map(|r| format!(" ({})", r)).unwrap_or_default()
+        );
+        report
+    }
+
+    fn report(&self, opportunity: &TriangularArbitrageOpportunity, amount: f64) -> ExecutionReport {
+        ExecutionReport {
+            id: Uuid::new_v4().to_string(),
+            exchange: self.exchange.name().to_string(),
+            path: opportunity.path.clone(),
+            pairs: opportunity.pairs.clone(),
+            dry_run: self.settings.dry_run,
+            mode: self.settings.mode,
+            time_in_force: self.settings.time_in_force,
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+            status: ExecutionStatus::Completed,
+            reason: None,
+            start_currency: start_currency(opportunity),
+            amount_in: amount,
+            pnl: 0.0,
+            orders: Vec::new(),
+            residuals: BTreeMap::new(),
+        }
+    }
+
+    async fn run(&self, opportunity: &TriangularArbitrageOpportunity, amount: f64, report: &mut ExecutionReport) {
+        let plan = match self.plan(opportunity, &report.start_currency, amount).await {
+            Ok(plan) => plan,
+            Err(e) => {
+                report.status = ExecutionStatus::Failed;
+                report.reason = Some(e);
+                report.finished_at = Utc::now();
+                return;
+            }
+        };
+
+        let mut positions: BTreeMap<String, f64> = BTreeMap::new();
+        let outcome = match self.settings.mode {
+            LegMode::Sequential => self.run_sequential(&plan, amount, &mut positions, &mut report.orders).await,
+            LegMode::Concurrent => self.run_concurrent(&plan, amount, &mut positions, &mut report.orders).await,
+        };
+        if let Err(reason) = outcome {
+            let traded = report
+                .orders
+                .iter()
+                .any(|o| o.result.as_ref().is_some_and(|r| r.filled_quantity > 0.0));
+            report.status = if traded {
+                ExecutionStatus::Aborted
+            } else {
+                ExecutionStatus::Failed
+            };
+            report.reason = Some(reason);
+        }
+
+        self.unwind(&plan, &report.start_currency, &mut positions, &mut report.orders)
+            .await;
+
+        report.pnl = positions.remove(&report.start_currency).unwrap_or_default();
+        report.residuals = positions.into_iter().filter(|(_, amount)| *amount != 0.0).collect();
+        report.finished_at = Utc::now();
+    }
+
+    async fn plan(
+        &self,
+        opportunity: &TriangularArbitrageOpportunity,
+        start: &str,
+        amount: f64,
+    ) -> Result<Vec<PlannedLeg>, String> {
+        let tickers = self
+            .exchange
+            .fetch_tickers()
+            .await
+            .map_err(|e| format!("Failed to fetch tickers: {}", e))?;
+        let book: HashMap<&str, &Ticker> = tickers.iter().map(|t| (t.symbol.as_str(), t)).collect();
+
+        let mut plan = Vec::new();
+        let mut holding = start.to_string();
+        for leg in &opportunity.legs {
+            let ticker = *book
+                .get(leg.symbol.as_str())
+                .ok_or_else(|| format!("{} is not listed on {}", leg.symbol, self.exchange.name()))?;
+            let route = Route::new(ticker, leg.side)
+                .filter(|route| route.from == holding)
+                .ok_or_else(|| format!("{} does not trade {}", leg.symbol, holding))?;
+            let rules = self
+                .executor
+                .market_rules(&ticker.venue_symbol)
+                .await
+                .map_err(|e| format!("Failed to load market rules for {}: {}", ticker.venue_symbol, e))?;
+            holding = route.to.clone();
+            plan.push(PlannedLeg {
+                ticker: ticker.clone(),
+                route,
+                rules,
+            });
+        }
+
+        if plan.is_empty() || holding != start {
+            return Err(format!("{} does not lead back to {}", opportunity.pairs, start));
+        }
+
+        let net = self.reprice(&plan, amount);
+        if net <= self.min_profit {
+            return Err(format!(
+                "Re-priced at {:.4}% net, not above the {:.4}% threshold",
+                net, self.min_profit
+            ));
+        }
+        Ok(plan)
+    }
+
+    // Net return in percent of sending `amount` around the cycle at the
+    // fetched top of book, paying the taker fee on every leg. What a leg's
+    // top of book can't take counts as lost.
+    fn reprice(&self, plan: &[PlannedLeg], amount: f64) -> f64 {
+        let fee = self.exchange.taker_fee() / 100.0;
+        let returned = plan.iter().fold(amount, |held, leg| {
+            let (_, received) = leg.route.fill(held);
+            received * (1.0 - fee)
+        });
+        (returned / amount - 1.0) * 100.0
+    }
+
+    async fn run_sequential(
+        &self,
+        plan: &[PlannedLeg],
+        amount: f64,
+        positions: &mut BTreeMap<String, f64>,
+        orders: &mut Vec<ExecutedOrder>,
+    ) -> Result<(), String> {
+        let fee = self.exchange.taker_fee() / 100.0;
+        let mut amount = amount;
+        for (index, leg) in plan.iter().enumerate() {
+            let (request, quantity) = self.order(leg, amount, self.settings.slippage_bps, self.settings.time_in_force)?;
+            let result = self.send(OrderPurpose::Leg, leg, request, quantity, orders).await?;
+            if result.filled_quantity <= 0.0 {
+                return Err(format!("Leg {} ({}) did not fill", index + 1, leg.ticker.symbol));
+            }
+            amount = apply_fill(positions, leg, &result, fee);
+        }
+
+        // A partly filled leg passes on less than planned but still
+        // completes the cycle; the remainder is unwound.
+        let partial = orders.iter().any(|o| {
+            o.purpose == OrderPurpose::Leg && o.result.as_ref().is_some_and(|r| r.status != OrderStatus::Filled)
+        });
+        if partial {
+            return Err("A leg was only partly filled".to_string());
+        }
+        Ok(())
+    }
+
+    async fn run_concurrent(
+        &self,
+        plan: &[PlannedLeg],
+        amount: f64,
+        positions: &mut BTreeMap<String, f64>,
+        orders: &mut Vec<ExecutedOrder>,
+    ) -> Result<(), String> {
+        // Size every leg up front from the quoted prices, as if the previous
+        // leg had filled completely; they all spend balances already held.
+        let fee = self.exchange.taker_fee() / 100.0;
+        let mut requests = Vec::new();
+        let mut amount = amount;
+        for leg in plan {
+            let (request, quantity) = self.order(leg, amount, self.settings.slippage_bps, self.settings.time_in_force)?;
+            amount = match leg.route.side {
+                TradeSide::Buy => quantity,
+                TradeSide::Sell => quantity * leg.route.price,
+            } * (1.0 - fee);
+            requests.push((request, quantity));
+        }
+
+        let results = join_all(
+            plan.iter()
+                .zip(&requests)
+                .map(|(leg, (request, quantity))| self.submit(leg, request, *quantity)),
+        )
+        .await;
+
+        let mut failure = None;
+        for ((leg, (request, _)), result) in plan.iter().zip(requests).zip(results) {
+            match result {
+                Ok(result) => {
+                    apply_fill(positions, leg, &result, fee);
+                    if result.status != OrderStatus::Filled && failure.is_none() {
+                        failure = Some(format!("{} was not completely filled", leg.ticker.symbol));
+                    }
+                    orders.push(ExecutedOrder {
+                        purpose: OrderPurpose::Leg,
+                        request,
+                        result: Some(result),
+                        error: None,
+                    });
+                }
+                Err(e) => {
+                    failure.get_or_insert_with(|| format!("{} failed: {}", leg.ticker.symbol, e));
+                    orders.push(ExecutedOrder {
+                        purpose: OrderPurpose::Leg,
+                        request,
+                        result: None,
+                        error: Some(e),
+                    });
+                }
+            }
+        }
+
+        failure.map_or(Ok(()), Err)
+    }
+
+    // Converts every non-start currency the legs left behind back to the
+    // start currency (or buys back what concurrent legs overspent) through
+    // the pair linking it to the start currency.
+    async fn unwind(
+        &self,
+        plan: &[PlannedLeg],
+        start: &str,
+        positions: &mut BTreeMap<String, f64>,
+        orders: &mut Vec<ExecutedOrder>,
+    ) {
+        let fee = self.exchange.taker_fee() / 100.0;
+        let residuals: Vec<(String, f64)> = positions
+            .iter()
+            .filter(|(currency, amount)| currency.as_str() != start && **amount != 0.0)
+            .map(|(currency, amount)| (currency.clone(), *amount))
+            .collect();
+
+        for (currency, residual) in residuals {
+            let Some(leg) = plan.iter().find(|leg| {
+                let ticker = &leg.ticker;
+                (ticker.base_currency == currency && ticker.quote_currency == start)
+                    || (ticker.base_currency == start && ticker.quote_currency == currency)
+            }) else {
+                log::warn!("No {}/{} market to unwind {:+} {}", currency, start, residual, currency);
+                continue;
+            };
+
+            // Spend the residual, or enough start currency to cover a shortfall.
+            let (holding, amount) = if residual > 0.0 {
+                (currency.as_str(), residual)
+            } else if leg.ticker.base_currency == currency {
+                (start, -residual * leg.ticker.ask_price)
+            } else {
+                (start, -residual / leg.ticker.bid_price)
+            };
+            let Some(route) = Route::from_holding(&leg.ticker, holding) else {
+                continue;
+            };
+            let unwind = PlannedLeg {
+                ticker: leg.ticker.clone(),
+                route,
+                rules: leg.rules.clone(),
+            };
+
+            let (request, quantity) =
+                match self.order(&unwind, amount, self.settings.unwind_slippage_bps, TimeInForce::Ioc) {
+                    Ok(order) => order,
+                    Err(e) => {
+                        log::info!("Leaving {:+} {} on {}: {}", residual, currency, self.exchange.name(), e);
+                        continue;
+                    }
+                };
+            if let Ok(result) = self.send(OrderPurpose::Unwind, &unwind, request, quantity, orders).await {
+                apply_fill(positions, &unwind, &result, fee);
+            }
+        }
+    }
+
+    // Limit order spending up to `amount` of the leg's input currency.
+    fn order(
+        &self,
+        leg: &PlannedLeg,
+        amount: f64,
+        slippage_bps: f64,
+        time_in_force: TimeInForce,
+    ) -> Result<(OrderRequest, f64), String> {
+        let rules = &leg.rules;
+        let side = leg.route.side;
+        let offset = slippage_bps / 10_000.0;
+        let price = match side {
+            TradeSide::Buy => rules.round_price(leg.route.price * (1.0 + offset), side),
+            TradeSide::Sell => rules.round_price(leg.route.price * (1.0 - offset), side),
+        };
+        let quantity = match side {
+            TradeSide::Buy => rules.round_quantity(amount / price),
+            TradeSide::Sell => rules.round_quantity(amount),
+        };
+
+        if quantity <= 0.0 || quantity < rules.min_quantity {
+            return Err(format!(
+                "{} quantity {} is below the minimum of {}",
+                rules.symbol,
+                rules.format_quantity(quantity),
+                rules.min_quantity
+            ));
+        }
+        if quantity * price < rules.min_notional {
+            return Err(format!(
+                "{} order value {:.8} is below the minimum of {}",
+                rules.symbol,
+                quantity * price,
+                rules.min_notional
+            ));
+        }
+
+        let request = OrderRequest {
+            symbol: leg.ticker.venue_symbol.clone(),
+            side,
+            quantity: rules.format_quantity(quantity),
+            price: rules.format_price(price),
+            time_in_force,
+            client_order_id: format!("arb{}", Uuid::new_v4().simple()),
+        };
+        Ok((request, quantity))
+    }
+
+    async fn send(
+        &self,
+        purpose: OrderPurpose,
+        leg: &PlannedLeg,
+        request: OrderRequest,
+        quantity: f64,
+        orders: &mut Vec<ExecutedOrder>,
+    ) -> Result<OrderResult, String> {
+        let result = self.submit(leg, &request, quantity).await;
+        orders.push(ExecutedOrder {
+            purpose,
+            request,
+            result: result.as_ref().ok().cloned(),
+            error: result.as_ref().err().cloned(),
+        });
+        result
+    }
+
+    async fn submit(&self, leg: &PlannedLeg, request: &OrderRequest, quantity: f64) -> Result<OrderResult, String> {
+        if self.settings.dry_run {
+            return Ok(simulate(leg, request, quantity));
+        }
+        log::info!(
+            "Placing {:?} {:?} {} {} @ {} on {}",
+            request.time_in_force,
+            request.side,
+            request.quantity,
+            request.symbol,
+            request.price,
+            self.executor.name()
+        );
+        self.executor
+            .place_order(request)
+            .await
+            .map_err(|e| format!("Order on {} failed: {}", request.symbol, e))
+    }
+}
+
+// Dry-run fill against the fetched top of book, at the touch price.
+fn simulate(leg: &PlannedLeg, request: &OrderRequest, quantity: f64) -> OrderResult {
+    let limit: f64 = request.price.parse().unwrap_or_default();
+    let ticker = &leg.ticker;
+    let (touch, available) = match request.side {
+        TradeSide::Buy if limit >= ticker.ask_price => (ticker.ask_price, ticker.ask_qty),
+        TradeSide::Sell if limit <= ticker.bid_price => (ticker.bid_price, ticker.bid_qty),
+        _ => (0.0, 0.0),
+    };
+    let filled = match request.time_in_force {
+        TimeInForce::Fok if available < quantity => 0.0,
+        _ => quantity.min(available),
+    };
+    OrderResult::new(format!("dry-run-{}", request.client_order_id), quantity, filled, touch)
+}
+
+// Books a fill in `positions` and returns what it delivered in the leg's
+// output currency, net of the taker fee.
+fn apply_fill(positions: &mut BTreeMap<String, f64>, leg: &PlannedLeg, result: &OrderResult, fee: f64) -> f64 {
+    let base = &leg.ticker.base_currency;
+    let quote = &leg.ticker.quote_currency;
+    let filled = result.filled_quantity;
+    let value = filled * result.average_price;
+    let received = match leg.route.side {
+        TradeSide::Buy => {
+            *positions.entry(quote.clone()).or_insert(0.0) -= value;
+            filled * (1.0 - fee)
+        }
+        TradeSide::Sell => {
+            *positions.entry(base.clone()).or_insert(0.0) -= filled;
+            value * (1.0 - fee)
+        }
+    };
+    *positions.entry(leg.route.to.clone()).or_insert(0.0) += received;
+    received
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrage::ArbitrageDetector;
+    use crate::test_support::ticker;
+    use std::sync::Mutex;
+
+    // BTC → USDT → ETH → BTC returns 40000 / 2000 * 0.0505 = 1.01 before fees.
+    fn book(eth_btc_bid: f64) -> Vec<Ticker> {
+        vec![
+            ticker("BTC", "USDT", 40000.0, 40010.0, 10.0),
+            ticker("ETH", "USDT", 1999.0, 2000.0, 100.0),
+            ticker("ETH", "BTC", eth_btc_bid, 0.0506, 100.0),
+        ]
+    }
+
+    fn opportunity() -> TriangularArbitrageOpportunity {
+        let mut found = ArbitrageDetector::find_triangular_opportunities(&book(0.0505), "Mock", 0.0, 0.1, 10);
+        assert_eq!(found.len(), 1);
+        found.remove(0)
+    }
+
+    fn rules(symbol: &str) -> MarketRules {
+        MarketRules {
+            symbol: symbol.to_string(),
+            quantity_step: 0.0001,
+            min_quantity: 0.0001,
+            min_notional: 0.0,
+            price_tick: 0.00001,
+        }
+    }
+
+    struct MockExchange {
+        tickers: Vec<Ticker>,
+    }
+
+    #[async_trait]
+    impl Exchange for MockExchange {
+        fn name(&self) -> &str {
+            "Mock"
+        }
+
+        fn id(&self) -> u32 {
+            0
+        }
+
+        async fn fetch_tickers(&self) -> Result<Vec<Ticker>, Box<dyn std::error::Error>> {
+            Ok(self.tickers.clone())
+        }
+
+        fn is_enabled(&self) -> bool {
+            true
+        }
+
+        fn taker_fee(&self) -> f64 {
+            0.1
+        }
+    }
+
+    // Fills each order by the next scripted share of its quantity at the
+    // limit price; `None` makes the venue reject the order.
+    struct ScriptedExecutor {
+        fills: Mutex<Vec<Option<f64>>>,
+        sent: Mutex<Vec<OrderRequest>>,
+    }
+
+    impl ScriptedExecutor {
+        fn new(fills: &[Option<f64>]) -> Self {
+            Self {
+                fills: Mutex::new(fills.iter().rev().copied().collect()),
+                sent: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Executor for ScriptedExecutor {
+        fn name(&self) -> &str {
+            "Mock"
+        }
+
+        async fn market_rules(&self, symbol: &str) -> Result<MarketRules, Box<dyn std::error::Error>> {
+            Ok(rules(symbol))
+        }
+
+        async fn place_order(&self, order: &OrderRequest) -> Result<OrderResult, Box<dyn std::error::Error>> {
+            self.sent.lock().unwrap().push(order.clone());
+            let share = self.fills.lock().unwrap().pop().flatten().ok_or("rejected")?;
+            let quantity: f64 = order.quantity.parse()?;
+            let price: f64 = order.price.parse()?;
+            Ok(OrderResult::new(format!("mock-{}", order.client_order_id), quantity, quantity * share, price))
+        }
+    }
+
+    fn settings(dry_run: bool) -> ExecutionConfig {
+        ExecutionConfig {
+            dry_run,
+            ..ExecutionConfig::default()
+        }
+    }
+
+    // Room for the 0.1 BTC the tests trade.
+    fn limits() -> RiskConfig {
+        let btc = |limit: f64| BTreeMap::from([("BTC".to_string(), limit)]);
+        RiskConfig {
+            max_notional: btc(1.0),
+            max_daily_loss: btc(1.0),
+            max_open_exposure: btc(1.0),
+            ..RiskConfig::default()
+        }
+    }
+
+    #[test]
+    fn quantities_round_down_to_the_step() {
+        let rules = MarketRules {
+            quantity_step: 0.001,
+            ..rules("BTCUSDT")
+        };
+        assert!((rules.round_quantity(1.23456) - 1.234).abs() < 1e-12);
+        assert!((rules.round_quantity(1.234) - 1.234).abs() < 1e-12);
+        assert_eq!(rules.format_quantity(1.234), "1.234");
+    }
+
+    #[test]
+    fn prices_round_towards_the_other_side() {
+        let rules = MarketRules {
+            price_tick: 0.01,
+            ..rules("BTCUSDT")
+        };
+        assert!((rules.round_price(100.001, TradeSide::Buy) - 100.01).abs() < 1e-9);
+        assert!((rules.round_price(100.009, TradeSide::Sell) - 100.0).abs() < 1e-9);
+        assert!((rules.round_price(100.01, TradeSide::Buy) - 100.01).abs() < 1e-9);
+        assert!((rules.round_price(100.01, TradeSide::Sell) - 100.01).abs() < 1e-9);
+        assert_eq!(rules.format_price(100.0), "100.00");
+    }
+
+    #[test]
+    fn step_decimals_follow_the_step() {
+        assert_eq!(step_decimals(0.001), 3);
+        assert_eq!(step_decimals(0.00001), 5);
+        assert_eq!(step_decimals(1.0), 0);
+        assert_eq!(step_decimals(0.0), 8);
+        assert_eq!(precision_step(2), 0.01);
+    }
+
+    #[test]
+    fn dry_run_fills_at_the_touch_up_to_its_quantity() {
+        let book = ticker("BTC", "USDT", 40000.0, 40010.0, 0.5);
+        let leg = PlannedLeg {
+            route: Route::new(&book, TradeSide::Buy).unwrap(),
+            ticker: book,
+            rules: rules("BTCUSDT"),
+        };
+        let order = |price: &str, time_in_force| OrderRequest {
+            symbol: "BTCUSDT".to_string(),
+            side: TradeSide::Buy,
+            quantity: "1.0".to_string(),
+            price: price.to_string(),
+            time_in_force,
+            client_order_id: "test".to_string(),
+        };
+
+        let ioc = simulate(&leg, &order("40100", TimeInForce::Ioc), 1.0);
+        assert_eq!(ioc.status, OrderStatus::PartiallyFilled);
+        assert_eq!(ioc.filled_quantity, 0.5);
+        assert_eq!(ioc.average_price, 40010.0);
+
+        let fok = simulate(&leg, &order("40100", TimeInForce::Fok), 1.0);
+        assert_eq!(fok.status, OrderStatus::Unfilled);
+
+        let below_ask = simulate(&leg, &order("40000", TimeInForce::Ioc), 0.1);
+        assert_eq!(below_ask.status, OrderStatus::Unfilled);
+    }
+
+    #[tokio::test]
+    async fn dry_run_completes_without_sending_orders() {
+        let exchange = MockExchange { tickers: book(0.0505) };
+        let executor = ScriptedExecutor::new(&[]);
+        let risk = RiskManager::default();
+        let report = ExecutionEngine::new(&exchange, &executor, &risk, limits(), settings(true), 0.1)
+            .execute(&opportunity(), 0.1)
+            .await;
+
+        assert_eq!(report.status, ExecutionStatus::Completed, "{:?}", report.reason);
+        assert_eq!(report.orders.len(), 3);
+        assert!(report
+            .orders
+            .iter()
+            .all(|o| o.result.as_ref().is_some_and(|r| r.order_id.starts_with("dry-run-"))));
+        assert!(executor.sent.lock().unwrap().is_empty());
+        assert!(report.pnl > 0.0);
+    }
+
+    #[tokio::test]
+    async fn cycle_that_no_longer_pays_is_not_traded() {
+        // ETH/BTC fell: 40000 / 2000 * 0.05 = 1.0 before fees.
+        let exchange = MockExchange { tickers: book(0.05) };
+        let executor = ScriptedExecutor::new(&[]);
+        let risk = RiskManager::default();
+        let report = ExecutionEngine::new(&exchange, &executor, &risk, limits(), settings(false), 0.1)
+            .execute(&opportunity(), 0.1)
+            .await;
+
+        assert_eq!(report.status, ExecutionStatus::Failed);
+        assert!(report.reason.unwrap().starts_with("Re-priced"));
+        assert!(report.orders.is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_leg_aborts_and_unwinds_to_the_start() {
+        let exchange = MockExchange { tickers: book(0.0505) };
+        // Leg 1 fills, leg 2 is rejected, the unwind fills.
+        let executor = ScriptedExecutor::new(&[Some(1.0), None, Some(1.0)]);
+        let risk = RiskManager::default();
+        let report = ExecutionEngine::new(&exchange, &executor, &risk, limits(), settings(false), 0.1)
+            .execute(&opportunity(), 0.1)
+            .await;
+
+        assert_eq!(report.status, ExecutionStatus::Aborted);
+        let purposes: Vec<_> = report.orders.iter().map(|o| o.purpose).collect();
+        assert_eq!(purposes, [OrderPurpose::Leg, OrderPurpose::Leg, OrderPurpose::Unwind]);
+        assert!(report.orders[1].error.is_some());
+
+        // The USDT from selling BTC is bought back into BTC.
+        let unwind = &report.orders[2].request;
+        assert_eq!((unwind.symbol.as_str(), unwind.side), ("BTCUSDT", TradeSide::Buy));
+        assert_eq!(unwind.time_in_force, TimeInForce::Ioc);
+        assert!(report.pnl < 0.0 && report.pnl > -0.01);
+        // Only USDT worth less than one 0.0001 BTC lot stays behind.
+        assert!(report.residuals.keys().all(|currency| currency == "USDT"));
+        assert!(report.residuals.values().all(|amount| amount.abs() < 4.1), "{:?}", report.residuals);
+        assert_eq!(executor.sent.lock().unwrap().len(), 3);
+        assert_eq!(risk.snapshot(&limits()).consecutive_failures["Mock"], 1);
+    }
+
+    #[tokio::test]
+    async fn risk_refusal_sends_nothing() {
+        let exchange = MockExchange { tickers: book(0.0505) };
+        let executor = ScriptedExecutor::new(&[Some(1.0), Some(1.0), Some(1.0)]);
+        let risk = RiskManager::default();
+        // The default limits allow 0.002 BTC per execution.
+        let report = ExecutionEngine::new(&exchange, &executor, &risk, RiskConfig::default(), settings(false), 0.1)
+            .execute(&opportunity(), 0.1)
+            .await;
+
+        assert_eq!(report.status, ExecutionStatus::Refused);
+        assert!(report.reason.unwrap().contains("max_notional"));
+        assert!(report.orders.is_empty());
+        assert!(executor.sent.lock().unwrap().is_empty());
+        assert!(risk.snapshot(&RiskConfig::default()).open_exposure.is_empty());
+    }
+}
@@ -0,0 +1,172 @@
+use crate::models::{TradeSide, TriangularArbitrageOpportunity};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// One exported opportunity. The field order is the CSV column order and is
+/// part of the export format: add columns at the end, never reorder.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvRow {
+    pub timestamp: DateTime<Utc>,
+    pub exchange: String,
+    pub path: String,
+    pub legs: String,
+    pub leg1_side: String,
+    pub leg1_price: f64,
+    pub leg2_side: String,
+    pub leg2_price: f64,
+    pub leg3_side: String,
+    pub leg3_price: f64,
+    pub gross_profit_pct: f64,
+    pub fees_pct: f64,
+    pub net_profit_pct: f64,
+    pub notional: f64,
+    pub id: String,
+}
+
+impl CsvRow {
+    pub fn from_opportunity(opportunity: &TriangularArbitrageOpportunity) -> Self {
+        let leg = |index: usize| {
+            opportunity
+                .legs
+                .get(index)
+                .map(|leg| {
+                    let side = match leg.side {
+                        TradeSide::Buy => "buy",
+                        TradeSide::Sell => "sell",
+                    };
+                    (side.to_string(), leg.price)
+                })
+                .unwrap_or_default()
+        };
+        let (leg1_side, leg1_price) = leg(0);
+        let (leg2_side, leg2_price) = leg(1);
+        let (leg3_side, leg3_price) = leg(2);
+
+        Self {
+            timestamp: opportunity.timestamp,
+            exchange: opportunity.exchange.clone(),
+            path: opportunity.path.clone(),
+            legs: opportunity
+                .legs
+                .iter()
+                .map(|leg| leg.symbol.as_str())
+                .collect::<Vec<_>>()
+                .join(";"),
+            leg1_side,
+            leg1_price,
+            leg2_side,
+            leg2_price,
+            leg3_side,
+            leg3_price,
+            gross_profit_pct: opportunity.gross_profit_percentage,
+            fees_pct: opportunity.estimated_fees,
+            net_profit_pct: opportunity.net_profit_percentage,
+            notional: opportunity.max_notional,
+            id: opportunity.id.clone(),
+        }
+    }
+}
+
+/// Appends opportunities to `opportunities-YYYY-MM-DD.csv` in `directory`,
+/// starting a new file each UTC day.
+pub struct CsvExporter {
+    directory: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl CsvExporter {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    fn file_for(&self, date: NaiveDate) -> PathBuf {
+        self.directory
+            .join(format!("opportunities-{}.csv", date.format("%Y-%m-%d")))
+    }
+
+    pub fn append(&self, opportunities: &[TriangularArbitrageOpportunity]) -> Result<(), Box<dyn std::error::Error>> {
+        if opportunities.is_empty() {
+            return Ok(());
+        }
+
+        let _guard = self.write_lock.lock().unwrap();
+        std::fs::create_dir_all(&self.directory)?;
+
+        let path = self.file_for(Utc::now().date_naive());
+        let is_new = std::fs::metadata(&path).map(|m| m.len() == 0).unwrap_or(true);
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+
+        let mut writer = csv::WriterBuilder::new().has_headers(is_new).from_writer(file);
+        for opportunity in opportunities {
+            writer.serialize(CsvRow::from_opportunity(opportunity))?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Rows with `from <= timestamp < to`, optionally limited to one exchange.
+    pub fn read_range(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        exchange: Option<&str>,
+    ) -> Result<Vec<CsvRow>, Box<dyn std::error::Error>> {
+        let mut rows = Vec::new();
+        let mut date = from.date_naive();
+
+        while date <= to.date_naive() {
+            let path = self.file_for(date);
+            if Path::new(&path).exists() {
+                let mut reader = csv::Reader::from_path(&path)?;
+                for row in reader.deserialize::<CsvRow>() {
+                    let row = row?;
+                    let in_range = row.timestamp >= from && row.timestamp < to;
+                    let exchange_matches = exchange.is_none_or(|e| row.exchange.eq_ignore_ascii_case(e));
+                    if in_range && exchange_matches {
+                        rows.push(row);
+                    }
+                }
+            }
+            date += Duration::days(1);
+        }
+
+        Ok(rows)
+    }
+
+    pub fn to_csv(rows: &[CsvRow]) -> Result<String, Box<dyn std::error::Error>> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        if rows.is_empty() {
+            // `serialize` only emits headers alongside the first row.
+            writer.write_record(CSV_HEADERS)?;
+        }
+        for row in rows {
+            writer.serialize(row)?;
+        }
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+}
+
+const CSV_HEADERS: [&str; 15] = [
+    "timestamp",
+    "exchange",
+    "path",
+    "legs",
+    "leg1_side",
+    "leg1_price",
+    "leg2_side",
+    "leg2_price",
+    "leg3_side",
+    "leg3_price",
+    "gross_profit_pct",
+    "fees_pct",
+    "net_profit_pct",
+    "notional",
+    "id",
+];
@@ -0,0 +1,47 @@
+use crate::account;
+use crate::config::Config;
+use crate::currency::CurrencyNormalizer;
+use crate::exchanges::Exchange;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Free amounts by canonical currency.
+pub type Balances = HashMap<String, f64>;
+
+/// Free account balances per exchange, fetched on demand and reused for
+/// `funding.refresh_secs` so scans don't each hit the signed endpoints.
+#[derive(Default)]
+pub struct Funding {
+    cache: Mutex<HashMap<String, (Instant, Balances)>>,
+}
+
+impl Funding {
+    /// Free balances on `exchange`.
+    pub async fn balances(&self, config: &Config, exchange: &dyn Exchange) -> Result<Balances, String> {
+        let refresh = Duration::from_secs(config.funding.refresh_secs);
+        if let Some((fetched_at, balances)) = self.cache.lock().unwrap().get(exchange.name()) {
+            if fetched_at.elapsed() < refresh {
+                return Ok(balances.clone());
+            }
+        }
+
+        let client = account::build_account(config, exchange)?;
+        let fetched = client.balances().await.map_err(|e| e.to_string())?;
+
+        // Venue codes are folded onto the ids the detector uses.
+        let normalizer = CurrencyNormalizer::global();
+        let mut balances = HashMap::new();
+        for balance in fetched {
+            *balances
+                .entry(normalizer.canonical(exchange.name(), &balance.asset))
+                .or_insert(0.0) += balance.free;
+        }
+
+        self.cache
+            .lock()
+            .unwrap()
+            .insert(exchange.name().to_string(), (Instant::now(), balances.clone()));
+        Ok(balances)
+    }
+}
@@ -0,0 +1,99 @@
+use crate::config::SharedConfig;
+use crate::models::ErrorResponse;
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{AUTHORIZATION, WWW_AUTHENTICATE};
+use actix_web::middleware::Next;
+use actix_web::{web, HttpResponse};
+
+/// Middleware for the routes that trade, read accounts or change the
+/// server's state: the request must carry `Authorization: Bearer <token>`
+/// matching `server.admin_token`. With no token configured the routes are
+/// closed. The token is read per request, so a reload applies immediately.
+pub async fn require_admin_token(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let expected = req
+        .app_data::<web::Data<SharedConfig>>()
+        .and_then(|config| config.current().server.admin_token.clone());
+    let presented = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+
+    let refusal = match (expected, presented) {
+        (None, _) => Some(HttpResponse::Forbidden().json(ErrorResponse {
+            error: "This route is disabled: set server.admin_token to enable it".to_string(),
+        })),
+        (Some(expected), Some(presented)) if tokens_match(expected.as_bytes(), presented.as_bytes()) => None,
+        (Some(_), _) => Some(
+            HttpResponse::Unauthorized()
+                .insert_header((WWW_AUTHENTICATE, "Bearer"))
+                .json(ErrorResponse {
+                    error: "Missing or invalid bearer token".to_string(),
+                }),
+        ),
+    };
+
+    match refusal {
+        Some(response) => Ok(req.into_response(response).map_into_right_body()),
+        None => next.call(req).await.map(ServiceResponse::map_into_left_body),
+    }
+}
+
+// Compares every byte so the time taken doesn't reveal how much of the
+// token was right.
+fn tokens_match(expected: &[u8], presented: &[u8]) -> bool {
+    expected.len() == presented.len()
+        && expected
+            .iter()
+            .zip(presented)
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use actix_web::middleware::from_fn;
+    use actix_web::{test, App};
+
+    async fn status(admin_token: Option<&str>, authorization: Option<&str>) -> u16 {
+        let mut config = Config::default();
+        config.server.admin_token = admin_token.map(str::to_string);
+        let app = test::init_service(
+            App::new()
+                .app_data(web::Data::new(SharedConfig::new(config, None)))
+                .service(
+                    web::resource("/admin")
+                        .wrap(from_fn(require_admin_token))
+                        .route(web::post().to(HttpResponse::Ok)),
+                ),
+        )
+        .await;
+
+        let mut request = test::TestRequest::post().uri("/admin");
+        if let Some(authorization) = authorization {
+            request = request.insert_header((AUTHORIZATION, authorization));
+        }
+        test::call_service(&app, request.to_request()).await.status().as_u16()
+    }
+
+    #[actix_web::test]
+    async fn routes_are_closed_without_a_token() {
+        assert_eq!(status(None, None).await, 403);
+        assert_eq!(status(None, Some("Bearer anything")).await, 403);
+    }
+
+    #[actix_web::test]
+    async fn only_the_configured_token_gets_through() {
+        assert_eq!(status(Some("s3cret"), None).await, 401);
+        assert_eq!(status(Some("s3cret"), Some("Bearer wrong")).await, 401);
+        assert_eq!(status(Some("s3cret"), Some("s3cret")).await, 401);
+        assert_eq!(status(Some("s3cret"), Some("Bearer s3cret")).await, 200);
+    }
+}
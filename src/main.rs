@@ -1,34 +1,277 @@
-use actix_web::{web, App, HttpResponse, HttpServer, Result, middleware::Logger};
+use actix_web::{web, App, HttpResponse, HttpServer, Result, middleware::{from_fn, Logger}};
 use actix_files::Files;
-use serde::Deserialize;
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, OpenApi, ToSchema};
+use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
+use futures::future::join_all;
 
 mod exchanges;
 mod arbitrage;
 mod models;
 mod ui;
+mod currency;
+mod config;
+mod export;
+mod scanner;
+mod scan_log;
+mod analytics;
+mod alerts;
+mod cli;
+mod storage;
+mod recorder;
+mod backtest;
+mod paper;
+mod execution;
+mod auth;
+mod guard;
+mod account;
+mod funding;
+mod risk;
+mod scheduler;
+mod metrics;
+mod openapi;
+#[cfg(test)]
+mod test_support;
 
 use exchanges::{get_exchange_by_id, Exchange};
-use arbitrage::ArbitrageDetector;
-use models::{ScanRequest, ScanResponse, ErrorResponse, ExchangeInfo};
+use models::{ScanRequest, ScanResponse, ErrorResponse, ExchangeInfo, ExecuteRequest, ExchangeSelection};
+use config::{Config, SharedConfig};
+use export::CsvExporter;
+use scan_log::ScanLogger;
+use analytics::Analytics;
+use alerts::AlertEngine;
+use alerts::rules::{AlertRule, AlertRuleInput, RuleStore};
+use storage::{HistoryFilter, Storage};
+use paper::PaperTrader;
+use execution::ExecutionEngine;
+use funding::Funding;
+use risk::RiskManager;
+use metrics::Metrics;
+use recorder::Recorder;
+use scheduler::{scan_one, CachedScan, ScanCache, ScanParams, ScanSinks};
+
+#[derive(Parser)]
+#[command(name = "crypto-arb-web", about = "Crypto triangular arbitrage scanner")]
+struct Cli {
+    /// Path to the TOML config file (defaults to ./config.toml when present)
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the web server (the default)
+    Serve,
+    /// Run the interactive terminal scanner
+    Terminal,
+    /// Run the full-screen terminal dashboard
+    Dashboard,
+    /// Scan one exchange once and print the result
+    Scan(cli::ScanArgs),
+    /// Scan every batch of a market-data recording
+    Replay(cli::ReplayArgs),
+    /// Simulate trading the opportunities in market-data recordings
+    Backtest(cli::BacktestArgs),
+}
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    env_logger::init();
+    let cli = Cli::parse();
+    let config = match Config::load(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ Invalid configuration: {}", e);
+            std::process::exit(1);
+        }
+    };
+    
+    let command = cli.command.unwrap_or(Command::Serve);
+    init_logging(&command, &config);
+
+    match command {
+        Command::Serve => run_server(config, cli.config).await,
+        Command::Terminal => ui::terminal::run(&config).await,
+        Command::Dashboard => ui::dashboard::run(&config).await,
+        Command::Scan(args) => std::process::exit(cli::scan(&config, args).await),
+        Command::Replay(args) => std::process::exit(cli::replay(&config, args).await),
+        Command::Backtest(args) => std::process::exit(cli::backtest(&config, args)),
+    }
+}
+
+// The dashboard owns the screen, so its log output goes to a file instead of
+// stderr.
+fn init_logging(command: &Command, config: &Config) {
+    let mut builder = env_logger::Builder::from_default_env();
+    
+    if let Command::Dashboard = command {
+        let path = std::path::Path::new(&config.logging.directory).join("dashboard.log");
+        let file = std::fs::create_dir_all(&config.logging.directory)
+            .and_then(|_| std::fs::OpenOptions::new().create(true).append(true).open(&path));
+        match file {
+            Ok(file) => {
+                builder.target(env_logger::Target::Pipe(Box::new(file)));
+            }
+            Err(e) => eprintln!("⚠️  Could not open {}: {}", path.display(), e),
+        }
+    }
+    
+    builder.init();
+}
+
+async fn run_server(config: Config, config_path: Option<PathBuf>) -> std::io::Result<()> {
+    let bind_address = config.bind_address();
+    let exporter = web::Data::new(CsvExporter::new(&config.export.directory));
+    let scan_logger = web::Data::new(ScanLogger::new(&config.logging.directory));
+    let analytics = web::Data::new(Analytics::default());
+    let funding = web::Data::new(Funding::default());
+    let risk = web::Data::new(RiskManager::default());
+    let metrics = web::Data::new(Metrics::new());
+    let recorder = web::Data::new(Recorder::new(&config.recorder.directory));
+    // A rules file that exists but cannot be read would be overwritten by the
+    // next API change, so refuse to start instead.
+    let rules = match RuleStore::load(&config.alerts.rules_file) {
+        Ok(rules) => rules,
+        Err(e) => {
+            eprintln!("❌ Failed to load alert rules: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let alert_engine = web::Data::new(AlertEngine::new(rules));
+    let paper_trader = web::Data::new(PaperTrader::new(&config.paper));
+    let storage = if config.storage.enabled {
+        match Storage::open(&config.storage.path) {
+            Ok(storage) => Some(web::Data::new(storage)),
+            Err(e) => {
+                eprintln!("❌ Failed to open storage at {}: {}", config.storage.path, e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+    let sinks = web::Data::new(ScanSinks {
+        exporter: exporter.clone(),
+        scan_logger: scan_logger.clone(),
+        analytics: analytics.clone(),
+        alert_engine: alert_engine.clone(),
+        paper_trader: paper_trader.clone(),
+        storage: storage.clone(),
+        metrics: metrics.clone(),
+        recorder: recorder.clone(),
+    });
+    let cache = web::Data::new(ScanCache::default());
+    let config = Arc::new(SharedConfig::new(config, config_path));
+
+    actix_web::rt::spawn(config::reload::watch(config.clone()));
+    let config = web::Data::from(config);
+    actix_web::rt::spawn(scheduler::run(
+        config.clone(),
+        sinks.get_ref().clone(),
+        funding.clone(),
+        cache.clone(),
+    ));
     
     println!("🚀 Starting Crypto Arbitrage Web Scanner...");
-    println!("🌐 Server running at http://localhost:8080");
+    println!("🌐 Server running at http://{}:{}", bind_address.0, bind_address.1);
     
-    HttpServer::new(|| {
+    HttpServer::new(move || {
         App::new()
+            .app_data(config.clone())
+            .app_data(exporter.clone())
+            .app_data(scan_logger.clone())
+            .app_data(analytics.clone())
+            .app_data(alert_engine.clone())
+            .app_data(paper_trader.clone())
+            .app_data(funding.clone())
+            .app_data(risk.clone())
+            .app_data(sinks.clone())
+            .app_data(cache.clone())
+            .app_data(metrics.clone())
+            .app_data(recorder.clone())
+            .configure(|cfg| {
+                if let Some(storage) = &storage {
+                    cfg.app_data(storage.clone());
+                }
+            })
+            .wrap(from_fn(metrics::track_http))
             .wrap(Logger::default())
             .service(Files::new("/static", "static/").show_files_listing())
             .route("/", web::get().to(index))
-            .route("/api/exchanges", web::get().to(get_exchanges))
-            .route("/api/scan", web::post().to(scan_arbitrage))
+            .route("/api/openapi.json", web::get().to(get_openapi))
+            .service(
+                web::scope("/api/v1")
+                    .route("/exchanges", web::get().to(get_exchanges))
+                    .route("/scan", web::post().to(scan_arbitrage))
+                    .route("/export.csv", web::get().to(export_csv))
+                    .route("/analytics", web::get().to(get_analytics))
+                    .route("/history", web::get().to(history_opportunities))
+                    .route("/history/scans", web::get().to(history_scans))
+                    .route("/history/alerts", web::get().to(history_alerts))
+                    .service(
+                        web::resource("/alerts")
+                            .wrap(from_fn(guard::require_admin_token))
+                            .route(web::get().to(list_alert_rules))
+                            .route(web::post().to(create_alert_rule)),
+                    )
+                    .service(
+                        web::resource("/alerts/{id}")
+                            .wrap(from_fn(guard::require_admin_token))
+                            .route(web::get().to(get_alert_rule))
+                            .route(web::put().to(update_alert_rule))
+                            .route(web::delete().to(delete_alert_rule)),
+                    )
+                    .service(
+                        web::resource("/execute")
+                            .wrap(from_fn(guard::require_admin_token))
+                            .route(web::post().to(execute_opportunity)),
+                    )
+                    .service(
+                        web::resource("/account/{exchange_id}/balances")
+                            .wrap(from_fn(guard::require_admin_token))
+                            .route(web::get().to(get_account_balances)),
+                    )
+                    .service(
+                        web::resource("/account/{exchange_id}/fees")
+                            .wrap(from_fn(guard::require_admin_token))
+                            .route(web::get().to(get_account_fees)),
+                    )
+                    .route("/paper", web::get().to(get_paper))
+                    .service(
+                        web::resource("/paper/reset")
+                            .wrap(from_fn(guard::require_admin_token))
+                            .route(web::post().to(reset_paper)),
+                    )
+                    .service(
+                        web::resource("/admin/reload")
+                            .wrap(from_fn(guard::require_admin_token))
+                            .route(web::post().to(reload_config)),
+                    )
+                    .service(
+                        web::resource("/admin/halt")
+                            .wrap(from_fn(guard::require_admin_token))
+                            .route(web::post().to(halt_trading)),
+                    )
+                    .service(
+                        web::resource("/admin/resume")
+                            .wrap(from_fn(guard::require_admin_token))
+                            .route(web::post().to(resume_trading)),
+                    )
+                    .service(
+                        web::resource("/admin/risk")
+                            .wrap(from_fn(guard::require_admin_token))
+                            .route(web::get().to(get_risk)),
+                    )
+            )
             .route("/health", web::get().to(health_check))
+            .route("/metrics", web::get().to(get_metrics))
     })
-    .bind("0.0.0.0:8080")?
+    .bind(bind_address)?
     .run()
     .await
 }
@@ -41,8 +284,15 @@ async fn index() -> Result<HttpResponse> {
         .body(html))
 }
 
-async fn get_exchanges() -> Result<HttpResponse> {
-    let exchanges = exchanges::get_all_exchanges();
+#[utoipa::path(
+    get,
+    path = "/api/v1/exchanges",
+    tag = "scan",
+    responses((status = 200, description = "Every configured exchange", body = [ExchangeInfo]))
+)]
+async fn get_exchanges(shared_config: web::Data<SharedConfig>) -> Result<HttpResponse> {
+    let config = shared_config.current();
+    let exchanges = exchanges::build_exchanges(&config, None);
     let exchange_info: Vec<ExchangeInfo> = exchanges
         .into_iter()
         .map(|e| ExchangeInfo {
@@ -55,40 +305,765 @@ async fn get_exchanges() -> Result<HttpResponse> {
     Ok(HttpResponse::Ok().json(exchange_info))
 }
 
-async fn scan_arbitrage(scan_request: web::Json<ScanRequest>) -> Result<HttpResponse> {
+#[utoipa::path(
+    post,
+    path = "/api/v1/scan",
+    tag = "scan",
+    request_body = ScanRequest,
+    responses(
+        (status = 200, description = "Opportunities of every selected exchange, best first", body = ScanResponse),
+        (status = 400, description = "Invalid exchange selection", body = ErrorResponse),
+        (status = 500, description = "The single requested exchange failed", body = ErrorResponse)
+    )
+)]
+async fn scan_arbitrage(
+    shared_config: web::Data<SharedConfig>,
+    sinks: web::Data<ScanSinks>,
+    funding: web::Data<Funding>,
+    cache: web::Data<ScanCache>,
+    scan_request: web::Json<ScanRequest>,
+) -> Result<HttpResponse> {
     let start_time = Instant::now();
+    let config = shared_config.current();
+    let request = scan_request.into_inner();
     
-    let exchange = match get_exchange_by_id(scan_request.exchange_id) {
-        Some(exchange) => exchange,
-        None => {
-            return Ok(HttpResponse::BadRequest().json(ErrorResponse {
-                error: "Exchange not found".to_string(),
-            }));
+    let exchanges: Vec<Box<dyn Exchange>> = match (&request.exchange_id, &request.exchanges) {
+        (Some(id), None) => match get_exchange_by_id(&config, *id, Some(&*sinks.recorder)) {
+            Some(exchange) => vec![exchange],
+            None => return Ok(bad_request("Exchange not found")),
+        },
+        (None, Some(ExchangeSelection::Ids(ids))) => {
+            let mut selected = Vec::new();
+            for id in ids {
+                match get_exchange_by_id(&config, *id, Some(&*sinks.recorder)) {
+                    Some(exchange) => selected.push(exchange),
+                    None => return Ok(bad_request(&format!("Exchange {} not found", id))),
+                }
+            }
+            selected
         }
+        (None, Some(ExchangeSelection::All(_))) => exchanges::build_exchanges(&config, Some(&*sinks.recorder))
+            .into_iter()
+            .filter(|e| e.is_enabled())
+            .collect(),
+        (None, None) => return Ok(bad_request("Set exchange_id or exchanges")),
+        (Some(_), Some(_)) => return Ok(bad_request("Set only one of exchange_id and exchanges")),
     };
+    if exchanges.is_empty() {
+        return Ok(bad_request("No exchanges selected"));
+    }
+    
+    // Exchanges the background scans can answer for are served from the
+    // cache; the rest are fetched now, concurrently.
+    let params = ScanParams {
+        min_profit: request.min_profit,
+        min_quote_volume: request.min_quote_volume,
+        fundable_only: request.fundable_only,
+    };
+    let cached: Vec<Option<CachedScan>> = exchanges
+        .iter()
+        .map(|exchange| match request.fresh {
+            true => None,
+            false => cache.usable(&config, exchange.id(), &params),
+        })
+        .collect();
+    let live = join_all(
+        exchanges
+            .iter()
+            .zip(&cached)
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(exchange, _)| scan_one(exchange.as_ref(), &config, &funding, &params)),
+    )
+    .await;
+    let mut live = live.into_iter();
     
-    match exchange.fetch_tickers().await {
-        Ok(tickers) => {
-            let opportunities = ArbitrageDetector::find_triangular_opportunities(
-                &tickers,
-                exchange.name(),
-                scan_request.min_profit,
-            );
-            
-            let response = ScanResponse {
-                opportunities,
-                total_pairs: tickers.len(),
-                scan_time_ms: start_time.elapsed().as_millis(),
-            };
-            
-            Ok(HttpResponse::Ok().json(response))
+    let single = request.exchange_id.is_some();
+    let mut opportunities = Vec::new();
+    let mut total_pairs = 0;
+    let mut age_ms = 0;
+    let mut statuses = Vec::new();
+    for cached in cached {
+        let scan = match cached {
+            Some(cached) => {
+                let mut scan = cached.scan;
+                scan.status.cached = true;
+                scan.status.age_ms = (chrono::Utc::now() - cached.scanned_at).num_milliseconds().max(0) as u64;
+                if let Some(outcome) = &mut scan.outcome {
+                    outcome.opportunities.retain(|o| o.net_profit_percentage > request.min_profit);
+                    scan.status.opportunities = outcome.opportunities.len();
+                }
+                scan
+            }
+            // Only the background rounds feed the sinks, so a request's own
+            // thresholds can't shift alert cooldowns, analytics or the paper
+            // ledger. A scan that matches a round refreshes the cache.
+            None => {
+                let scan = live.next().expect("one live scan per uncached exchange");
+                if params.is_background(&config) {
+                    cache.store(&config, scan.clone());
+                } else {
+                    cache.issue(&config, &scan);
+                }
+                scan
+            }
+        };
+        
+        match scan.outcome {
+            Some(outcome) if outcome.error.is_none() => {
+                total_pairs += outcome.total_pairs;
+                opportunities.extend(outcome.opportunities);
+            }
+            // Skipped before fetching, which only a request can cause.
+            None if single => return Ok(bad_request(scan.status.error.as_deref().unwrap_or_default())),
+            _ => {}
         }
-        Err(e) => {
-            Ok(HttpResponse::InternalServerError().json(ErrorResponse {
-                error: format!("Failed to fetch tickers: {}", e),
-            }))
+        // A single-exchange request fails as a whole, as it always has.
+        if let (true, Some(error)) = (single, &scan.status.error) {
+            return Ok(HttpResponse::InternalServerError().json(ErrorResponse { error: error.clone() }));
         }
+        age_ms = age_ms.max(scan.status.age_ms);
+        statuses.push(scan.status);
     }
+    
+    if request.fundable_only {
+        opportunities.retain(|o| o.fundable == Some(true));
+    }
+    opportunities.sort_by(|a, b| b.net_profit_percentage.total_cmp(&a.net_profit_percentage));
+    
+    let response = ScanResponse {
+        opportunities,
+        total_pairs,
+        scan_time_ms: start_time.elapsed().as_millis(),
+        age_ms,
+        exchanges: statuses,
+    };
+    
+    Ok(HttpResponse::Ok().json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/execute",
+    tag = "execution",
+    security(("admin_token" = [])),
+    request_body = ExecuteRequest,
+    responses(
+        (status = 200, description = "The finished execution, including any unwind", body = execution::ExecutionReport),
+        (status = 400, description = "Invalid request or live trading disabled", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured, or refused by the risk checks", body = ErrorResponse),
+        (status = 404, description = "The opportunity is not in a recent scan", body = ErrorResponse)
+    )
+)]
+async fn execute_opportunity(
+    shared_config: web::Data<SharedConfig>,
+    risk: web::Data<RiskManager>,
+    recorder: web::Data<Recorder>,
+    cache: web::Data<ScanCache>,
+    execute_request: web::Json<ExecuteRequest>,
+) -> Result<HttpResponse> {
+    let config = shared_config.current();
+    let request = execute_request.into_inner();
+    
+    // Only opportunities this server found can be traded, exactly as found.
+    let Some(opportunity) = cache.opportunity(&config, request.exchange_id, &request.opportunity_id) else {
+        return Ok(HttpResponse::NotFound().json(ErrorResponse {
+            error: format!(
+                "Opportunity {} is not in a recent scan of exchange {}; scan again",
+                request.opportunity_id, request.exchange_id
+            ),
+        }));
+    };
+    let exchange = match get_exchange_by_id(&config, request.exchange_id, Some(&*recorder)) {
+        Some(exchange) => exchange,
+        None => return Ok(bad_request("Exchange not found")),
+    };
+    let executor = match execution::build_executor(&config, exchange.as_ref()) {
+        Some(executor) => executor,
+        None => return Ok(bad_request(&format!("Trading is not supported on {}", exchange.name()))),
+    };
+    if !(request.amount.is_finite() && request.amount > 0.0) {
+        return Ok(bad_request("amount must be positive"));
+    }
+    
+    // Live orders need both the config and the request to opt in; anything
+    // short of an explicit `"dry_run": false` is a dry run.
+    let mut settings = config.execution.clone();
+    if request.dry_run == Some(false) && settings.dry_run {
+        return Ok(bad_request("Live trading is disabled; set execution.dry_run = false"));
+    }
+    settings.dry_run = request.dry_run != Some(false);
+    if let Some(mode) = request.mode {
+        settings.mode = mode;
+    }
+    if let Some(time_in_force) = request.time_in_force {
+        settings.time_in_force = time_in_force;
+    }
+    
+    // Run detached so a dropped connection can't stop a cycle halfway or
+    // skip settling it with the risk manager.
+    let limits = config.risk.clone();
+    let min_profit = config.detector.default_min_profit;
+    let report = actix_web::rt::spawn(async move {
+        ExecutionEngine::new(exchange.as_ref(), executor.as_ref(), &risk, limits, settings, min_profit)
+            .execute(&opportunity, request.amount)
+            .await
+    })
+    .await
+    .map_err(actix_web::error::ErrorInternalServerError)?;
+    if report.status == execution::ExecutionStatus::Refused {
+        return Ok(HttpResponse::Forbidden().json(ErrorResponse {
+            error: report.reason.unwrap_or_default(),
+        }));
+    }
+    Ok(HttpResponse::Ok().json(report))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/account/{exchange_id}/balances",
+    tag = "account",
+    security(("admin_token" = [])),
+    params(("exchange_id" = u32, Path, description = "Exchange id")),
+    responses(
+        (status = 200, description = "Non-zero spot balances", body = [account::Balance]),
+        (status = 400, description = "Unknown exchange, unsupported venue or no keys", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse),
+        (status = 500, description = "The venue refused the query", body = ErrorResponse)
+    )
+)]
+async fn get_account_balances(
+    shared_config: web::Data<SharedConfig>,
+    exchange_id: web::Path<u32>,
+) -> Result<HttpResponse> {
+    let config = shared_config.current();
+    let client = match account_client(&config, *exchange_id) {
+        Ok(client) => client,
+        Err(error) => return Ok(bad_request(&error)),
+    };
+    match client.balances().await {
+        Ok(balances) => Ok(HttpResponse::Ok().json(balances)),
+        Err(e) => Ok(account_error(client.name(), e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/account/{exchange_id}/fees",
+    tag = "account",
+    security(("admin_token" = [])),
+    params(("exchange_id" = u32, Path, description = "Exchange id")),
+    responses(
+        (status = 200, description = "Maker and taker fees in percent", body = [account::TradingFee]),
+        (status = 400, description = "Unknown exchange, unsupported venue or no keys", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse),
+        (status = 500, description = "The venue refused the query", body = ErrorResponse)
+    )
+)]
+async fn get_account_fees(
+    shared_config: web::Data<SharedConfig>,
+    exchange_id: web::Path<u32>,
+) -> Result<HttpResponse> {
+    let config = shared_config.current();
+    let client = match account_client(&config, *exchange_id) {
+        Ok(client) => client,
+        Err(error) => return Ok(bad_request(&error)),
+    };
+    match client.trading_fees().await {
+        Ok(fees) => Ok(HttpResponse::Ok().json(fees)),
+        Err(e) => Ok(account_error(client.name(), e)),
+    }
+}
+
+fn account_client(config: &Config, exchange_id: u32) -> std::result::Result<Box<dyn account::AccountClient>, String> {
+    let exchange = get_exchange_by_id(config, exchange_id, None).ok_or("Exchange not found")?;
+    account::build_account(config, exchange.as_ref())
+}
+
+fn account_error(exchange: &str, error: Box<dyn std::error::Error>) -> HttpResponse {
+    log::warn!("{} account query failed: {}", exchange, error);
+    HttpResponse::InternalServerError().json(ErrorResponse {
+        error: format!("Account query failed: {}", error),
+    })
+}
+
+fn bad_request(error: &str) -> HttpResponse {
+    HttpResponse::BadRequest().json(ErrorResponse {
+        error: error.to_string(),
+    })
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/paper",
+    tag = "paper",
+    responses((status = 200, description = "Paper balances, PnL and recent trades", body = paper::PaperSnapshot))
+)]
+async fn get_paper(
+    shared_config: web::Data<SharedConfig>,
+    paper_trader: web::Data<PaperTrader>,
+) -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(paper_trader.snapshot(&shared_config.current().paper)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/paper/reset",
+    tag = "paper",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "The ledger after the reset", body = paper::PaperSnapshot),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse)
+    )
+)]
+async fn reset_paper(
+    shared_config: web::Data<SharedConfig>,
+    paper_trader: web::Data<PaperTrader>,
+) -> Result<HttpResponse> {
+    let config = shared_config.current();
+    paper_trader.reset(&config.paper);
+    Ok(HttpResponse::Ok().json(paper_trader.snapshot(&config.paper)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/analytics",
+    tag = "analytics",
+    responses((status = 200, description = "Rolling per-exchange statistics", body = analytics::AnalyticsSnapshot))
+)]
+async fn get_analytics(analytics: web::Data<Analytics>) -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(analytics.snapshot()))
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ExportQuery {
+    from: Option<chrono::DateTime<chrono::Utc>>,
+    to: Option<chrono::DateTime<chrono::Utc>>,
+    exchange: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/export.csv",
+    tag = "history",
+    params(ExportQuery),
+    responses(
+        (status = 200, description = "Exported opportunities", body = String, content_type = "text/csv"),
+        (status = 400, description = "Invalid time range", body = ErrorResponse)
+    )
+)]
+async fn export_csv(
+    exporter: web::Data<CsvExporter>,
+    query: web::Query<ExportQuery>,
+) -> Result<HttpResponse> {
+    // Defaults to the last 24 hours.
+    let to = query.to.unwrap_or_else(chrono::Utc::now);
+    let from = query.from.unwrap_or(to - chrono::Duration::hours(24));
+    
+    if from >= to {
+        return Ok(HttpResponse::BadRequest().json(ErrorResponse {
+            error: "`from` must be before `to`".to_string(),
+        }));
+    }
+    
+    let csv = exporter
+        .read_range(from, to, query.exchange.as_deref())
+        .and_then(|rows| CsvExporter::to_csv(&rows));
+    
+    match csv {
+        Ok(csv) => Ok(HttpResponse::Ok()
+            .content_type("text/csv; charset=utf-8")
+            .insert_header(("Content-Disposition", "attachment; filename=\"opportunities.csv\""))
+            .body(csv)),
+        Err(e) => Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+            error: format!("Failed to read CSV export: {}", e),
+        })),
+    }
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct HistoryQuery {
+    exchange: Option<String>,
+    from: Option<chrono::DateTime<chrono::Utc>>,
+    to: Option<chrono::DateTime<chrono::Utc>>,
+    min_profit: Option<f64>,
+    limit: Option<usize>,
+}
+
+const DEFAULT_HISTORY_LIMIT: usize = 500;
+const MAX_HISTORY_LIMIT: usize = 5000;
+
+impl HistoryQuery {
+    // Same window default as the CSV export: the last 24 hours.
+    fn filter(&self, config: &Config) -> Result<HistoryFilter, String> {
+        let to = self.to.unwrap_or_else(chrono::Utc::now);
+        let from = self.from.unwrap_or(to - chrono::Duration::hours(24));
+        if from >= to {
+            return Err("`from` must be before `to`".to_string());
+        }
+        
+        // Accept config keys and ids as well as the stored exchange name.
+        let exchange = self.exchange.as_ref().map(|query| {
+            exchanges::find_exchange(config, query, None)
+                .map(|e| e.name().to_string())
+                .unwrap_or_else(|| query.clone())
+        });
+        
+        Ok(HistoryFilter {
+            exchange,
+            from,
+            to,
+            min_profit: self.min_profit,
+            limit: self.limit.unwrap_or(DEFAULT_HISTORY_LIMIT).min(MAX_HISTORY_LIMIT),
+        })
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/history",
+    tag = "history",
+    params(HistoryQuery),
+    responses(
+        (status = 200, description = "Stored opportunities, most profitable first", body = [storage::StoredOpportunity]),
+        (status = 400, description = "Invalid time range", body = ErrorResponse),
+        (status = 404, description = "Storage is disabled", body = ErrorResponse)
+    )
+)]
+async fn history_opportunities(
+    shared_config: web::Data<SharedConfig>,
+    storage: Option<web::Data<Storage>>,
+    query: web::Query<HistoryQuery>,
+) -> Result<HttpResponse> {
+    history_response(&shared_config, storage, &query, |storage, filter| {
+        storage.opportunities(filter).map(|rows| serde_json::json!(rows))
+    })
+    .await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/history/scans",
+    tag = "history",
+    params(HistoryQuery),
+    responses(
+        (status = 200, description = "Stored scans, newest first", body = [storage::StoredScan]),
+        (status = 400, description = "Invalid time range", body = ErrorResponse),
+        (status = 404, description = "Storage is disabled", body = ErrorResponse)
+    )
+)]
+async fn history_scans(
+    shared_config: web::Data<SharedConfig>,
+    storage: Option<web::Data<Storage>>,
+    query: web::Query<HistoryQuery>,
+) -> Result<HttpResponse> {
+    history_response(&shared_config, storage, &query, |storage, filter| {
+        storage.scans(filter).map(|rows| serde_json::json!(rows))
+    })
+    .await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/history/alerts",
+    tag = "history",
+    params(HistoryQuery),
+    responses(
+        (status = 200, description = "Stored alert firings, newest first", body = [storage::StoredAlert]),
+        (status = 400, description = "Invalid time range", body = ErrorResponse),
+        (status = 404, description = "Storage is disabled", body = ErrorResponse)
+    )
+)]
+async fn history_alerts(
+    shared_config: web::Data<SharedConfig>,
+    storage: Option<web::Data<Storage>>,
+    query: web::Query<HistoryQuery>,
+) -> Result<HttpResponse> {
+    history_response(&shared_config, storage, &query, |storage, filter| {
+        storage.alerts(filter).map(|rows| serde_json::json!(rows))
+    })
+    .await
+}
+
+async fn history_response(
+    shared_config: &SharedConfig,
+    storage: Option<web::Data<Storage>>,
+    query: &HistoryQuery,
+    load: impl FnOnce(&Storage, &HistoryFilter) -> std::result::Result<serde_json::Value, Box<dyn std::error::Error>>
+        + Send
+        + 'static,
+) -> Result<HttpResponse> {
+    let Some(storage) = storage else {
+        return Ok(HttpResponse::NotFound().json(ErrorResponse {
+            error: "History is unavailable: storage is disabled (set [storage] enabled = true)".to_string(),
+        }));
+    };
+    
+    let filter = match query.filter(&shared_config.current()) {
+        Ok(filter) => filter,
+        Err(e) => return Ok(HttpResponse::BadRequest().json(ErrorResponse { error: e })),
+    };
+    
+    // rusqlite is synchronous, so the query runs on the blocking pool.
+    let rows = web::block(move || load(&storage, &filter).map_err(|e| e.to_string())).await?;
+    match rows {
+        Ok(rows) => Ok(HttpResponse::Ok().json(rows)),
+        Err(e) => Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+            error: format!("Failed to read history: {}", e),
+        })),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/alerts",
+    tag = "alerts",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Every alert rule, secrets redacted", body = [alerts::rules::AlertRule]),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse)
+    )
+)]
+async fn list_alert_rules(alert_engine: web::Data<AlertEngine>) -> Result<HttpResponse> {
+    let rules: Vec<_> = alert_engine.rules().list().iter().map(AlertRule::redacted).collect();
+    Ok(HttpResponse::Ok().json(rules))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/alerts/{id}",
+    tag = "alerts",
+    security(("admin_token" = [])),
+    params(("id" = String, Path, description = "Rule id")),
+    responses(
+        (status = 200, description = "The rule, secrets redacted", body = alerts::rules::AlertRule),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse),
+        (status = 404, description = "No such rule", body = ErrorResponse)
+    )
+)]
+async fn get_alert_rule(
+    alert_engine: web::Data<AlertEngine>,
+    id: web::Path<String>,
+) -> Result<HttpResponse> {
+    match alert_engine.rules().get(&id) {
+        Some(rule) => Ok(HttpResponse::Ok().json(rule.redacted())),
+        None => Ok(alert_rule_not_found()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/alerts",
+    tag = "alerts",
+    security(("admin_token" = [])),
+    request_body = AlertRuleInput,
+    responses(
+        (status = 201, description = "The created rule, secrets redacted", body = alerts::rules::AlertRule),
+        (status = 400, description = "Invalid rule", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse),
+        (status = 500, description = "The rules file could not be written", body = ErrorResponse)
+    )
+)]
+async fn create_alert_rule(
+    alert_engine: web::Data<AlertEngine>,
+    input: web::Json<AlertRuleInput>,
+) -> Result<HttpResponse> {
+    if let Err(e) = input.validate() {
+        return Ok(invalid_alert_rule(e));
+    }
+    
+    match alert_engine.rules().create(input.into_inner()) {
+        Ok(rule) => Ok(HttpResponse::Created().json(rule.redacted())),
+        Err(e) => Ok(alert_rule_save_failed(e)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/alerts/{id}",
+    tag = "alerts",
+    security(("admin_token" = [])),
+    params(("id" = String, Path, description = "Rule id")),
+    request_body = AlertRuleInput,
+    responses(
+        (status = 200, description = "The updated rule, secrets redacted", body = alerts::rules::AlertRule),
+        (status = 400, description = "Invalid rule", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse),
+        (status = 404, description = "No such rule", body = ErrorResponse),
+        (status = 500, description = "The rules file could not be written", body = ErrorResponse)
+    )
+)]
+async fn update_alert_rule(
+    alert_engine: web::Data<AlertEngine>,
+    id: web::Path<String>,
+    input: web::Json<AlertRuleInput>,
+) -> Result<HttpResponse> {
+    // A secret sent back redacted, as GET returned it, stays as stored.
+    let mut input = input.into_inner();
+    if let Some(existing) = alert_engine.rules().get(&id) {
+        input.channel.keep_secrets(&existing.rule.channel);
+    }
+    if let Err(e) = input.validate() {
+        return Ok(invalid_alert_rule(e));
+    }
+    
+    match alert_engine.rules().update(&id, input) {
+        Ok(Some(rule)) => Ok(HttpResponse::Ok().json(rule.redacted())),
+        Ok(None) => Ok(alert_rule_not_found()),
+        Err(e) => Ok(alert_rule_save_failed(e)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/v1/alerts/{id}",
+    tag = "alerts",
+    security(("admin_token" = [])),
+    params(("id" = String, Path, description = "Rule id")),
+    responses(
+        (status = 204, description = "Deleted"),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse),
+        (status = 404, description = "No such rule", body = ErrorResponse),
+        (status = 500, description = "The rules file could not be written", body = ErrorResponse)
+    )
+)]
+async fn delete_alert_rule(
+    alert_engine: web::Data<AlertEngine>,
+    id: web::Path<String>,
+) -> Result<HttpResponse> {
+    match alert_engine.rules().delete(&id) {
+        Ok(true) => Ok(HttpResponse::NoContent().finish()),
+        Ok(false) => Ok(alert_rule_not_found()),
+        Err(e) => Ok(alert_rule_save_failed(e)),
+    }
+}
+
+fn invalid_alert_rule(e: Box<dyn std::error::Error>) -> HttpResponse {
+    HttpResponse::BadRequest().json(ErrorResponse {
+        error: format!("Invalid alert rule: {}", e),
+    })
+}
+
+fn alert_rule_not_found() -> HttpResponse {
+    HttpResponse::NotFound().json(ErrorResponse {
+        error: "Alert rule not found".to_string(),
+    })
+}
+
+fn alert_rule_save_failed(e: Box<dyn std::error::Error>) -> HttpResponse {
+    HttpResponse::InternalServerError().json(ErrorResponse {
+        error: format!("Failed to save alert rules: {}", e),
+    })
+}
+
+#[derive(Serialize, ToSchema)]
+struct ReloadResponse {
+    status: String,
+    enabled_exchanges: Vec<String>,
+    timestamp: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/reload",
+    tag = "admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "The new config is live", body = ReloadResponse),
+        (status = 400, description = "The new config or alias file is invalid; the old ones stay", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse)
+    )
+)]
+async fn reload_config(shared_config: web::Data<SharedConfig>) -> Result<HttpResponse> {
+    match shared_config.reload() {
+        Ok(config) => Ok(HttpResponse::Ok().json(ReloadResponse {
+            status: "reloaded".to_string(),
+            enabled_exchanges: exchanges::build_exchanges(&config, None)
+                .iter()
+                .filter(|e| e.is_enabled())
+                .map(|e| e.name().to_string())
+                .collect(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        })),
+        Err(e) => Ok(HttpResponse::BadRequest().json(ErrorResponse {
+            error: format!("Config reload rejected: {}", e),
+        })),
+    }
+}
+
+#[derive(Deserialize, ToSchema)]
+struct HaltRequest {
+    reason: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/halt",
+    tag = "admin",
+    security(("admin_token" = [])),
+    request_body = Option<HaltRequest>,
+    responses(
+        (status = 200, description = "Trading is halted", body = risk::Halt),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse)
+    )
+)]
+async fn halt_trading(
+    risk: web::Data<RiskManager>,
+    halt_request: Option<web::Json<HaltRequest>>,
+) -> Result<HttpResponse> {
+    let reason = halt_request
+        .and_then(|r| r.into_inner().reason)
+        .unwrap_or_else(|| "halted via /api/v1/admin/halt".to_string());
+    Ok(HttpResponse::Ok().json(risk.halt(&reason)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/resume",
+    tag = "admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Risk state after resuming", body = risk::RiskSnapshot),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse)
+    )
+)]
+async fn resume_trading(
+    shared_config: web::Data<SharedConfig>,
+    risk: web::Data<RiskManager>,
+) -> Result<HttpResponse> {
+    risk.resume();
+    Ok(HttpResponse::Ok().json(risk.snapshot(&shared_config.current().risk)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/risk",
+    tag = "admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 200, description = "Halt state, PnL, exposure and circuit breakers", body = risk::RiskSnapshot),
+        (status = 401, description = "Missing or invalid bearer token", body = ErrorResponse),
+        (status = 403, description = "No admin token configured", body = ErrorResponse)
+    )
+)]
+async fn get_risk(
+    shared_config: web::Data<SharedConfig>,
+    risk: web::Data<RiskManager>,
+) -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(risk.snapshot(&shared_config.current().risk)))
 }
 
 async fn health_check() -> Result<HttpResponse> {
@@ -97,3 +1072,16 @@ async fn health_check() -> Result<HttpResponse> {
         "timestamp": chrono::Utc::now().to_rfc3339()
     })))
 }
+
+async fn get_openapi() -> Result<HttpResponse> {
+    Ok(HttpResponse::Ok().json(openapi::ApiDoc::openapi()))
+}
+
+async fn get_metrics(metrics: web::Data<Metrics>) -> Result<HttpResponse> {
+    match metrics.render() {
+        Ok(body) => Ok(HttpResponse::Ok().content_type(prometheus::TEXT_FORMAT).body(body)),
+        Err(e) => Ok(HttpResponse::InternalServerError().json(ErrorResponse {
+            error: format!("Failed to encode metrics: {}", e),
+        })),
+    }
+}
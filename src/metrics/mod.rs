@@ -0,0 +1,181 @@
+use crate::scanner::ScanOutcome;
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::web;
+use prometheus::{
+    Encoder, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::time::Instant;
+
+// Seconds; ticker fetches run from tens of milliseconds to the scan timeout.
+const FETCH_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+const DETECT_BUCKETS: &[f64] = &[0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0];
+
+/// Prometheus metrics served on `/metrics`. Scan metrics are fed by the
+/// background scans; gauges hold each exchange's latest round.
+pub struct Metrics {
+    registry: Registry,
+    fetch_seconds: HistogramVec,
+    fetch_errors: IntCounterVec,
+    tickers: IntGaugeVec,
+    rejected_tickers: IntGaugeVec,
+    detect_seconds: HistogramVec,
+    opportunities: IntGaugeVec,
+    best_net_profit: GaugeVec,
+    http_requests: IntCounterVec,
+    http_seconds: HistogramVec,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let metrics = Self {
+            fetch_seconds: HistogramVec::new(
+                HistogramOpts::new("arb_fetch_duration_seconds", "Ticker fetch latency").buckets(FETCH_BUCKETS.to_vec()),
+                &["exchange"],
+            )
+            .unwrap(),
+            fetch_errors: IntCounterVec::new(
+                Opts::new("arb_fetch_errors_total", "Failed ticker fetches by kind"),
+                &["exchange", "kind"],
+            )
+            .unwrap(),
+            tickers: IntGaugeVec::new(
+                Opts::new("arb_tickers", "Valid tickers in the latest scan"),
+                &["exchange"],
+            )
+            .unwrap(),
+            rejected_tickers: IntGaugeVec::new(
+                Opts::new("arb_rejected_tickers", "Tickers dropped by sanitizing in the latest scan"),
+                &["exchange", "reason"],
+            )
+            .unwrap(),
+            detect_seconds: HistogramVec::new(
+                HistogramOpts::new("arb_detect_duration_seconds", "Detector runtime").buckets(DETECT_BUCKETS.to_vec()),
+                &["exchange"],
+            )
+            .unwrap(),
+            opportunities: IntGaugeVec::new(
+                Opts::new("arb_opportunities", "Opportunities in the latest scan"),
+                &["exchange"],
+            )
+            .unwrap(),
+            best_net_profit: GaugeVec::new(
+                Opts::new(
+                    "arb_best_net_profit_percent",
+                    "Best net profit in the latest scan; absent when it found none",
+                ),
+                &["exchange"],
+            )
+            .unwrap(),
+            http_requests: IntCounterVec::new(
+                Opts::new("arb_http_requests_total", "HTTP requests by route and status"),
+                &["method", "route", "status"],
+            )
+            .unwrap(),
+            http_seconds: HistogramVec::new(
+                HistogramOpts::new("arb_http_request_duration_seconds", "HTTP request latency"),
+                &["method", "route"],
+            )
+            .unwrap(),
+            registry,
+        };
+
+        // Names are fixed and distinct, so registering cannot fail.
+        metrics.registry.register(Box::new(metrics.fetch_seconds.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.fetch_errors.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.tickers.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.rejected_tickers.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.detect_seconds.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.opportunities.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.best_net_profit.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.http_requests.clone())).unwrap();
+        metrics.registry.register(Box::new(metrics.http_seconds.clone())).unwrap();
+        metrics
+    }
+
+    pub fn record_scan(&self, outcome: &ScanOutcome) {
+        let exchange = outcome.exchange.as_str();
+        self.fetch_seconds
+            .with_label_values(&[exchange])
+            .observe(outcome.fetch_ms as f64 / 1000.0);
+
+        // A failed scan leaves nothing behind: its counts read zero.
+        if let Some(kind) = outcome.error_kind {
+            self.fetch_errors.with_label_values(&[exchange, kind.as_str()]).inc();
+        } else if outcome.error.is_none() {
+            self.detect_seconds
+                .with_label_values(&[exchange])
+                .observe(outcome.detect_ms as f64 / 1000.0);
+        }
+
+        self.tickers.with_label_values(&[exchange]).set(outcome.total_pairs as i64);
+        for (reason, count) in [
+            ("invalid_price", outcome.rejected.invalid_price),
+            ("crossed_book", outcome.rejected.crossed_book),
+            ("low_volume", outcome.rejected.low_volume),
+        ] {
+            self.rejected_tickers.with_label_values(&[exchange, reason]).set(count as i64);
+        }
+        self.opportunities
+            .with_label_values(&[exchange])
+            .set(outcome.opportunities.len() as i64);
+
+        let best = outcome
+            .opportunities
+            .iter()
+            .map(|o| o.net_profit_percentage)
+            .reduce(f64::max);
+        match best {
+            Some(best) => self.best_net_profit.with_label_values(&[exchange]).set(best),
+            None => {
+                let _ = self.best_net_profit.remove_label_values(&[exchange]);
+            }
+        }
+    }
+
+    fn record_http(&self, method: &str, route: &str, status: u16, started: Instant) {
+        self.http_requests
+            .with_label_values(&[method, route, &status.to_string()])
+            .inc();
+        self.http_seconds
+            .with_label_values(&[method, route])
+            .observe(started.elapsed().as_secs_f64());
+    }
+
+    /// The registry in the Prometheus text format (`prometheus::TEXT_FORMAT`).
+    pub fn render(&self) -> Result<String, Box<dyn std::error::Error>> {
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8(buffer)?)
+    }
+}
+
+/// Middleware counting and timing every request by its route pattern, so
+/// path parameters don't each get a series.
+pub async fn track_http(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let started = Instant::now();
+    let metrics = req.app_data::<web::Data<Metrics>>().cloned();
+    let method = req.method().to_string();
+    let route = req.match_pattern().unwrap_or_else(|| "unmatched".to_string());
+
+    let response = next.call(req).await;
+    if let Some(metrics) = metrics {
+        let status = match &response {
+            Ok(response) => response.status(),
+            Err(e) => e.as_response_error().status_code(),
+        };
+        metrics.record_http(&method, &route, status.as_u16(), started);
+    }
+    response
+}
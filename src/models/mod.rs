@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ExchangeInfo {
     pub id: u32,
     pub name: String,
@@ -17,10 +18,20 @@ pub struct Ticker {
     pub ask_price: f64,
     pub bid_qty: f64,
     pub ask_qty: f64,
+    #[serde(default)]
+    pub quote_volume: Option<f64>, // 24h volume in the quote currency, when the venue reports it
     pub timestamp: u64,
+    // Codes as the venue spells them, kept for order placement after
+    // `symbol`/`base_currency`/`quote_currency` are normalized.
+    #[serde(default)]
+    pub venue_symbol: String,
+    #[serde(default)]
+    pub venue_base_currency: String,
+    #[serde(default)]
+    pub venue_quote_currency: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TriangularArbitrageOpportunity {
     pub id: String,
     pub exchange: String,
@@ -30,22 +41,145 @@ pub struct TriangularArbitrageOpportunity {
     pub estimated_fees: f64,
     pub net_profit_percentage: f64,
     pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub legs: Vec<OpportunityLeg>,
+    // Largest starting amount the top-of-book quantities allow, in the
+    // currency spent on the first leg.
+    #[serde(default)]
+    pub max_notional: f64,
+    // Set when balances are known: the starting amount the book and our
+    // balance of the path's first currency both allow, in that currency.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub funded_notional: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fundable: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct OpportunityLeg {
+    pub symbol: String,
+    pub venue_symbol: String,
+    pub side: TradeSide,
+    pub price: f64,
+    pub quantity: f64, // top-of-book quantity on the side being hit
+}
+
+// How the legs of an executed opportunity are sent: each after the previous
+// one fills, or all at once from balances already held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LegMode {
+    Sequential,
+    Concurrent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeInForce {
+    Ioc, // fill what is available now, cancel the rest
+    Fok, // fill completely or not at all
+}
+
+// Tickers dropped before detection, by reason.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RejectionCounts {
+    pub invalid_price: usize, // zero, negative or non-finite bid/ask
+    pub crossed_book: usize,  // bid above ask
+    pub low_volume: usize,    // below the requested minimum quote volume
+}
+
+impl RejectionCounts {
+    pub fn total(&self) -> usize {
+        self.invalid_price + self.crossed_book + self.low_volume
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ScanRequest {
-    pub exchange_id: u32,
+    #[serde(default)]
+    pub exchange_id: Option<u32>, // a single exchange
+    #[serde(default)]
+    pub exchanges: Option<ExchangeSelection>,
     pub min_profit: f64,
+    #[serde(default)]
+    pub min_quote_volume: Option<f64>,
+    #[serde(default)]
+    pub fundable_only: bool, // drop opportunities our balances can't fund
+    #[serde(default)]
+    pub fresh: bool, // fetch now instead of answering from the background scans
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExecuteRequest {
+    pub exchange_id: u32,
+    pub opportunity_id: String, // `id` of an opportunity from a recent /api/v1/scan
+    pub amount: f64, // of the path's first currency
+    #[serde(default)]
+    pub mode: Option<LegMode>,
+    #[serde(default)]
+    pub time_in_force: Option<TimeInForce>,
+    #[serde(default)]
+    pub dry_run: Option<bool>,
+}
+
+/// Exchanges a scan covers: `[1, 3]` or `"all"` for every enabled one.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ExchangeSelection {
+    Ids(Vec<u32>),
+    All(AllEnabled),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+pub enum AllEnabled {
+    #[serde(rename = "all")]
+    All,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ScanResponse {
-    pub opportunities: Vec<TriangularArbitrageOpportunity>,
+    pub opportunities: Vec<TriangularArbitrageOpportunity>, // every exchange's, best first
     pub total_pairs: usize,
     pub scan_time_ms: u128,
+    #[serde(default)]
+    pub age_ms: u64, // of the oldest exchange result included; 0 when all are live
+    #[serde(default)]
+    pub exchanges: Vec<ExchangeScanStatus>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExchangeScanState {
+    Ok,
+    Error,
+    Timeout,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExchangeScanStatus {
+    pub exchange_id: u32,
+    pub exchange: String,
+    pub status: ExchangeScanState,
+    pub total_pairs: usize,
+    pub opportunities: usize,
+    pub fetch_ms: u128,
+    pub detect_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(default)]
+    pub cached: bool, // served from the background scans
+    #[serde(default)]
+    pub age_ms: u64,  // since the scan finished
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
     pub error: String,
 }
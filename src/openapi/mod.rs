@@ -0,0 +1,59 @@
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+/// OpenAPI document for the `/api/v1` routes, served at `/api/openapi.json`.
+/// Schemas come from the request and response types through `ToSchema`.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Crypto Arbitrage Scanner API"),
+    modifiers(&AdminToken),
+    paths(
+        crate::get_exchanges,
+        crate::scan_arbitrage,
+        crate::execute_opportunity,
+        crate::get_account_balances,
+        crate::get_account_fees,
+        crate::get_paper,
+        crate::reset_paper,
+        crate::get_analytics,
+        crate::export_csv,
+        crate::history_opportunities,
+        crate::history_scans,
+        crate::history_alerts,
+        crate::list_alert_rules,
+        crate::create_alert_rule,
+        crate::get_alert_rule,
+        crate::update_alert_rule,
+        crate::delete_alert_rule,
+        crate::reload_config,
+        crate::halt_trading,
+        crate::resume_trading,
+        crate::get_risk,
+    ),
+    tags(
+        (name = "scan", description = "Exchanges and opportunity scans"),
+        (name = "execution", description = "Live and dry-run order execution"),
+        (name = "account", description = "Signed balance and fee queries"),
+        (name = "paper", description = "Simulated trading"),
+        (name = "analytics", description = "Rolling scan statistics"),
+        (name = "history", description = "Stored scans, opportunities and alerts"),
+        (name = "alerts", description = "Alert rules"),
+        (name = "admin", description = "Config reload and trading halts"),
+    )
+)]
+pub struct ApiDoc;
+
+/// `Authorization: Bearer <server.admin_token>`, required by the routes that
+/// list `admin_token` under `security`.
+struct AdminToken;
+
+impl Modify for AdminToken {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "admin_token",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+        }
+    }
+}
@@ -0,0 +1,493 @@
+use crate::arbitrage::{start_currency, Route};
+use crate::config::PaperConfig;
+use crate::exchanges::exchange_key;
+use crate::models::{OpportunityLeg, Ticker, TradeSide, TriangularArbitrageOpportunity};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Mutex;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// Trades kept for /api/v1/paper; older ones are dropped.
+const MAX_TRADES: usize = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PaperTradeStatus {
+    Completed, // every leg filled in full
+    Partial,   // every leg traded, but some only partly; see `stranded`
+    Aborted,   // stopped after some legs; what they bought stays in the ledger
+    Skipped,   // nothing traded
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaperFill {
+    pub symbol: String,
+    pub side: TradeSide,
+    pub from_currency: String,
+    pub to_currency: String,
+    pub price: f64,
+    pub requested: f64, // input currency the leg tried to spend
+    pub spent: f64,
+    pub received: f64, // output currency after the fee
+    pub fee: f64,      // in the output currency
+    pub book_time: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaperTrade {
+    pub id: String,
+    pub exchange: String,
+    pub path: String,
+    pub pairs: String,
+    pub expected_net_profit: f64,
+    pub detected_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    pub status: PaperTradeStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub start_currency: String,
+    pub amount_in: f64, // start currency offered to the first leg
+    // Start currency that made it through every leg, and what it came back
+    // as; set for completed and partial trades. PnL is over this part only.
+    pub cycled_in: Option<f64>,
+    pub amount_out: Option<f64>,
+    pub pnl: Option<f64>,
+    pub pnl_percentage: Option<f64>,
+    pub fills: Vec<PaperFill>,
+    // Intermediate currencies a partly filled leg left behind, in the ledger.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub stranded: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PaperSnapshot {
+    pub enabled: bool,
+    pub balances: BTreeMap<String, BTreeMap<String, f64>>, // exchange → asset → amount
+    pub pnl: BTreeMap<String, BTreeMap<String, f64>>,      // balances less starting balances
+    pub open_trades: Vec<String>,                          // exchanges with a trade waiting for its book
+    pub trades: Vec<PaperTrade>,                           // newest first
+}
+
+// A trade opened on one background round, waiting for a later round's book.
+struct PendingTrade {
+    opportunity: TriangularArbitrageOpportunity,
+    opened_at: DateTime<Utc>,
+    size: f64,
+    fee: f64, // taker fee as a fraction
+}
+
+struct PaperState {
+    starting_balances: BTreeMap<String, f64>,
+    balances: BTreeMap<String, BTreeMap<String, f64>>,
+    trades: VecDeque<PaperTrade>,
+    pending: HashMap<String, PendingTrade>, // by exchange
+}
+
+impl PaperState {
+    fn ledger(&mut self, exchange: &str) -> &mut BTreeMap<String, f64> {
+        let starting = &self.starting_balances;
+        self.balances
+            .entry(exchange.to_string())
+            .or_insert_with(|| starting.clone())
+    }
+
+    fn push(&mut self, trade: PaperTrade) {
+        match trade.status {
+            PaperTradeStatus::Completed | PaperTradeStatus::Partial => log::info!(
+                "Paper trade on {} {} {:?}: {:+.8} {} ({:+.4}%)",
+                trade.exchange,
+                trade.path,
+                trade.status,
+                trade.pnl.unwrap_or_default(),
+                trade.start_currency,
+                trade.pnl_percentage.unwrap_or_default()
+            ),
+            _ => log::info!(
+                "Paper trade on {} {} {:?}: {}",
+                trade.exchange,
+                trade.path,
+                trade.status,
+                trade.reason.as_deref().unwrap_or_default()
+            ),
+        }
+        self.trades.push_back(trade);
+        while self.trades.len() > MAX_TRADES {
+            self.trades.pop_front();
+        }
+    }
+}
+
+/// Simulates executing opportunities with a virtual balance ledger per
+/// exchange and asset, using only the books the background scans fetch.
+/// `open` sets aside `trade_fraction` of the path's first currency when a
+/// round finds an opportunity; `advance` then fills it, leg by leg up to
+/// each top-of-book quantity, against the first later round that finished
+/// at least `latency_ms` after it was opened. One trade runs per exchange at
+/// a time, so trades never compete for the same balance.
+pub struct PaperTrader {
+    state: Mutex<PaperState>,
+}
+
+impl PaperTrader {
+    pub fn new(config: &PaperConfig) -> Self {
+        Self {
+            state: Mutex::new(PaperState {
+                starting_balances: config.starting_balances.clone(),
+                balances: BTreeMap::new(),
+                trades: VecDeque::new(),
+                pending: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Clears trades and restores the starting balances from `config`.
+    /// Pending trades fill against the new ledger.
+    pub fn reset(&self, config: &PaperConfig) {
+        let mut state = self.state.lock().unwrap();
+        state.starting_balances = config.starting_balances.clone();
+        state.balances.clear();
+        state.trades.clear();
+    }
+
+    pub fn snapshot(&self, config: &PaperConfig) -> PaperSnapshot {
+        let state = self.state.lock().unwrap();
+        let pnl = state
+            .balances
+            .iter()
+            .map(|(exchange, balances)| {
+                let mut assets: BTreeMap<String, f64> = balances.clone();
+                for (asset, starting) in &state.starting_balances {
+                    *assets.entry(asset.clone()).or_insert(0.0) -= starting;
+                }
+                (exchange.clone(), assets)
+            })
+            .collect();
+
+        let mut open_trades: Vec<String> = state.pending.keys().cloned().collect();
+        open_trades.sort();
+
+        PaperSnapshot {
+            enabled: config.enabled,
+            balances: state.balances.clone(),
+            pnl,
+            open_trades,
+            trades: state.trades.iter().rev().cloned().collect(),
+        }
+    }
+
+    /// True when `opportunity` passes the configured filters.
+    pub fn accepts(config: &PaperConfig, opportunity: &TriangularArbitrageOpportunity) -> bool {
+        let key = exchange_key(&opportunity.exchange);
+        (config.exchanges.is_empty() || config.exchanges.iter().any(|e| exchange_key(e) == key))
+            && opportunity.net_profit_percentage >= config.min_net_profit
+            && opportunity.max_notional >= config.min_notional
+    }
+
+    /// Opens a paper trade of `opportunity` on `exchange`, charged
+    /// `taker_fee` percent per leg, unless one is already pending there.
+    pub fn open(&self, config: &PaperConfig, exchange: &str, taker_fee: f64, opportunity: &TriangularArbitrageOpportunity) {
+        self.open_at(config, exchange, taker_fee, opportunity, Utc::now());
+    }
+
+    fn open_at(
+        &self,
+        config: &PaperConfig,
+        exchange: &str,
+        taker_fee: f64,
+        opportunity: &TriangularArbitrageOpportunity,
+        now: DateTime<Utc>,
+    ) {
+        let mut state = self.state.lock().unwrap();
+        if state.pending.contains_key(exchange) {
+            return;
+        }
+
+        let start = start_currency(opportunity);
+        let balance = state.ledger(exchange).get(&start).copied().unwrap_or_default();
+        let size = balance * config.trade_fraction;
+        if size <= 0.0 {
+            let mut trade = new_trade(exchange, opportunity, now);
+            trade.status = PaperTradeStatus::Skipped;
+            trade.reason = Some(format!("No {} balance", trade.start_currency));
+            state.push(trade);
+            return;
+        }
+
+        state.pending.insert(
+            exchange.to_string(),
+            PendingTrade {
+                opportunity: opportunity.clone(),
+                opened_at: now,
+                size,
+                fee: taker_fee / 100.0,
+            },
+        );
+    }
+
+    /// Fills `exchange`'s pending trade against `tickers`, a background
+    /// round's book, once `latency_ms` has passed since it was opened.
+    pub fn advance(&self, config: &PaperConfig, exchange: &str, tickers: &[Ticker]) {
+        self.advance_at(config, exchange, tickers, Utc::now());
+    }
+
+    fn advance_at(&self, config: &PaperConfig, exchange: &str, tickers: &[Ticker], now: DateTime<Utc>) {
+        let mut state = self.state.lock().unwrap();
+        let due = state
+            .pending
+            .get(exchange)
+            .is_some_and(|pending| now - pending.opened_at >= Duration::milliseconds(config.latency_ms as i64));
+        if !due {
+            return;
+        }
+        let pending = state.pending.remove(exchange).expect("checked above");
+        let trade = fill_trade(state.ledger(exchange), exchange, &pending, tickers, now);
+        state.push(trade);
+    }
+}
+
+fn new_trade(exchange: &str, opportunity: &TriangularArbitrageOpportunity, now: DateTime<Utc>) -> PaperTrade {
+    PaperTrade {
+        id: Uuid::new_v4().to_string(),
+        exchange: exchange.to_string(),
+        path: opportunity.path.clone(),
+        pairs: opportunity.pairs.clone(),
+        expected_net_profit: opportunity.net_profit_percentage,
+        detected_at: opportunity.timestamp,
+        finished_at: now,
+        status: PaperTradeStatus::Completed,
+        reason: None,
+        start_currency: start_currency(opportunity),
+        amount_in: 0.0,
+        cycled_in: None,
+        amount_out: None,
+        pnl: None,
+        pnl_percentage: None,
+        fills: Vec::new(),
+        stranded: BTreeMap::new(),
+    }
+}
+
+// Puts the pending trade through its legs against `tickers`, booking each
+// fill in `ledger`.
+fn fill_trade(
+    ledger: &mut BTreeMap<String, f64>,
+    exchange: &str,
+    pending: &PendingTrade,
+    tickers: &[Ticker],
+    now: DateTime<Utc>,
+) -> PaperTrade {
+    let mut trade = new_trade(exchange, &pending.opportunity, now);
+    trade.amount_in = pending.size;
+
+    let mut holding = (trade.start_currency.clone(), pending.size);
+    // Share of `size` that got through every leg so far.
+    let mut cycled = 1.0;
+    for leg in &pending.opportunity.legs {
+        match fill(ledger, leg, pending.fee, &holding, tickers, now) {
+            Ok(fill) => {
+                // A buy spends `amount / price * price`, which can miss the
+                // amount by rounding alone.
+                let share = fill.spent / fill.requested;
+                if share < 1.0 - 1e-9 {
+                    cycled *= share;
+                    if !trade.fills.is_empty() {
+                        *trade.stranded.entry(fill.from_currency.clone()).or_insert(0.0) += fill.requested - fill.spent;
+                    }
+                }
+                holding = (fill.to_currency.clone(), fill.received);
+                trade.fills.push(fill);
+            }
+            Err(reason) => {
+                trade.status = if trade.fills.is_empty() {
+                    PaperTradeStatus::Skipped
+                } else {
+                    PaperTradeStatus::Aborted
+                };
+                trade.reason = Some(reason);
+                break;
+            }
+        }
+    }
+
+    if trade.status == PaperTradeStatus::Completed && holding.0 == trade.start_currency {
+        if cycled < 1.0 {
+            trade.status = PaperTradeStatus::Partial;
+        }
+        let cycled_in = pending.size * cycled;
+        let (_, amount) = holding;
+        trade.cycled_in = Some(cycled_in);
+        trade.amount_out = Some(amount);
+        trade.pnl = Some(amount - cycled_in);
+        trade.pnl_percentage = Some((amount - cycled_in) / cycled_in * 100.0);
+    }
+    trade
+}
+
+// Trades `holding` through one leg against `tickers` and books the fill in
+// the ledger. What a partial fill leaves unspent stays in the ledger.
+fn fill(
+    ledger: &mut BTreeMap<String, f64>,
+    leg: &OpportunityLeg,
+    fee: f64,
+    holding: &(String, f64),
+    tickers: &[Ticker],
+    book_time: DateTime<Utc>,
+) -> Result<PaperFill, String> {
+    let ticker = tickers
+        .iter()
+        .find(|t| t.symbol == leg.symbol)
+        .ok_or_else(|| format!("{} is no longer listed", leg.symbol))?;
+
+    let (currency, requested) = holding;
+    let route = Route::new(ticker, leg.side)
+        .filter(|route| route.from == *currency)
+        .ok_or_else(|| format!("{} does not trade {}", leg.symbol, currency))?;
+    if route.available <= 0.0 {
+        return Err(format!("{} has an empty book", leg.symbol));
+    }
+
+    let (spent, out) = route.fill(*requested);
+    let received = out * (1.0 - fee);
+    *ledger.entry(currency.clone()).or_insert(0.0) -= spent;
+    *ledger.entry(route.to.clone()).or_insert(0.0) += received;
+
+    Ok(PaperFill {
+        symbol: leg.symbol.clone(),
+        side: route.side,
+        from_currency: currency.clone(),
+        to_currency: route.to,
+        price: route.price,
+        requested: *requested,
+        spent,
+        received,
+        fee: out - received,
+        book_time,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::arbitrage::ArbitrageDetector;
+    use crate::test_support::ticker;
+
+    // BTC → USDT → ETH → BTC returns 40000 / 2000 * 0.0505 = 1.01 before fees.
+    fn book(eth_usdt_qty: f64) -> Vec<Ticker> {
+        vec![
+            ticker("BTC", "USDT", 40000.0, 40010.0, 10.0),
+            ticker("ETH", "USDT", 1999.0, 2000.0, eth_usdt_qty),
+            ticker("ETH", "BTC", 0.0505, 0.0506, 100.0),
+        ]
+    }
+
+    fn opportunity() -> TriangularArbitrageOpportunity {
+        let mut found = ArbitrageDetector::find_triangular_opportunities(&book(100.0), "Mock", 0.0, 0.1, 10);
+        assert_eq!(found.len(), 1);
+        found.remove(0)
+    }
+
+    fn trader() -> (PaperTrader, PaperConfig) {
+        let config = PaperConfig {
+            enabled: true,
+            ..PaperConfig::default()
+        };
+        (PaperTrader::new(&config), config)
+    }
+
+    fn ms(ms: i64) -> Duration {
+        Duration::milliseconds(ms)
+    }
+
+    #[test]
+    fn fills_against_the_first_round_after_the_latency() {
+        let (trader, config) = trader();
+        let opened = Utc::now();
+        trader.open_at(&config, "Mock", 0.1, &opportunity(), opened);
+
+        // Too early: the trade waits for a later round.
+        trader.advance_at(&config, "Mock", &book(100.0), opened + ms(100));
+        let snapshot = trader.snapshot(&config);
+        assert_eq!(snapshot.open_trades, ["Mock"]);
+        assert!(snapshot.trades.is_empty());
+
+        trader.advance_at(&config, "Mock", &book(100.0), opened + ms(300));
+        let snapshot = trader.snapshot(&config);
+        assert!(snapshot.open_trades.is_empty());
+        let trade = &snapshot.trades[0];
+        assert_eq!(trade.status, PaperTradeStatus::Completed, "{:?}", trade.reason);
+        assert_eq!(trade.fills.len(), 3);
+        assert_eq!(trade.amount_in, 0.025);
+        assert_eq!(trade.cycled_in, Some(0.025));
+
+        // 1.01 less three 0.1% fees.
+        let expected = 0.025 * 1.01 * 0.999f64.powi(3);
+        assert!((trade.amount_out.unwrap() - expected).abs() < 1e-12);
+        assert!((trade.pnl.unwrap() - (expected - 0.025)).abs() < 1e-12);
+        let ledger = &snapshot.balances["Mock"];
+        assert!((ledger["BTC"] - (0.25 - 0.025 + expected)).abs() < 1e-12);
+        assert!((ledger["USDT"] - 10_000.0).abs() < 1e-9);
+        assert!((ledger["ETH"] - 5.0).abs() < 1e-12);
+        assert!((snapshot.pnl["Mock"]["BTC"] - trade.pnl.unwrap()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn partial_fills_count_only_what_cycled() {
+        let (trader, config) = trader();
+        let opened = Utc::now();
+        trader.open_at(&config, "Mock", 0.1, &opportunity(), opened);
+        // Only 0.3 ETH is offered: 600 of the 999 USDT buy it.
+        trader.advance_at(&config, "Mock", &book(0.3), opened + ms(300));
+
+        let snapshot = trader.snapshot(&config);
+        let trade = &snapshot.trades[0];
+        assert_eq!(trade.status, PaperTradeStatus::Partial);
+        let cycled_in = 0.025 * 600.0 / 999.0;
+        assert!((trade.cycled_in.unwrap() - cycled_in).abs() < 1e-12);
+        let expected = cycled_in * 1.01 * 0.999f64.powi(3);
+        assert!((trade.pnl.unwrap() - (expected - cycled_in)).abs() < 1e-12);
+        assert!(trade.pnl.unwrap() > 0.0);
+        assert!((trade.stranded["USDT"] - 399.0).abs() < 1e-9);
+        assert!((snapshot.balances["Mock"]["USDT"] - 10_399.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn missing_books_skip_or_abort() {
+        let (trader, config) = trader();
+        let opened = Utc::now();
+
+        trader.open_at(&config, "Mock", 0.1, &opportunity(), opened);
+        trader.advance_at(&config, "Mock", &book(100.0)[1..], opened + ms(300));
+        trader.open_at(&config, "Mock", 0.1, &opportunity(), opened + ms(300));
+        trader.advance_at(&config, "Mock", &book(100.0)[..2], opened + ms(600));
+
+        let snapshot = trader.snapshot(&config);
+        let (aborted, skipped) = (&snapshot.trades[0], &snapshot.trades[1]);
+        assert_eq!(skipped.status, PaperTradeStatus::Skipped);
+        assert_eq!(skipped.reason.as_deref(), Some("BTCUSDT is no longer listed"));
+        assert!(skipped.fills.is_empty());
+        assert_eq!(aborted.status, PaperTradeStatus::Aborted);
+        assert_eq!(aborted.fills.len(), 2);
+        assert_eq!(aborted.pnl, None);
+        // What the first two legs bought stays in the ledger.
+        assert!((snapshot.balances["Mock"]["ETH"] - (5.0 + aborted.fills[1].received)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn one_trade_per_exchange_and_none_without_balance() {
+        let (trader, mut config) = trader();
+        let opened = Utc::now();
+        trader.open_at(&config, "Mock", 0.1, &opportunity(), opened);
+        trader.open_at(&config, "Mock", 0.1, &opportunity(), opened);
+        assert_eq!(trader.snapshot(&config).open_trades, ["Mock"]);
+
+        config.starting_balances.remove("BTC");
+        trader.reset(&config);
+        trader.open_at(&config, "Other", 0.1, &opportunity(), opened);
+        let snapshot = trader.snapshot(&config);
+        assert_eq!(snapshot.open_trades, ["Mock"]);
+        assert_eq!(snapshot.trades[0].status, PaperTradeStatus::Skipped);
+        assert_eq!(snapshot.trades[0].reason.as_deref(), Some("No BTC balance"));
+    }
+}
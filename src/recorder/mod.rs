@@ -0,0 +1,266 @@
+use crate::exchanges::{exchange_key, Exchange};
+use crate::models::Ticker;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// One line of a recording: a ticker batch exactly as the venue adapter
+/// returned it, before currency normalization.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedBatch {
+    pub timestamp: DateTime<Utc>,
+    pub exchange: String,
+    pub tickers: Vec<Ticker>,
+}
+
+/// Appends ticker batches to `{exchange}-YYYY-MM-DD.ndjson.gz` in
+/// `directory`. Each batch is its own gzip member, so a file cut off
+/// mid-write loses at most the last batch and readers need
+/// `MultiGzDecoder`.
+pub struct Recorder {
+    directory: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl Recorder {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn record(&self, exchange: &str, tickers: &[Ticker]) -> Result<(), Box<dyn std::error::Error>> {
+        self.record_at(exchange, tickers, Utc::now())
+    }
+
+    fn record_at(&self, exchange: &str, tickers: &[Ticker], timestamp: DateTime<Utc>) -> Result<(), Box<dyn std::error::Error>> {
+        let mut line = serde_json::to_vec(&RecordedBatch {
+            timestamp,
+            exchange: exchange.to_string(),
+            tickers: tickers.to_vec(),
+        })?;
+        line.push(b'\n');
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&line)?;
+        let compressed = encoder.finish()?;
+
+        let _guard = self.write_lock.lock().unwrap();
+        std::fs::create_dir_all(&self.directory)?;
+
+        let path = self.directory.join(format!(
+            "{}-{}.ndjson.gz",
+            exchange_key(exchange),
+            timestamp.format("%Y-%m-%d")
+        ));
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(&compressed)?;
+
+        Ok(())
+    }
+}
+
+/// Wraps a venue adapter and records every batch it fetches. Failed fetches
+/// are not recorded.
+pub struct RecordingExchange {
+    inner: Box<dyn Exchange>,
+    recorder: Arc<Recorder>,
+}
+
+impl RecordingExchange {
+    pub fn new(inner: Box<dyn Exchange>, recorder: Arc<Recorder>) -> Self {
+        Self { inner, recorder }
+    }
+}
+
+#[async_trait]
+impl Exchange for RecordingExchange {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn id(&self) -> u32 {
+        self.inner.id()
+    }
+
+    async fn fetch_tickers(&self) -> Result<Vec<Ticker>, Box<dyn std::error::Error>> {
+        let tickers = self.inner.fetch_tickers().await?;
+        // A full disk shouldn't stop the scan.
+        if let Err(e) = self.recorder.record(self.inner.name(), &tickers) {
+            log::error!("Recording {} tickers failed: {}", self.inner.name(), e);
+        }
+        Ok(tickers)
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.inner.is_enabled()
+    }
+
+    fn taker_fee(&self) -> f64 {
+        self.inner.taker_fee()
+    }
+}
+
+/// Streams the batches of a recording in file order. A file that ends
+/// mid-batch, as one being written or cut off by a crash does, ends the
+/// stream at the last complete batch; any other error ends it after the error.
+pub struct BatchReader {
+    path: PathBuf,
+    lines: std::io::Lines<BufReader<MultiGzDecoder<File>>>,
+    finished: bool,
+}
+
+impl BatchReader {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            lines: BufReader::new(MultiGzDecoder::new(file)).lines(),
+            finished: false,
+        })
+    }
+}
+
+impl Iterator for BatchReader {
+    type Item = Result<RecordedBatch, Box<dyn std::error::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        loop {
+            let line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => {
+                    self.finished = true;
+                    if e.kind() == std::io::ErrorKind::UnexpectedEof {
+                        log::warn!("{} ends mid-batch; skipping the truncated last batch", self.path.display());
+                        return None;
+                    }
+                    return Some(Err(format!("Failed to read {}: {}", self.path.display(), e).into()));
+                }
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Some(
+                serde_json::from_str(&line)
+                    .map_err(|e| format!("Bad batch in {}: {}", self.path.display(), e).into()),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::exchanges::replay::ReplayExchange;
+    use crate::test_support::{temp_dir, ticker};
+    use chrono::TimeZone;
+    use std::time::{Duration, Instant};
+
+    fn at(millis: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap() + chrono::Duration::milliseconds(millis)
+    }
+
+    // Three batches 300ms apart, the first with Kraken's spelling of BTC.
+    fn record(name: &str) -> PathBuf {
+        let directory = temp_dir(name);
+        let recorder = Recorder::new(&directory);
+        recorder.record_at("Binance", &[ticker("XBT", "USDT", 100.0, 101.0, 1.0)], at(0)).unwrap();
+        recorder.record_at("Binance", &[ticker("ETH", "USDT", 10.0, 10.1, 2.0)], at(300)).unwrap();
+        recorder.record_at("Binance", &[ticker("ETH", "BTC", 0.1, 0.11, 3.0)], at(600)).unwrap();
+        directory.join("binance-2024-03-01.ndjson.gz")
+    }
+
+    fn symbols(batch: &RecordedBatch) -> Vec<&str> {
+        batch.tickers.iter().map(|t| t.symbol.as_str()).collect()
+    }
+
+    #[test]
+    fn reads_back_every_batch_in_order() {
+        let batches: Vec<RecordedBatch> = BatchReader::open(record("recorder-read")).unwrap().map(Result::unwrap).collect();
+
+        assert_eq!(batches.len(), 3);
+        assert_eq!(symbols(&batches[0]), ["XBTUSDT"]);
+        assert_eq!(symbols(&batches[2]), ["ETHBTC"]);
+        assert_eq!((batches[1].exchange.as_str(), batches[1].timestamp), ("Binance", at(300)));
+        assert_eq!(batches[2].tickers[0].ask_qty, 3.0);
+    }
+
+    #[tokio::test]
+    async fn a_truncated_last_member_loses_only_the_last_batch() {
+        let path = record("recorder-truncated");
+        let length = std::fs::metadata(&path).unwrap().len();
+        OpenOptions::new().write(true).open(&path).unwrap().set_len(length - 10).unwrap();
+
+        let batches: Vec<RecordedBatch> = BatchReader::open(&path).unwrap().map(Result::unwrap).collect();
+        assert_eq!(batches.iter().map(symbols).collect::<Vec<_>>(), [["XBTUSDT"], ["ETHUSDT"]]);
+
+        let replay = ReplayExchange::open(&path, 0.0, &Config::default()).unwrap();
+        replay.fetch_tickers().await.unwrap();
+        replay.fetch_tickers().await.unwrap();
+        assert!(replay.is_finished());
+    }
+
+    #[tokio::test]
+    async fn replays_normalized_batches_at_the_recorded_pace() {
+        let replay = ReplayExchange::open(record("recorder-paced"), 2.0, &Config::default()).unwrap();
+        assert_eq!(replay.name(), "Binance");
+
+        let started = Instant::now();
+        let first = replay.fetch_tickers().await.unwrap();
+        assert_eq!(first[0].symbol, "BTCUSDT");
+        assert_eq!(first[0].venue_symbol, "XBTUSDT");
+        replay.fetch_tickers().await.unwrap();
+        replay.fetch_tickers().await.unwrap();
+
+        // 600ms of recording at double speed.
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(290), "{:?}", elapsed);
+        assert!(elapsed < Duration::from_millis(600), "{:?}", elapsed);
+
+        assert!(replay.is_finished());
+        let error = replay.fetch_tickers().await.unwrap_err();
+        assert_eq!(error.to_string(), "Replay finished: no more recorded batches");
+    }
+
+    #[tokio::test]
+    async fn replays_unpaced_without_waiting() {
+        let replay = ReplayExchange::open(record("recorder-unpaced"), 0.0, &Config::default()).unwrap();
+
+        let started = Instant::now();
+        for _ in 0..3 {
+            replay.fetch_tickers().await.unwrap();
+        }
+        assert!(started.elapsed() < Duration::from_millis(200));
+        assert!(replay.fetch_tickers().await.is_err());
+    }
+
+    #[test]
+    fn stops_after_a_corrupt_batch() {
+        let path = record("recorder-corrupt");
+        let mut bytes = std::fs::read(&path).unwrap();
+        bytes.extend_from_slice(b"not gzip at all");
+        std::fs::write(&path, bytes).unwrap();
+
+        let mut reader = BatchReader::open(&path).unwrap();
+        for _ in 0..3 {
+            reader.next().unwrap().unwrap();
+        }
+        let error = reader.next().unwrap().unwrap_err().to_string();
+        assert!(error.starts_with("Failed to read "), "{}", error);
+        assert!(reader.next().is_none());
+    }
+}
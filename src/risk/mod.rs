@@ -0,0 +1,397 @@
+use crate::config::RiskConfig;
+use crate::execution::{ExecutionReport, OrderPurpose, OrderStatus};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Halt {
+    pub reason: String,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RiskSnapshot {
+    pub halted: Option<Halt>,
+    pub day: NaiveDate,
+    pub daily_pnl: BTreeMap<String, f64>,     // by asset, live executions only
+    pub open_exposure: BTreeMap<String, f64>, // by asset
+    pub consecutive_failures: BTreeMap<String, u32>, // by exchange
+    pub blocked_exchanges: Vec<String>,
+}
+
+// Live executions only: dry runs are checked against the limits but never
+// change them.
+struct RiskState {
+    halted: Option<Halt>,
+    day: NaiveDate,
+    daily_pnl: BTreeMap<String, f64>,
+    in_flight: BTreeMap<String, f64>,
+    residuals: BTreeMap<String, f64>,
+    consecutive_failures: BTreeMap<String, u32>,
+}
+
+impl RiskState {
+    fn roll_day(&mut self) {
+        let today = Utc::now().date_naive();
+        if today != self.day {
+            self.day = today;
+            self.daily_pnl.clear();
+        }
+    }
+
+    fn exposure(&self, asset: &str) -> f64 {
+        self.in_flight.get(asset).copied().unwrap_or_default() + self.residuals.get(asset).copied().unwrap_or_default().abs()
+    }
+}
+
+/// Guardrails every execution passes through: `admit` before anything is
+/// sent, `settle` with the finished report. Refusals and state changes are
+/// logged here so the limits are enforced in one place.
+pub struct RiskManager {
+    state: Mutex<RiskState>,
+}
+
+impl Default for RiskManager {
+    fn default() -> Self {
+        Self {
+            state: Mutex::new(RiskState {
+                halted: None,
+                day: Utc::now().date_naive(),
+                daily_pnl: BTreeMap::new(),
+                in_flight: BTreeMap::new(),
+                residuals: BTreeMap::new(),
+                consecutive_failures: BTreeMap::new(),
+            }),
+        }
+    }
+}
+
+impl RiskManager {
+    /// Checks an execution of `amount` of `path`'s first currency. A live
+    /// execution that passes is counted as open exposure until `settle`.
+    pub fn admit(&self, limits: &RiskConfig, exchange: &str, path: &str, amount: f64, live: bool) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        state.roll_day();
+
+        let currencies: Vec<&str> = path.split(" → ").map(str::trim).collect();
+        let start = currencies.first().copied().unwrap_or_default();
+        let checked = Self::check(&state, limits, exchange, &currencies, amount, live);
+        if let Err(reason) = &checked {
+            log::warn!(
+                "Risk check refused {} execution of {} {} on {} ({}): {}",
+                if live { "live" } else { "dry-run" },
+                amount,
+                start,
+                exchange,
+                path,
+                reason
+            );
+            return checked;
+        }
+
+        if live {
+            *state.in_flight.entry(start.to_string()).or_default() += amount;
+        }
+        Ok(())
+    }
+
+    fn check(
+        state: &RiskState,
+        limits: &RiskConfig,
+        exchange: &str,
+        currencies: &[&str],
+        amount: f64,
+        live: bool,
+    ) -> Result<(), String> {
+        if let Some(halt) = &state.halted {
+            return Err(format!("trading is halted: {}", halt.reason));
+        }
+
+        // Real money only moves from a currency the operator has set limits
+        // for; a dry run can start anywhere.
+        let start = currencies.first().copied().unwrap_or_default();
+        if live {
+            for (name, table) in [
+                ("max_notional", &limits.max_notional),
+                ("max_daily_loss", &limits.max_daily_loss),
+                ("max_open_exposure", &limits.max_open_exposure),
+            ] {
+                if !table.contains_key(start) {
+                    return Err(format!("{} has no risk.{} configured", start, name));
+                }
+            }
+        }
+
+        let failures = state.consecutive_failures.get(exchange).copied().unwrap_or_default();
+        if limits.max_consecutive_failures > 0 && failures >= limits.max_consecutive_failures {
+            return Err(format!(
+                "{} is blocked after {} consecutive failed legs",
+                exchange, failures
+            ));
+        }
+
+        if let Some(limit) = limits.max_notional.get(start) {
+            if amount > *limit {
+                return Err(format!("{} {} is above risk.max_notional of {}", amount, start, limit));
+            }
+        }
+        if let Some(limit) = limits.max_daily_loss.get(start) {
+            let loss = -state.daily_pnl.get(start).copied().unwrap_or_default();
+            if loss >= *limit {
+                return Err(format!("today's {} loss of {} reached risk.max_daily_loss of {}", start, loss, limit));
+            }
+        }
+
+        // The first currency is committed in full; the others are only held
+        // briefly, so they just must not already be over their limit.
+        for (i, currency) in currencies.iter().enumerate() {
+            let Some(limit) = limits.max_open_exposure.get(*currency) else {
+                continue;
+            };
+            let exposure = state.exposure(currency) + if i == 0 { amount } else { 0.0 };
+            if exposure > *limit {
+                return Err(format!(
+                    "{} exposure of {} would exceed risk.max_open_exposure of {}",
+                    currency, exposure, limit
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the outcome of an execution that `admit` let through.
+    pub fn settle(&self, limits: &RiskConfig, report: &ExecutionReport) {
+        if report.dry_run {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.roll_day();
+
+        if let Some(in_flight) = state.in_flight.get_mut(&report.start_currency) {
+            *in_flight = (*in_flight - report.amount_in).max(0.0);
+        }
+        *state.daily_pnl.entry(report.start_currency.clone()).or_default() += report.pnl;
+        for (asset, amount) in &report.residuals {
+            *state.residuals.entry(asset.clone()).or_default() += amount;
+        }
+
+        // A leg that went out and filled resets the count; one that errored
+        // or filled nothing adds to it.
+        let failures = state.consecutive_failures.entry(report.exchange.clone()).or_default();
+        let before = *failures;
+        for order in report.orders.iter().filter(|o| o.purpose == OrderPurpose::Leg) {
+            match &order.result {
+                Some(result) if result.status != OrderStatus::Unfilled => *failures = 0,
+                _ => *failures += 1,
+            }
+        }
+        let failures = *failures;
+        if limits.max_consecutive_failures > 0
+            && before < limits.max_consecutive_failures
+            && failures >= limits.max_consecutive_failures
+        {
+            log::error!(
+                "Circuit breaker open for {} after {} consecutive failed legs; POST /api/v1/admin/resume to clear",
+                report.exchange,
+                failures
+            );
+        }
+
+        let loss = -state.daily_pnl.get(&report.start_currency).copied().unwrap_or_default();
+        if let Some(limit) = limits.max_daily_loss.get(&report.start_currency) {
+            if loss >= *limit {
+                log::error!(
+                    "Daily {} loss of {} reached risk.max_daily_loss of {}; further {} executions are refused today",
+                    report.start_currency,
+                    loss,
+                    limit,
+                    report.start_currency
+                );
+            }
+        }
+    }
+
+    /// Refuses every execution until `resume`. Executions already running
+    /// finish, including their unwind.
+    pub fn halt(&self, reason: &str) -> Halt {
+        let halt = Halt {
+            reason: reason.to_string(),
+            at: Utc::now(),
+        };
+        self.state.lock().unwrap().halted = Some(halt.clone());
+        log::error!("Trading halted: {}", reason);
+        halt
+    }
+
+    /// Lifts a halt, closes every circuit breaker and forgets leftover
+    /// positions, which the operator is taken to have dealt with.
+    pub fn resume(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.halted = None;
+        state.consecutive_failures.clear();
+        state.residuals.clear();
+        log::warn!("Trading resumed");
+    }
+
+    pub fn snapshot(&self, limits: &RiskConfig) -> RiskSnapshot {
+        let mut state = self.state.lock().unwrap();
+        state.roll_day();
+
+        let mut open_exposure = BTreeMap::new();
+        for asset in state.in_flight.keys().chain(state.residuals.keys()) {
+            let exposure = state.exposure(asset);
+            if exposure != 0.0 {
+                open_exposure.insert(asset.clone(), exposure);
+            }
+        }
+        let blocked_exchanges = state
+            .consecutive_failures
+            .iter()
+            .filter(|(_, failures)| limits.max_consecutive_failures > 0 && **failures >= limits.max_consecutive_failures)
+            .map(|(exchange, _)| exchange.clone())
+            .collect();
+
+        RiskSnapshot {
+            halted: state.halted.clone(),
+            day: state.day,
+            daily_pnl: state.daily_pnl.clone(),
+            open_exposure,
+            consecutive_failures: state.consecutive_failures.clone(),
+            blocked_exchanges,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::execution::{ExecutedOrder, ExecutionStatus, OrderRequest, OrderResult};
+    use crate::models::{LegMode, TimeInForce, TradeSide};
+
+    const PATH: &str = "USDT → BTC → ETH → USDT";
+
+    fn leg(filled: Option<f64>) -> ExecutedOrder {
+        ExecutedOrder {
+            purpose: OrderPurpose::Leg,
+            request: OrderRequest {
+                symbol: "BTCUSDT".to_string(),
+                side: TradeSide::Buy,
+                quantity: "1".to_string(),
+                price: "1".to_string(),
+                time_in_force: TimeInForce::Ioc,
+                client_order_id: "test".to_string(),
+            },
+            result: filled.map(|filled| OrderResult::new("1".to_string(), 1.0, filled, 1.0)),
+            error: filled.is_none().then(|| "rejected".to_string()),
+        }
+    }
+
+    fn report(amount: f64, pnl: f64, orders: Vec<ExecutedOrder>) -> ExecutionReport {
+        ExecutionReport {
+            id: "test".to_string(),
+            exchange: "Binance".to_string(),
+            path: PATH.to_string(),
+            pairs: String::new(),
+            dry_run: false,
+            mode: LegMode::Sequential,
+            time_in_force: TimeInForce::Ioc,
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+            status: ExecutionStatus::Completed,
+            reason: None,
+            start_currency: "USDT".to_string(),
+            amount_in: amount,
+            pnl,
+            orders,
+            residuals: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn live_executions_need_limits_for_their_start_currency() {
+        let risk = RiskManager::default();
+        let mut limits = RiskConfig::default();
+        limits.max_daily_loss.remove("USDT");
+
+        let refused = risk.admit(&limits, "Binance", PATH, 10.0, true).unwrap_err();
+        assert!(refused.contains("max_daily_loss"), "{}", refused);
+        // A dry run risks nothing, so it may start anywhere.
+        assert!(risk.admit(&limits, "Binance", PATH, 10.0, false).is_ok());
+        assert!(risk.admit(&limits, "Binance", "DOGE → BTC → ETH → DOGE", 10.0, false).is_ok());
+    }
+
+    #[test]
+    fn notional_and_exposure_limits_apply() {
+        let risk = RiskManager::default();
+        let limits = RiskConfig::default();
+
+        assert!(risk.admit(&limits, "Binance", PATH, 101.0, true).is_err());
+        for _ in 0..5 {
+            risk.admit(&limits, "Binance", PATH, 100.0, true).unwrap();
+        }
+        // 500 USDT is in flight, the limit.
+        let refused = risk.admit(&limits, "Binance", PATH, 1.0, true).unwrap_err();
+        assert!(refused.contains("max_open_exposure"), "{}", refused);
+
+        risk.settle(&limits, &report(100.0, 0.5, vec![leg(Some(1.0))]));
+        assert!(risk.admit(&limits, "Binance", PATH, 1.0, true).is_ok());
+    }
+
+    #[test]
+    fn settling_losses_stops_the_day() {
+        let risk = RiskManager::default();
+        let limits = RiskConfig::default();
+
+        risk.admit(&limits, "Binance", PATH, 100.0, true).unwrap();
+        risk.settle(&limits, &report(100.0, -20.0, vec![leg(Some(1.0))]));
+
+        let snapshot = risk.snapshot(&limits);
+        assert_eq!(snapshot.daily_pnl["USDT"], -20.0);
+        assert!(snapshot.open_exposure.is_empty());
+        let refused = risk.admit(&limits, "Binance", PATH, 1.0, true).unwrap_err();
+        assert!(refused.contains("max_daily_loss"), "{}", refused);
+    }
+
+    #[test]
+    fn failed_legs_open_the_breaker_until_resume() {
+        let risk = RiskManager::default();
+        let limits = RiskConfig::default();
+
+        risk.settle(&limits, &report(10.0, 0.0, vec![leg(None), leg(Some(0.0))]));
+        assert!(risk.admit(&limits, "Binance", PATH, 10.0, true).is_ok());
+        let mut leftover = report(10.0, 0.0, vec![leg(None)]);
+        leftover.residuals.insert("BTC".to_string(), 0.001);
+        risk.settle(&limits, &leftover);
+
+        let snapshot = risk.snapshot(&limits);
+        assert_eq!(snapshot.blocked_exchanges, ["Binance"]);
+        assert_eq!(snapshot.open_exposure["BTC"], 0.001);
+        assert!(risk.admit(&limits, "Binance", PATH, 10.0, false).is_err());
+        assert!(risk.admit(&limits, "Bybit", PATH, 10.0, false).is_ok());
+
+        risk.resume();
+        let snapshot = risk.snapshot(&limits);
+        assert!(snapshot.blocked_exchanges.is_empty());
+        assert!(snapshot.open_exposure.is_empty());
+        assert!(risk.admit(&limits, "Binance", PATH, 10.0, true).is_ok());
+    }
+
+    #[test]
+    fn dry_runs_leave_the_state_alone() {
+        let risk = RiskManager::default();
+        let limits = RiskConfig::default();
+
+        risk.admit(&limits, "Binance", PATH, 100.0, false).unwrap();
+        let mut dry_run = report(100.0, -50.0, vec![leg(None), leg(None), leg(None)]);
+        dry_run.dry_run = true;
+        risk.settle(&limits, &dry_run);
+
+        let snapshot = risk.snapshot(&limits);
+        assert!(snapshot.daily_pnl.is_empty());
+        assert!(snapshot.open_exposure.is_empty());
+        assert!(snapshot.consecutive_failures.is_empty());
+    }
+}
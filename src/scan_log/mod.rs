@@ -0,0 +1,93 @@
+use crate::models::RejectionCounts;
+use crate::scanner::ScanOutcome;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// One line of `scans-YYYY-MM-DD.jsonl`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanLogRecord {
+    pub timestamp: DateTime<Utc>,
+    pub exchange: String,
+    pub ticker_count: usize,
+    pub rejected: RejectionCounts,
+    pub rejected_total: usize,
+    pub fetch_ms: u128,
+    pub detect_ms: u128,
+    pub opportunity_count: usize,
+    pub opportunities: Vec<LoggedOpportunity>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggedOpportunity {
+    pub id: String,
+    pub path: String,
+    pub pairs: String,
+    pub gross_profit_percentage: f64,
+    pub net_profit_percentage: f64,
+    pub max_notional: f64,
+}
+
+impl ScanLogRecord {
+    pub fn from_outcome(outcome: &ScanOutcome) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            exchange: outcome.exchange.clone(),
+            ticker_count: outcome.total_pairs,
+            rejected: outcome.rejected.clone(),
+            rejected_total: outcome.rejected.total(),
+            fetch_ms: outcome.fetch_ms,
+            detect_ms: outcome.detect_ms,
+            opportunity_count: outcome.opportunities.len(),
+            opportunities: outcome
+                .opportunities
+                .iter()
+                .map(|o| LoggedOpportunity {
+                    id: o.id.clone(),
+                    path: o.path.clone(),
+                    pairs: o.pairs.clone(),
+                    gross_profit_percentage: o.gross_profit_percentage,
+                    net_profit_percentage: o.net_profit_percentage,
+                    max_notional: o.max_notional,
+                })
+                .collect(),
+            error: outcome.error.clone(),
+        }
+    }
+}
+
+/// Appends one JSON object per scan to `scans-YYYY-MM-DD.jsonl` in
+/// `directory`, starting a new file each UTC day.
+pub struct ScanLogger {
+    directory: PathBuf,
+    write_lock: Mutex<()>,
+}
+
+impl ScanLogger {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            write_lock: Mutex::new(()),
+        }
+    }
+
+    pub fn record(&self, outcome: &ScanOutcome) -> Result<(), Box<dyn std::error::Error>> {
+        let mut line = serde_json::to_string(&ScanLogRecord::from_outcome(outcome))?;
+        line.push('\n');
+
+        let _guard = self.write_lock.lock().unwrap();
+        std::fs::create_dir_all(&self.directory)?;
+
+        let path = self
+            .directory
+            .join(format!("scans-{}.jsonl", Utc::now().format("%Y-%m-%d")));
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(line.as_bytes())?;
+
+        Ok(())
+    }
+}
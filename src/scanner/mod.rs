@@ -0,0 +1,141 @@
+use crate::arbitrage::ArbitrageDetector;
+use crate::config::Config;
+use crate::exchanges::Exchange;
+use crate::models::{RejectionCounts, Ticker, TriangularArbitrageOpportunity};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Result of one fetch → sanitize → detect pass over a single exchange.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanOutcome {
+    pub exchange: String,
+    pub total_pairs: usize,
+    pub rejected: RejectionCounts,
+    pub fetch_ms: u128,
+    pub detect_ms: u128,
+    pub opportunities: Vec<TriangularArbitrageOpportunity>,
+    pub error: Option<String>, // set when the ticker fetch failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<FetchErrorKind>,
+    // The sanitized book the opportunities were found in, for the paper
+    // trader; never serialized or cached.
+    #[serde(skip)]
+    pub tickers: Vec<Ticker>,
+}
+
+/// Why a ticker fetch failed, for metrics and alerting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FetchErrorKind {
+    Timeout,
+    Connect,
+    Status, // the venue answered with an error status
+    Decode,
+    Other,
+}
+
+impl FetchErrorKind {
+    pub fn classify(error: &(dyn std::error::Error + 'static)) -> Self {
+        if let Some(e) = error.downcast_ref::<reqwest::Error>() {
+            if e.is_timeout() {
+                return Self::Timeout;
+            }
+            if e.is_connect() || e.is_request() {
+                return Self::Connect;
+            }
+            if e.is_status() {
+                return Self::Status;
+            }
+            if e.is_decode() {
+                return Self::Decode;
+            }
+        }
+        if error.is::<serde_json::Error>() {
+            return Self::Decode;
+        }
+        // Exchanges report non-success statuses as plain messages.
+        if error.to_string().starts_with("HTTP Error") {
+            return Self::Status;
+        }
+        Self::Other
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::Connect => "connect",
+            Self::Status => "status",
+            Self::Decode => "decode",
+            Self::Other => "other",
+        }
+    }
+}
+
+pub async fn scan_exchange(
+    exchange: &dyn Exchange,
+    config: &Config,
+    min_profit: f64,
+    min_quote_volume: Option<f64>,
+) -> ScanOutcome {
+    scan_exchange_with_balances(exchange, config, min_profit, min_quote_volume, None).await
+}
+
+/// Same as `scan_exchange`, additionally sizing opportunities by `balances`
+/// (free amounts by canonical currency) when they are known.
+pub async fn scan_exchange_with_balances(
+    exchange: &dyn Exchange,
+    config: &Config,
+    min_profit: f64,
+    min_quote_volume: Option<f64>,
+    balances: Option<&HashMap<String, f64>>,
+) -> ScanOutcome {
+    let fetch_start = Instant::now();
+    let fetched = exchange
+        .fetch_tickers()
+        .await
+        .map_err(|e| (e.to_string(), FetchErrorKind::classify(e.as_ref())));
+    let fetch_ms = fetch_start.elapsed().as_millis();
+
+    let tickers = match fetched {
+        Ok(tickers) => tickers,
+        Err((e, kind)) => {
+            return ScanOutcome {
+                exchange: exchange.name().to_string(),
+                total_pairs: 0,
+                rejected: RejectionCounts::default(),
+                fetch_ms,
+                detect_ms: 0,
+                opportunities: Vec::new(),
+                error: Some(e),
+                error_kind: Some(kind),
+                tickers: Vec::new(),
+            };
+        }
+    };
+
+    let detect_start = Instant::now();
+    let (tickers, rejected) = ArbitrageDetector::sanitize_tickers(tickers, min_quote_volume);
+    let mut opportunities = ArbitrageDetector::find_triangular_opportunities(
+        &tickers,
+        exchange.name(),
+        min_profit,
+        exchange.taker_fee(),
+        config.detector.max_results,
+    );
+    if let Some(balances) = balances {
+        ArbitrageDetector::apply_balances(&mut opportunities, balances);
+    }
+
+    ScanOutcome {
+        exchange: exchange.name().to_string(),
+        total_pairs: tickers.len(),
+        rejected,
+        fetch_ms,
+        detect_ms: detect_start.elapsed().as_millis(),
+        opportunities,
+        error: None,
+        error_kind: None,
+        tickers,
+    }
+}
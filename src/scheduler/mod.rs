@@ -0,0 +1,358 @@
+use crate::alerts::AlertEngine;
+use crate::analytics::Analytics;
+use crate::config::{Config, SharedConfig};
+use crate::exchanges::{self, Exchange};
+use crate::export::CsvExporter;
+use crate::funding::Funding;
+use crate::models::{ExchangeScanState, ExchangeScanStatus, TriangularArbitrageOpportunity};
+use crate::paper::PaperTrader;
+use crate::scan_log::ScanLogger;
+use crate::metrics::Metrics;
+use crate::recorder::Recorder;
+use crate::scanner::{self, FetchErrorKind, ScanOutcome};
+use crate::storage::Storage;
+use actix_web::web;
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use std::collections::HashMap;
+use std::sync::RwLock;
+use std::time::Duration;
+
+/// Services every finished scan is recorded into.
+#[derive(Clone)]
+pub struct ScanSinks {
+    pub exporter: web::Data<CsvExporter>,
+    pub scan_logger: web::Data<ScanLogger>,
+    pub analytics: web::Data<Analytics>,
+    pub alert_engine: web::Data<AlertEngine>,
+    pub paper_trader: web::Data<PaperTrader>,
+    pub storage: Option<web::Data<Storage>>,
+    pub metrics: web::Data<Metrics>,
+    pub recorder: web::Data<Recorder>,
+}
+
+impl ScanSinks {
+    pub async fn record(&self, config: &Config, outcome: &ScanOutcome) {
+        self.analytics.record(outcome);
+        self.metrics.record_scan(outcome);
+
+        if config.logging.scan_log_enabled {
+            let outcome = outcome.clone();
+            write_blocking(&self.scan_logger, "Scan log write", move |logger| logger.record(&outcome)).await;
+        }
+
+        if let Some(storage) = &self.storage {
+            let outcome = outcome.clone();
+            write_blocking(storage, "Storage write", move |storage| storage.record_scan(&outcome).map(|_| ())).await;
+        }
+
+        if outcome.error.is_some() {
+            return;
+        }
+
+        if config.export.csv_enabled {
+            let opportunities = outcome.opportunities.clone();
+            write_blocking(&self.exporter, "CSV export", move |exporter| exporter.append(&opportunities)).await;
+        }
+
+        let alerts = self
+            .alert_engine
+            .process(&config.alerts, &outcome.exchange, &outcome.opportunities)
+            .await;
+
+        if let Some(storage) = &self.storage {
+            write_blocking(storage, "Storage write", move |storage| storage.record_alerts(&alerts)).await;
+        }
+    }
+
+    // Fills the exchange's pending paper trade against this round's book,
+    // then opens one for the best qualifying opportunity; the trader skips
+    // it if this exchange still has a trade pending.
+    pub fn paper_trade(&self, config: &Config, exchange: &dyn Exchange, outcome: &ScanOutcome) {
+        if !config.paper.enabled {
+            return;
+        }
+        self.paper_trader.advance(&config.paper, exchange.name(), &outcome.tickers);
+        let candidate = outcome
+            .opportunities
+            .iter()
+            .find(|o| PaperTrader::accepts(&config.paper, o));
+        if let Some(opportunity) = candidate {
+            self.paper_trader
+                .open(&config.paper, exchange.name(), exchange.taker_fee(), opportunity);
+        }
+    }
+}
+
+// The scan log, CSV export and rusqlite all write synchronously, so writes
+// run on the blocking pool rather than on the async workers serving requests.
+async fn write_blocking<T: Send + Sync + 'static>(
+    sink: &web::Data<T>,
+    what: &str,
+    write: impl FnOnce(&T) -> Result<(), Box<dyn std::error::Error>> + Send + 'static,
+) {
+    let sink = sink.clone();
+    let written = web::block(move || write(&sink).map_err(|e| e.to_string())).await;
+    if let Err(e) = written.map_err(|e| e.to_string()).and_then(|result| result) {
+        log::error!("{} failed: {}", what, e);
+    }
+}
+
+/// What one exchange's part of a scan looks for.
+pub struct ScanParams {
+    pub min_profit: f64,
+    pub min_quote_volume: Option<f64>,
+    pub fundable_only: bool, // fail instead of scanning without balances
+}
+
+impl ScanParams {
+    /// What each background round looks for.
+    pub fn background(config: &Config) -> Self {
+        Self {
+            min_profit: config.scanner.min_profit,
+            min_quote_volume: None,
+            fundable_only: false,
+        }
+    }
+
+    /// Whether a scan with these params finds what a background round would.
+    pub fn is_background(&self, config: &Config) -> bool {
+        self.min_profit == config.scanner.min_profit && self.min_quote_volume.is_none() && !self.fundable_only
+    }
+}
+
+/// One exchange's part of a scan. `outcome` is `None` when the exchange was
+/// skipped before fetching.
+#[derive(Clone)]
+pub struct ExchangeScan {
+    pub outcome: Option<ScanOutcome>,
+    pub status: ExchangeScanStatus,
+    pub funded: bool, // opportunities carry `fundable`
+}
+
+// Balances (when funding is on), then fetch and detect, all under the
+// per-exchange timeout.
+pub async fn scan_one(exchange: &dyn Exchange, config: &Config, funding: &Funding, params: &ScanParams) -> ExchangeScan {
+    let mut status = ExchangeScanStatus {
+        exchange_id: exchange.id(),
+        exchange: exchange.name().to_string(),
+        status: ExchangeScanState::Ok,
+        total_pairs: 0,
+        opportunities: 0,
+        fetch_ms: 0,
+        detect_ms: 0,
+        error: None,
+        cached: false,
+        age_ms: 0,
+    };
+
+    let scan = async {
+        let balances = if config.funding.enabled {
+            funding.balances(config, exchange).await
+        } else {
+            Err("funding is disabled; set funding.enabled = true".to_string())
+        };
+        let balances = match balances {
+            Ok(balances) => Some(balances),
+            Err(e) if params.fundable_only => return Err(format!("Balances are unavailable: {}", e)),
+            Err(e) => {
+                if config.funding.enabled {
+                    log::warn!("{} balances unavailable: {}", exchange.name(), e);
+                }
+                None
+            }
+        };
+        let outcome = scanner::scan_exchange_with_balances(
+            exchange,
+            config,
+            params.min_profit,
+            params.min_quote_volume,
+            balances.as_ref(),
+        )
+        .await;
+        Ok((outcome, balances.is_some()))
+    };
+
+    let timeout = Duration::from_millis(config.scanner.fetch_timeout_ms);
+    let (outcome, funded) = match tokio::time::timeout(timeout, scan).await {
+        Ok(Ok(scanned)) => scanned,
+        Ok(Err(e)) => {
+            status.status = ExchangeScanState::Error;
+            status.error = Some(e);
+            return ExchangeScan {
+                outcome: None,
+                status,
+                funded: false,
+            };
+        }
+        Err(_) => {
+            status.status = ExchangeScanState::Timeout;
+            status.error = Some(format!("Timed out after {} ms", timeout.as_millis()));
+            status.fetch_ms = timeout.as_millis();
+            let outcome = ScanOutcome {
+                exchange: exchange.name().to_string(),
+                total_pairs: 0,
+                rejected: Default::default(),
+                fetch_ms: timeout.as_millis(),
+                detect_ms: 0,
+                opportunities: Vec::new(),
+                error: status.error.clone(),
+                error_kind: Some(FetchErrorKind::Timeout),
+                tickers: Vec::new(),
+            };
+            return ExchangeScan {
+                outcome: Some(outcome),
+                status,
+                funded: false,
+            };
+        }
+    };
+
+    status.total_pairs = outcome.total_pairs;
+    status.opportunities = outcome.opportunities.len();
+    status.fetch_ms = outcome.fetch_ms;
+    status.detect_ms = outcome.detect_ms;
+    if let Some(e) = &outcome.error {
+        status.status = ExchangeScanState::Error;
+        status.error = Some(format!("Failed to fetch tickers: {}", e));
+    }
+    ExchangeScan {
+        outcome: Some(outcome),
+        status,
+        funded,
+    }
+}
+
+#[derive(Clone)]
+pub struct CachedScan {
+    pub scan: ExchangeScan,
+    pub scanned_at: DateTime<Utc>,
+}
+
+/// Latest background scan of each exchange, by exchange id, and the
+/// opportunities recent scans handed out, by opportunity id, so an execution
+/// can name one instead of sending it back.
+#[derive(Default)]
+pub struct ScanCache {
+    scans: RwLock<HashMap<u32, CachedScan>>,
+    issued: RwLock<HashMap<String, IssuedOpportunity>>,
+}
+
+struct IssuedOpportunity {
+    exchange_id: u32,
+    issued_at: DateTime<Utc>,
+    opportunity: TriangularArbitrageOpportunity,
+}
+
+impl ScanCache {
+    /// The cached scan of `exchange_id` when it can answer `params`: it was
+    /// scanned with no volume filter and a threshold no higher than
+    /// `params.min_profit`, and isn't older than two intervals plus the
+    /// timeout (a stalled scheduler falls back to live scans).
+    pub fn usable(&self, config: &Config, exchange_id: u32, params: &ScanParams) -> Option<CachedScan> {
+        if !config.scanner.background
+            || params.min_quote_volume.is_some()
+            || params.min_profit < config.scanner.min_profit
+        {
+            return None;
+        }
+        let cached = self.scans.read().unwrap().get(&exchange_id).cloned()?;
+        if (Utc::now() - cached.scanned_at).num_milliseconds() > max_age_ms(config) {
+            return None;
+        }
+        if params.fundable_only && !cached.scan.funded {
+            return None;
+        }
+        Some(cached)
+    }
+
+    /// Caches `scan` as the exchange's latest round. Only scans run with
+    /// `ScanParams::background` belong here.
+    pub fn store(&self, config: &Config, mut scan: ExchangeScan) {
+        if let Some(outcome) = &mut scan.outcome {
+            outcome.tickers = Vec::new();
+        }
+        self.issue(config, &scan);
+        let cached = CachedScan {
+            scan,
+            scanned_at: Utc::now(),
+        };
+        self.scans
+            .write()
+            .unwrap()
+            .insert(cached.scan.status.exchange_id, cached);
+    }
+
+    /// Remembers the opportunities of a scan, background or requested, for
+    /// `opportunity`. Entries expire like cached scans do.
+    pub fn issue(&self, config: &Config, scan: &ExchangeScan) {
+        let now = Utc::now();
+        let mut issued = self.issued.write().unwrap();
+        issued.retain(|_, entry| (now - entry.issued_at).num_milliseconds() <= max_age_ms(config));
+        let Some(outcome) = &scan.outcome else {
+            return;
+        };
+        for opportunity in &outcome.opportunities {
+            issued.insert(
+                opportunity.id.clone(),
+                IssuedOpportunity {
+                    exchange_id: scan.status.exchange_id,
+                    issued_at: now,
+                    opportunity: opportunity.clone(),
+                },
+            );
+        }
+    }
+
+    /// The opportunity `id` from a recent scan of `exchange_id`.
+    pub fn opportunity(&self, config: &Config, exchange_id: u32, id: &str) -> Option<TriangularArbitrageOpportunity> {
+        let issued = self.issued.read().unwrap();
+        let entry = issued.get(id).filter(|entry| entry.exchange_id == exchange_id)?;
+        if (Utc::now() - entry.issued_at).num_milliseconds() > max_age_ms(config) {
+            return None;
+        }
+        Some(entry.opportunity.clone())
+    }
+}
+
+// Two intervals plus the fetch timeout.
+fn max_age_ms(config: &Config) -> i64 {
+    config.scanner.interval_secs as i64 * 2000 + config.scanner.fetch_timeout_ms as i64
+}
+
+/// Scans every enabled exchange each `scanner.interval_secs` into `cache` and
+/// records each scan into `sinks`; these rounds are the only scans that
+/// reach the analytics, alerts, logs, storage and paper trading. Config
+/// reloads apply from the next round; with `scanner.background = false` the
+/// loop idles.
+pub async fn run(shared_config: web::Data<SharedConfig>, sinks: ScanSinks, funding: web::Data<Funding>, cache: web::Data<ScanCache>) {
+    loop {
+        let config = shared_config.current();
+        if config.scanner.background {
+            let exchanges: Vec<Box<dyn Exchange>> = exchanges::build_exchanges(&config, Some(&*sinks.recorder))
+                .into_iter()
+                .filter(|e| e.is_enabled())
+                .collect();
+            let params = ScanParams::background(&config);
+
+            let scans = join_all(
+                exchanges
+                    .iter()
+                    .map(|exchange| scan_one(exchange.as_ref(), &config, &funding, &params)),
+            )
+            .await;
+
+            for (exchange, scan) in exchanges.into_iter().zip(scans) {
+                if let Some(outcome) = &scan.outcome {
+                    sinks.record(&config, outcome).await;
+                    if outcome.error.is_none() {
+                        sinks.paper_trade(&config, exchange.as_ref(), outcome);
+                    }
+                }
+                cache.store(&config, scan);
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(config.scanner.interval_secs)).await;
+    }
+}
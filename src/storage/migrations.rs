@@ -0,0 +1,87 @@
+use rusqlite::Connection;
+
+// Applied in order; `PRAGMA user_version` records how many have run. Append
+// new migrations, never edit shipped ones.
+const MIGRATIONS: &[&str] = &[
+    // 1: scans, opportunities with legs, alert firings
+    "CREATE TABLE scans (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        timestamp TEXT NOT NULL,
+        exchange TEXT NOT NULL,
+        ticker_count INTEGER NOT NULL,
+        rejected_invalid_price INTEGER NOT NULL,
+        rejected_crossed_book INTEGER NOT NULL,
+        rejected_low_volume INTEGER NOT NULL,
+        fetch_ms INTEGER NOT NULL,
+        detect_ms INTEGER NOT NULL,
+        opportunity_count INTEGER NOT NULL,
+        error TEXT
+    );
+    CREATE INDEX scans_timestamp ON scans (timestamp);
+
+    CREATE TABLE opportunities (
+        id TEXT PRIMARY KEY,
+        scan_id INTEGER NOT NULL REFERENCES scans (id) ON DELETE CASCADE,
+        timestamp TEXT NOT NULL,
+        exchange TEXT NOT NULL,
+        path TEXT NOT NULL,
+        pairs TEXT NOT NULL,
+        gross_profit_pct REAL NOT NULL,
+        fees_pct REAL NOT NULL,
+        net_profit_pct REAL NOT NULL,
+        max_notional REAL NOT NULL
+    );
+    CREATE INDEX opportunities_timestamp ON opportunities (timestamp);
+    CREATE INDEX opportunities_exchange_timestamp ON opportunities (exchange COLLATE NOCASE, timestamp);
+    CREATE INDEX opportunities_scan ON opportunities (scan_id);
+
+    CREATE TABLE opportunity_legs (
+        opportunity_id TEXT NOT NULL REFERENCES opportunities (id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        symbol TEXT NOT NULL,
+        venue_symbol TEXT NOT NULL,
+        side TEXT NOT NULL,
+        price REAL NOT NULL,
+        quantity REAL NOT NULL,
+        PRIMARY KEY (opportunity_id, position)
+    );
+
+    CREATE TABLE alert_firings (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        fired_at TEXT NOT NULL,
+        exchange TEXT NOT NULL,
+        path TEXT NOT NULL,
+        pairs TEXT NOT NULL,
+        net_profit_pct REAL NOT NULL,
+        threshold REAL NOT NULL,
+        opportunity_id TEXT NOT NULL,
+        rule_id TEXT,
+        rule_name TEXT
+    );
+    CREATE INDEX alert_firings_fired_at ON alert_firings (fired_at);",
+];
+
+/// Brings the schema up to date, one transaction per migration. A database
+/// written by a newer build is refused rather than guessed at.
+pub fn run(conn: &mut Connection) -> Result<(), Box<dyn std::error::Error>> {
+    let applied: usize = conn.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0))? as usize;
+
+    if applied > MIGRATIONS.len() {
+        return Err(format!(
+            "database schema version {} is newer than this build supports ({})",
+            applied,
+            MIGRATIONS.len()
+        )
+        .into());
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(applied) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", (index + 1) as i64)?;
+        tx.commit()?;
+        log::info!("Applied storage migration {}", index + 1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,593 @@
+use crate::alerts::Alert;
+use crate::models::{OpportunityLeg, RejectionCounts, TradeSide, TriangularArbitrageOpportunity};
+use crate::scanner::ScanOutcome;
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use utoipa::ToSchema;
+
+mod migrations;
+
+/// Filters shared by the history queries. `from` is inclusive, `to` exclusive.
+#[derive(Debug, Clone)]
+pub struct HistoryFilter {
+    pub exchange: Option<String>,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub min_profit: Option<f64>,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredScan {
+    pub id: i64,
+    pub timestamp: DateTime<Utc>,
+    pub exchange: String,
+    pub ticker_count: usize,
+    pub rejected: RejectionCounts,
+    pub fetch_ms: u128,
+    pub detect_ms: u128,
+    pub opportunity_count: usize,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredOpportunity {
+    pub scan_id: i64,
+    #[serde(flatten)]
+    pub opportunity: TriangularArbitrageOpportunity,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct StoredAlert {
+    pub id: i64,
+    pub fired_at: DateTime<Utc>,
+    pub exchange: String,
+    pub path: String,
+    pub pairs: String,
+    pub net_profit_percentage: f64,
+    pub threshold: f64,
+    pub opportunity_id: String,
+    pub rule_id: Option<String>,
+    pub rule_name: Option<String>,
+}
+
+/// SQLite record of scans, their opportunities and legs, and alert firings.
+/// The schema is migrated forward on open.
+pub struct Storage {
+    conn: Mutex<Connection>,
+}
+
+impl Storage {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let conn = Connection::open(path)?;
+        // The server and a terminal session may share the file.
+        conn.busy_timeout(Duration::from_secs(5))?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        Self::with_connection(conn)
+    }
+
+    fn with_connection(mut conn: Connection) -> Result<Self, Box<dyn std::error::Error>> {
+        conn.pragma_update(None, "foreign_keys", "ON")?;
+        migrations::run(&mut conn)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Stores the scan with its opportunities and legs; returns the scan id.
+    pub fn record_scan(&self, outcome: &ScanOutcome) -> Result<i64, Box<dyn std::error::Error>> {
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+
+        tx.execute(
+            "INSERT INTO scans (timestamp, exchange, ticker_count, rejected_invalid_price,
+                rejected_crossed_book, rejected_low_volume, fetch_ms, detect_ms, opportunity_count, error)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            params![
+                timestamp(Utc::now()),
+                outcome.exchange,
+                outcome.total_pairs as i64,
+                outcome.rejected.invalid_price as i64,
+                outcome.rejected.crossed_book as i64,
+                outcome.rejected.low_volume as i64,
+                outcome.fetch_ms as i64,
+                outcome.detect_ms as i64,
+                outcome.opportunities.len() as i64,
+                outcome.error,
+            ],
+        )?;
+        let scan_id = tx.last_insert_rowid();
+
+        {
+            let mut insert_opportunity = tx.prepare(
+                "INSERT INTO opportunities (id, scan_id, timestamp, exchange, path, pairs,
+                    gross_profit_pct, fees_pct, net_profit_pct, max_notional)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+            )?;
+            let mut insert_leg = tx.prepare(
+                "INSERT INTO opportunity_legs (opportunity_id, position, symbol, venue_symbol, side, price, quantity)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            )?;
+
+            for opportunity in &outcome.opportunities {
+                insert_opportunity.execute(params![
+                    opportunity.id,
+                    scan_id,
+                    timestamp(opportunity.timestamp),
+                    opportunity.exchange,
+                    opportunity.path,
+                    opportunity.pairs,
+                    opportunity.gross_profit_percentage,
+                    opportunity.estimated_fees,
+                    opportunity.net_profit_percentage,
+                    opportunity.max_notional,
+                ])?;
+                for (position, leg) in opportunity.legs.iter().enumerate() {
+                    insert_leg.execute(params![
+                        opportunity.id,
+                        position as i64,
+                        leg.symbol,
+                        leg.venue_symbol,
+                        side_name(leg.side),
+                        leg.price,
+                        leg.quantity,
+                    ])?;
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok(scan_id)
+    }
+
+    pub fn record_alerts(&self, alerts: &[Alert]) -> Result<(), Box<dyn std::error::Error>> {
+        if alerts.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = self.conn.lock().unwrap();
+        let tx = conn.transaction()?;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO alert_firings (fired_at, exchange, path, pairs, net_profit_pct, threshold,
+                    opportunity_id, rule_id, rule_name)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+            )?;
+            for alert in alerts {
+                insert.execute(params![
+                    timestamp(alert.fired_at),
+                    alert.opportunity.exchange,
+                    alert.opportunity.path,
+                    alert.opportunity.pairs,
+                    alert.opportunity.net_profit_percentage,
+                    alert.threshold,
+                    alert.opportunity.id,
+                    alert.rule_id,
+                    alert.rule_name,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Opportunities in the window, most profitable first, with their legs.
+    pub fn opportunities(&self, filter: &HistoryFilter) -> Result<Vec<StoredOpportunity>, Box<dyn std::error::Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut statement = conn.prepare(
+            "SELECT id, scan_id, timestamp, exchange, path, pairs, gross_profit_pct, fees_pct,
+                    net_profit_pct, max_notional
+             FROM opportunities
+             WHERE timestamp >= ?1 AND timestamp < ?2
+               AND (?3 IS NULL OR exchange = ?3 COLLATE NOCASE)
+               AND (?4 IS NULL OR net_profit_pct >= ?4)
+             ORDER BY net_profit_pct DESC, timestamp DESC
+             LIMIT ?5",
+        )?;
+        let mut opportunities = statement
+            .query_map(
+                params![
+                    timestamp(filter.from),
+                    timestamp(filter.to),
+                    filter.exchange,
+                    filter.min_profit,
+                    filter.limit as i64,
+                ],
+                |row| {
+                    Ok(StoredOpportunity {
+                        scan_id: row.get(1)?,
+                        opportunity: TriangularArbitrageOpportunity {
+                            id: row.get(0)?,
+                            timestamp: parse_timestamp(&row.get::<_, String>(2)?)?,
+                            exchange: row.get(3)?,
+                            path: row.get(4)?,
+                            pairs: row.get(5)?,
+                            gross_profit_percentage: row.get(6)?,
+                            estimated_fees: row.get(7)?,
+                            net_profit_percentage: row.get(8)?,
+                            max_notional: row.get(9)?,
+                            funded_notional: None,
+                            fundable: None,
+                            legs: Vec::new(),
+                        },
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut legs = load_legs(&conn, &opportunities)?;
+        for stored in &mut opportunities {
+            stored.opportunity.legs = legs.remove(&stored.opportunity.id).unwrap_or_default();
+        }
+
+        Ok(opportunities)
+    }
+
+    /// Scans in the window, newest first. `min_profit` is ignored.
+    pub fn scans(&self, filter: &HistoryFilter) -> Result<Vec<StoredScan>, Box<dyn std::error::Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut statement = conn.prepare(
+            "SELECT id, timestamp, exchange, ticker_count, rejected_invalid_price, rejected_crossed_book,
+                    rejected_low_volume, fetch_ms, detect_ms, opportunity_count, error
+             FROM scans
+             WHERE timestamp >= ?1 AND timestamp < ?2
+               AND (?3 IS NULL OR exchange = ?3 COLLATE NOCASE)
+             ORDER BY timestamp DESC
+             LIMIT ?4",
+        )?;
+        let scans = statement
+            .query_map(
+                params![timestamp(filter.from), timestamp(filter.to), filter.exchange, filter.limit as i64],
+                |row| {
+                    Ok(StoredScan {
+                        id: row.get(0)?,
+                        timestamp: parse_timestamp(&row.get::<_, String>(1)?)?,
+                        exchange: row.get(2)?,
+                        ticker_count: row.get::<_, i64>(3)? as usize,
+                        rejected: RejectionCounts {
+                            invalid_price: row.get::<_, i64>(4)? as usize,
+                            crossed_book: row.get::<_, i64>(5)? as usize,
+                            low_volume: row.get::<_, i64>(6)? as usize,
+                        },
+                        fetch_ms: row.get::<_, i64>(7)? as u128,
+                        detect_ms: row.get::<_, i64>(8)? as u128,
+                        opportunity_count: row.get::<_, i64>(9)? as usize,
+                        error: row.get(10)?,
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(scans)
+    }
+
+    /// Alert firings in the window, newest first.
+    pub fn alerts(&self, filter: &HistoryFilter) -> Result<Vec<StoredAlert>, Box<dyn std::error::Error>> {
+        let conn = self.conn.lock().unwrap();
+
+        let mut statement = conn.prepare(
+            "SELECT id, fired_at, exchange, path, pairs, net_profit_pct, threshold, opportunity_id,
+                    rule_id, rule_name
+             FROM alert_firings
+             WHERE fired_at >= ?1 AND fired_at < ?2
+               AND (?3 IS NULL OR exchange = ?3 COLLATE NOCASE)
+               AND (?4 IS NULL OR net_profit_pct >= ?4)
+             ORDER BY fired_at DESC
+             LIMIT ?5",
+        )?;
+        let alerts = statement
+            .query_map(
+                params![
+                    timestamp(filter.from),
+                    timestamp(filter.to),
+                    filter.exchange,
+                    filter.min_profit,
+                    filter.limit as i64,
+                ],
+                |row| {
+                    Ok(StoredAlert {
+                        id: row.get(0)?,
+                        fired_at: parse_timestamp(&row.get::<_, String>(1)?)?,
+                        exchange: row.get(2)?,
+                        path: row.get(3)?,
+                        pairs: row.get(4)?,
+                        net_profit_percentage: row.get(5)?,
+                        threshold: row.get(6)?,
+                        opportunity_id: row.get(7)?,
+                        rule_id: row.get(8)?,
+                        rule_name: row.get(9)?,
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(alerts)
+    }
+}
+
+fn load_legs(
+    conn: &Connection,
+    opportunities: &[StoredOpportunity],
+) -> Result<HashMap<String, Vec<OpportunityLeg>>, Box<dyn std::error::Error>> {
+    let mut statement = conn.prepare_cached(
+        "SELECT symbol, venue_symbol, side, price, quantity
+         FROM opportunity_legs WHERE opportunity_id = ?1 ORDER BY position",
+    )?;
+
+    let mut legs = HashMap::new();
+    for stored in opportunities {
+        let id = &stored.opportunity.id;
+        let rows = statement
+            .query_map([id], |row| {
+                Ok(OpportunityLeg {
+                    symbol: row.get(0)?,
+                    venue_symbol: row.get(1)?,
+                    side: parse_side(&row.get::<_, String>(2)?)?,
+                    price: row.get(3)?,
+                    quantity: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        legs.insert(id.clone(), rows);
+    }
+
+    Ok(legs)
+}
+
+// Fixed-width UTC timestamps, so text comparison in SQL orders correctly.
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+fn parse_timestamp(value: &str) -> rusqlite::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|at| at.with_timezone(&Utc))
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(0, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+fn side_name(side: TradeSide) -> &'static str {
+    match side {
+        TradeSide::Buy => "buy",
+        TradeSide::Sell => "sell",
+    }
+}
+
+fn parse_side(value: &str) -> rusqlite::Result<TradeSide> {
+    match value {
+        "buy" => Ok(TradeSide::Buy),
+        "sell" => Ok(TradeSide::Sell),
+        other => Err(rusqlite::Error::FromSqlConversionFailure(
+            0,
+            rusqlite::types::Type::Text,
+            format!("unknown trade side `{}`", other).into(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::opportunity;
+    use chrono::TimeZone;
+
+    fn storage() -> Storage {
+        Storage::with_connection(Connection::open_in_memory().unwrap()).unwrap()
+    }
+
+    fn at(minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 12, minute, 0).unwrap()
+    }
+
+    fn window(from: DateTime<Utc>, to: DateTime<Utc>) -> HistoryFilter {
+        HistoryFilter {
+            exchange: None,
+            from,
+            to,
+            min_profit: None,
+            limit: 100,
+        }
+    }
+
+    fn found(exchange: &str, path: &str, net_profit: f64, minute: u32) -> TriangularArbitrageOpportunity {
+        TriangularArbitrageOpportunity {
+            timestamp: at(minute),
+            ..opportunity(exchange, path, net_profit)
+        }
+    }
+
+    fn scan(exchange: &str, opportunities: Vec<TriangularArbitrageOpportunity>) -> ScanOutcome {
+        ScanOutcome {
+            exchange: exchange.to_string(),
+            total_pairs: 120,
+            rejected: RejectionCounts {
+                invalid_price: 1,
+                crossed_book: 2,
+                low_volume: 3,
+            },
+            fetch_ms: 40,
+            detect_ms: 5,
+            opportunities,
+            error: None,
+            error_kind: None,
+            tickers: Vec::new(),
+        }
+    }
+
+    fn paths(stored: &[StoredOpportunity]) -> Vec<&str> {
+        stored.iter().map(|s| s.opportunity.path.as_str()).collect()
+    }
+
+    #[test]
+    fn migrates_an_empty_database_once() {
+        let storage = storage();
+        let mut conn = storage.conn.lock().unwrap();
+        let version = |conn: &Connection| conn.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0)).unwrap();
+        assert_eq!(version(&conn), 1);
+
+        let tables: Vec<String> = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(tables, ["alert_firings", "opportunities", "opportunity_legs", "scans"]);
+
+        migrations::run(&mut conn).unwrap();
+        assert_eq!(version(&conn), 1);
+
+        conn.pragma_update(None, "user_version", 2).unwrap();
+        let error = migrations::run(&mut conn).unwrap_err();
+        assert_eq!(error.to_string(), "database schema version 2 is newer than this build supports (1)");
+    }
+
+    #[test]
+    fn stores_a_scan_with_its_opportunities_and_legs() {
+        let storage = storage();
+        let mut triangle = found("Binance", "USDT → BTC → ETH → USDT", 0.4, 1);
+        triangle.legs = vec![
+            OpportunityLeg {
+                symbol: "BTCUSDT".to_string(),
+                venue_symbol: "BTCUSDT".to_string(),
+                side: TradeSide::Buy,
+                price: 60000.0,
+                quantity: 0.5,
+            },
+            OpportunityLeg {
+                symbol: "ETHBTC".to_string(),
+                venue_symbol: "ETHBTC".to_string(),
+                side: TradeSide::Buy,
+                price: 0.05,
+                quantity: 3.0,
+            },
+            OpportunityLeg {
+                symbol: "ETHUSDT".to_string(),
+                venue_symbol: "ETHUSDT".to_string(),
+                side: TradeSide::Sell,
+                price: 3012.0,
+                quantity: 2.0,
+            },
+        ];
+
+        let before = Utc::now();
+        let scan_id = storage.record_scan(&scan("Binance", vec![triangle.clone()])).unwrap();
+
+        let stored = storage.opportunities(&window(at(0), at(59))).unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].scan_id, scan_id);
+        let opportunity = &stored[0].opportunity;
+        assert_eq!((opportunity.id.as_str(), opportunity.exchange.as_str()), (triangle.id.as_str(), "Binance"));
+        assert_eq!((opportunity.net_profit_percentage, opportunity.max_notional), (0.4, 100.0));
+        assert_eq!(opportunity.timestamp, at(1));
+        let legs: Vec<(&str, TradeSide, f64, f64)> =
+            opportunity.legs.iter().map(|l| (l.symbol.as_str(), l.side, l.price, l.quantity)).collect();
+        assert_eq!(
+            legs,
+            [
+                ("BTCUSDT", TradeSide::Buy, 60000.0, 0.5),
+                ("ETHBTC", TradeSide::Buy, 0.05, 3.0),
+                ("ETHUSDT", TradeSide::Sell, 3012.0, 2.0),
+            ]
+        );
+
+        let scans = storage.scans(&window(before, Utc::now() + chrono::Duration::seconds(1))).unwrap();
+        assert_eq!(scans.len(), 1);
+        let stored_scan = &scans[0];
+        assert_eq!((stored_scan.id, stored_scan.exchange.as_str()), (scan_id, "Binance"));
+        assert_eq!((stored_scan.ticker_count, stored_scan.opportunity_count), (120, 1));
+        assert_eq!(
+            (stored_scan.rejected.invalid_price, stored_scan.rejected.crossed_book, stored_scan.rejected.low_volume),
+            (1, 2, 3)
+        );
+        assert_eq!((stored_scan.fetch_ms, stored_scan.detect_ms, stored_scan.error.as_deref()), (40, 5, None));
+    }
+
+    #[test]
+    fn opportunity_queries_filter_by_window_exchange_and_profit() {
+        let storage = storage();
+        storage
+            .record_scan(&scan(
+                "Binance",
+                vec![found("Binance", "A", 0.2, 0), found("Binance", "B", 0.8, 10), found("Binance", "C", 0.5, 20)],
+            ))
+            .unwrap();
+        storage.record_scan(&scan("Kraken", vec![found("Kraken", "D", 0.9, 10)])).unwrap();
+
+        // Most profitable first; `from` inclusive, `to` exclusive.
+        assert_eq!(paths(&storage.opportunities(&window(at(0), at(59))).unwrap()), ["D", "B", "C", "A"]);
+        assert_eq!(paths(&storage.opportunities(&window(at(0), at(20))).unwrap()), ["D", "B", "A"]);
+        assert_eq!(paths(&storage.opportunities(&window(at(10), at(59))).unwrap()), ["D", "B", "C"]);
+
+        let filter = HistoryFilter {
+            exchange: Some("binance".to_string()),
+            ..window(at(0), at(59))
+        };
+        assert_eq!(paths(&storage.opportunities(&filter).unwrap()), ["B", "C", "A"]);
+
+        let filter = HistoryFilter {
+            min_profit: Some(0.5),
+            ..filter
+        };
+        assert_eq!(paths(&storage.opportunities(&filter).unwrap()), ["B", "C"]);
+
+        let filter = HistoryFilter { limit: 1, ..filter };
+        assert_eq!(paths(&storage.opportunities(&filter).unwrap()), ["B"]);
+    }
+
+    #[test]
+    fn alert_queries_filter_by_window_exchange_and_profit() {
+        let storage = storage();
+        let alert = |exchange: &str, path: &str, net_profit: f64, minute: u32| Alert {
+            threshold: 0.3,
+            fired_at: at(minute),
+            opportunity: found(exchange, path, net_profit, minute),
+            rule_id: Some("rule-1".to_string()),
+            rule_name: None,
+            printed: false,
+        };
+        storage.record_alerts(&[]).unwrap();
+        storage
+            .record_alerts(&[alert("Binance", "A", 0.4, 0), alert("Binance", "B", 0.9, 10), alert("Kraken", "C", 0.6, 20)])
+            .unwrap();
+
+        let firings = |filter: &HistoryFilter| -> Vec<String> {
+            storage.alerts(filter).unwrap().into_iter().map(|a| a.path).collect()
+        };
+        // Newest first.
+        assert_eq!(firings(&window(at(0), at(59))), ["C", "B", "A"]);
+        assert_eq!(firings(&window(at(0), at(20))), ["B", "A"]);
+        assert_eq!(
+            firings(&HistoryFilter {
+                exchange: Some("KRAKEN".to_string()),
+                ..window(at(0), at(59))
+            }),
+            ["C"]
+        );
+        assert_eq!(
+            firings(&HistoryFilter {
+                min_profit: Some(0.5),
+                ..window(at(0), at(59))
+            }),
+            ["C", "B"]
+        );
+
+        let stored = storage.alerts(&window(at(10), at(11))).unwrap();
+        assert_eq!(stored[0].fired_at, at(10));
+        assert_eq!((stored[0].threshold, stored[0].net_profit_percentage), (0.3, 0.9));
+        assert_eq!((stored[0].rule_id.as_deref(), stored[0].rule_name.as_deref()), (Some("rule-1"), None));
+        assert_eq!(stored[0].opportunity_id, "Binance|B");
+    }
+}
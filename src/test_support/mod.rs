@@ -0,0 +1,141 @@
+//! Fixtures shared by the unit tests.
+
+use crate::config::ExchangeConfig;
+use crate::execution::OrderRequest;
+use crate::models::{Ticker, TimeInForce, TradeSide, TriangularArbitrageOpportunity};
+use chrono::Utc;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::thread::JoinHandle;
+
+/// A local HTTP stand-in that answers one request with `reply` and hands
+/// back the raw request, head and body.
+pub fn serve_once(reply: &'static str) -> (String, JoinHandle<String>) {
+    serve_once_with_status("200 OK", reply)
+}
+
+pub fn serve_once_with_status(status: &'static str, reply: &'static str) -> (String, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || answer(&listener, status, reply));
+    (url, handle)
+}
+
+/// Like `serve_once`, for a client that makes several requests: answers them
+/// in turn with `replies` and hands back the raw requests.
+pub fn serve_sequence(replies: &'static [&'static str]) -> (String, JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || replies.iter().map(|reply| answer(&listener, "200 OK", reply)).collect());
+    (url, handle)
+}
+
+// Accepts one connection, reads one request and closes the connection after
+// the reply, so a keep-alive client reconnects for the next one.
+fn answer(listener: &TcpListener, status: &str, reply: &str) -> String {
+    let (stream, _) = listener.accept().unwrap();
+    let mut reader = BufReader::new(stream);
+    let mut request = String::new();
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap();
+            }
+        }
+        request.push_str(&line);
+        if line == "\r\n" {
+            break;
+        }
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).unwrap();
+    request.push_str(&String::from_utf8(body).unwrap());
+
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reply.len(),
+        reply
+    );
+    reader.get_mut().write_all(response.as_bytes()).unwrap();
+    request
+}
+
+/// The JSON body of a request captured by `serve_once`.
+pub fn json_body(request: &str) -> serde_json::Value {
+    let (_, body) = request.split_once("\r\n\r\n").unwrap();
+    serde_json::from_str(body).unwrap()
+}
+
+/// An opportunity on `exchange` along `path` ("A → B → C → A"), without legs.
+pub fn opportunity(exchange: &str, path: &str, net_profit: f64) -> TriangularArbitrageOpportunity {
+    TriangularArbitrageOpportunity {
+        id: format!("{}|{}", exchange, path),
+        exchange: exchange.to_string(),
+        path: path.to_string(),
+        pairs: String::new(),
+        gross_profit_percentage: net_profit + 0.3,
+        estimated_fees: 0.3,
+        net_profit_percentage: net_profit,
+        timestamp: Utc::now(),
+        legs: Vec::new(),
+        max_notional: 100.0,
+        funded_notional: None,
+        fundable: None,
+    }
+}
+
+/// A `base`/`quote` book with `qty` on both sides, spelled the same by the
+/// venue.
+pub fn ticker(base: &str, quote: &str, bid: f64, ask: f64, qty: f64) -> Ticker {
+    Ticker {
+        symbol: format!("{}{}", base, quote),
+        base_currency: base.to_string(),
+        quote_currency: quote.to_string(),
+        bid_price: bid,
+        ask_price: ask,
+        bid_qty: qty,
+        ask_qty: qty,
+        quote_volume: None,
+        timestamp: 0,
+        venue_symbol: format!("{}{}", base, quote),
+        venue_base_currency: base.to_string(),
+        venue_quote_currency: quote.to_string(),
+    }
+}
+
+/// Venue settings pointing at a `serve_once` stand-in, with test keys.
+pub fn credentials(url: String) -> ExchangeConfig {
+    ExchangeConfig {
+        api_url: Some(url),
+        api_key: Some("test-key".to_string()),
+        api_secret: Some("test-secret".to_string()),
+        passphrase: Some("test-passphrase".to_string()),
+        ..ExchangeConfig::default()
+    }
+}
+
+/// A limit order for 2 units at 1.5 with client id "arbtest".
+pub fn order(symbol: &str, side: TradeSide, time_in_force: TimeInForce) -> OrderRequest {
+    OrderRequest {
+        symbol: symbol.to_string(),
+        side,
+        quantity: "2".to_string(),
+        price: "1.5".to_string(),
+        time_in_force,
+        client_order_id: "arbtest".to_string(),
+    }
+}
+
+/// A fresh, empty directory under the system temp dir, unique to this test
+/// run and `name`.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let directory = std::env::temp_dir().join(format!("crypto-arb-{}-{}", std::process::id(), name));
+    let _ = std::fs::remove_dir_all(&directory);
+    std::fs::create_dir_all(&directory).unwrap();
+    directory
+}
@@ -0,0 +1,567 @@
+use crate::analytics::AnalyticsSnapshot;
+use crate::config::Config;
+use crate::exchanges::{build_exchanges, Exchange};
+use crate::models::TriangularArbitrageOpportunity;
+use crate::recorder::Recorder;
+use crate::scanner::{self, ScanOutcome};
+use crate::ui::terminal::{ProcessedScan, TerminalSession};
+use crate::ui::UserInterface;
+use chrono::{DateTime, Local};
+use futures::future::join_all;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const TICK: Duration = Duration::from_millis(100);
+const LATENCY_HISTORY: usize = 20;
+const THRESHOLD_STEP: f64 = 0.05;
+
+/// Full-screen dashboard: scans every enabled exchange each interval and
+/// shows the focused exchange's opportunities next to per-exchange status and
+/// latency. Setup still goes through the dialoguer prompts.
+pub async fn run(config: &Config) -> std::io::Result<()> {
+    UserInterface::show_welcome();
+
+    let recorder = Arc::new(Recorder::new(&config.recorder.directory));
+    let exchanges: Vec<Box<dyn Exchange>> = build_exchanges(config, Some(&recorder))
+        .into_iter()
+        .filter(|e| e.is_enabled())
+        .collect();
+    let focused = match UserInterface::select_exchange(config)
+        .and_then(|id| exchanges.iter().position(|e| e.id() == id))
+    {
+        Some(index) => index,
+        None => {
+            UserInterface::show_error("No exchange selected");
+            return Ok(());
+        }
+    };
+
+    let scan_config = UserInterface::get_advanced_scan_parameters(config);
+    let interval = Duration::from_secs(scan_config.interval.max(1));
+    let session = TerminalSession::new(config, scan_config.clone()).without_console_output();
+    let mut dashboard = Dashboard::new(&exchanges, focused, scan_config.min_profit, scan_config.high_profit_threshold);
+
+    let mut terminal = ratatui::try_init()?;
+    let result = dashboard.run_loop(&mut terminal, &exchanges, &session, config, interval).await;
+    ratatui::restore();
+
+    UserInterface::show_stopped(dashboard.cycle);
+    result
+}
+
+type ScanBatch<'a> = Pin<Box<dyn Future<Output = Vec<(ScanOutcome, ProcessedScan)>> + 'a>>;
+
+// One scan of every exchange, each fed through the session's side effects.
+fn scan_all<'a>(
+    exchanges: &'a [Box<dyn Exchange>],
+    session: &'a TerminalSession<'a>,
+    config: &'a Config,
+    min_profit: f64,
+) -> ScanBatch<'a> {
+    Box::pin(join_all(exchanges.iter().map(move |exchange| async move {
+        let outcome = scanner::scan_exchange(exchange.as_ref(), config, min_profit, None).await;
+        let processed = session.process(&outcome).await;
+        (outcome, processed)
+    })))
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum SortColumn {
+    NetProfit,
+    GrossProfit,
+    Notional,
+    Path,
+}
+
+impl SortColumn {
+    fn next(self) -> Self {
+        match self {
+            SortColumn::NetProfit => SortColumn::GrossProfit,
+            SortColumn::GrossProfit => SortColumn::Notional,
+            SortColumn::Notional => SortColumn::Path,
+            SortColumn::Path => SortColumn::NetProfit,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            SortColumn::NetProfit => "net %",
+            SortColumn::GrossProfit => "gross %",
+            SortColumn::Notional => "notional",
+            SortColumn::Path => "path",
+        }
+    }
+}
+
+enum View {
+    Table,
+    // Pinned copy, so the view doesn't shift when the next scan lands.
+    Detail(Box<TriangularArbitrageOpportunity>),
+}
+
+struct ExchangeStatus {
+    name: String,
+    outcome: Option<ScanOutcome>,
+    scanned_at: Option<DateTime<Local>>,
+    fetch_history: VecDeque<u128>,
+}
+
+struct Dashboard {
+    exchanges: Vec<ExchangeStatus>,
+    focused: usize,
+    min_profit: f64,
+    high_profit_threshold: f64,
+    paused: bool,
+    scanning: bool,
+    sort: SortColumn,
+    descending: bool,
+    table_state: TableState,
+    view: View,
+    cycle: u64,
+    message: Option<String>,
+    analytics: Option<AnalyticsSnapshot>,
+}
+
+impl Dashboard {
+    fn new(exchanges: &[Box<dyn Exchange>], focused: usize, min_profit: f64, high_profit_threshold: f64) -> Self {
+        Self {
+            exchanges: exchanges
+                .iter()
+                .map(|e| ExchangeStatus {
+                    name: e.name().to_string(),
+                    outcome: None,
+                    scanned_at: None,
+                    fetch_history: VecDeque::new(),
+                })
+                .collect(),
+            focused,
+            min_profit,
+            high_profit_threshold,
+            paused: false,
+            scanning: false,
+            sort: SortColumn::NetProfit,
+            descending: true,
+            table_state: TableState::default().with_selected(Some(0)),
+            view: View::Table,
+            cycle: 0,
+            message: None,
+            analytics: None,
+        }
+    }
+
+    async fn run_loop<'a>(
+        &mut self,
+        terminal: &mut DefaultTerminal,
+        exchanges: &'a [Box<dyn Exchange>],
+        session: &'a TerminalSession<'a>,
+        config: &'a Config,
+        interval: Duration,
+    ) -> std::io::Result<()> {
+        let mut next_scan = Instant::now();
+        let mut in_flight: Option<ScanBatch<'a>> = None;
+
+        loop {
+            terminal.draw(|frame| self.render(frame))?;
+
+            if in_flight.is_none() && !self.paused && Instant::now() >= next_scan {
+                in_flight = Some(scan_all(exchanges, session, config, self.min_profit));
+                self.scanning = true;
+            }
+
+            match in_flight.as_mut() {
+                Some(batch) => tokio::select! {
+                    results = batch => {
+                        in_flight = None;
+                        self.apply(results);
+                        self.analytics = session.analytics().map(|a| a.snapshot());
+                        next_scan = Instant::now() + interval;
+                    }
+                    _ = tokio::time::sleep(TICK) => {}
+                },
+                None => tokio::time::sleep(TICK).await,
+            }
+
+            while event::poll(Duration::ZERO)? {
+                if let Event::Key(key) = event::read()? {
+                    if key.kind != KeyEventKind::Press {
+                        continue;
+                    }
+                    let ctrl_c = key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL);
+                    if ctrl_c || !self.handle_key(key.code) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    fn apply(&mut self, results: Vec<(ScanOutcome, ProcessedScan)>) {
+        self.cycle += 1;
+        self.scanning = false;
+
+        for (status, (outcome, processed)) in self.exchanges.iter_mut().zip(results) {
+            if let Some(warning) = processed.warnings.last() {
+                self.message = Some(format!("⚠ {}", warning));
+            }
+            if let Some(alert) = processed.alerts.last() {
+                self.message = Some(alert.summary());
+            }
+
+            status.fetch_history.push_back(outcome.fetch_ms);
+            if status.fetch_history.len() > LATENCY_HISTORY {
+                status.fetch_history.pop_front();
+            }
+            status.scanned_at = Some(Local::now());
+            status.outcome = Some(outcome);
+        }
+
+        self.clamp_selection();
+    }
+
+    /// Returns false when the dashboard should quit.
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        if let View::Detail(_) = self.view {
+            match code {
+                KeyCode::Char('q') => return false,
+                KeyCode::Esc | KeyCode::Backspace | KeyCode::Enter => self.view = View::Table,
+                _ => {}
+            }
+            return true;
+        }
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Char('p') | KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Char('+') | KeyCode::Char('=') => self.min_profit += THRESHOLD_STEP,
+            KeyCode::Char('-') => self.min_profit -= THRESHOLD_STEP,
+            KeyCode::Char('s') => self.sort = self.sort.next(),
+            KeyCode::Char('r') => self.descending = !self.descending,
+            KeyCode::Tab | KeyCode::Right => {
+                self.focused = (self.focused + 1) % self.exchanges.len();
+                self.table_state.select(Some(0));
+            }
+            KeyCode::BackTab | KeyCode::Left => {
+                self.focused = (self.focused + self.exchanges.len() - 1) % self.exchanges.len();
+                self.table_state.select(Some(0));
+            }
+            KeyCode::Down | KeyCode::Char('j') => self.table_state.select_next(),
+            KeyCode::Up | KeyCode::Char('k') => self.table_state.select_previous(),
+            KeyCode::Enter => {
+                if let Some(opportunity) = self
+                    .table_state
+                    .selected()
+                    .and_then(|index| self.visible_opportunities().get(index).copied())
+                {
+                    self.view = View::Detail(Box::new(opportunity.clone()));
+                }
+            }
+            _ => {}
+        }
+
+        self.clamp_selection();
+        true
+    }
+
+    fn clamp_selection(&mut self) {
+        let count = self.visible_opportunities().len();
+        let selected = self.table_state.selected().unwrap_or(0);
+        self.table_state
+            .select(if count == 0 { None } else { Some(selected.min(count - 1)) });
+    }
+
+    /// The focused exchange's last opportunities at or above the current
+    /// threshold, in the chosen order. Raising the threshold filters
+    /// immediately; lowering it takes effect from the next scan.
+    fn visible_opportunities(&self) -> Vec<&TriangularArbitrageOpportunity> {
+        let mut opportunities: Vec<&TriangularArbitrageOpportunity> = self.exchanges[self.focused]
+            .outcome
+            .iter()
+            .flat_map(|o| o.opportunities.iter())
+            .filter(|o| o.net_profit_percentage >= self.min_profit)
+            .collect();
+
+        opportunities.sort_by(|a, b| {
+            let ordering = match self.sort {
+                SortColumn::NetProfit => a.net_profit_percentage.total_cmp(&b.net_profit_percentage),
+                SortColumn::GrossProfit => a.gross_profit_percentage.total_cmp(&b.gross_profit_percentage),
+                SortColumn::Notional => a.max_notional.total_cmp(&b.max_notional),
+                SortColumn::Path => a.path.cmp(&b.path),
+            };
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+
+        opportunities
+    }
+
+    fn render(&mut self, frame: &mut Frame) {
+        let [header, body, footer] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Min(8),
+            Constraint::Length(3),
+        ])
+        .areas(frame.area());
+        let [main, side] = Layout::horizontal([Constraint::Percentage(68), Constraint::Percentage(32)]).areas(body);
+        let [status, latency] = Layout::vertical([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(side);
+
+        self.render_header(frame, header);
+        match &self.view {
+            View::Table => self.render_table(frame, main),
+            View::Detail(opportunity) => render_detail(frame, main, opportunity),
+        }
+        self.render_status(frame, status);
+        self.render_latency(frame, latency);
+        self.render_footer(frame, footer);
+    }
+
+    fn render_header(&self, frame: &mut Frame, area: Rect) {
+        let state = if self.paused {
+            Span::styled("⏸ PAUSED", Style::new().fg(Color::Yellow).bold())
+        } else if self.scanning {
+            Span::styled("🔍 scanning…", Style::new().fg(Color::Cyan))
+        } else {
+            Span::styled("● live", Style::new().fg(Color::Green))
+        };
+
+        let line = Line::from(vec![
+            Span::styled(self.exchanges[self.focused].name.clone(), Style::new().bold()),
+            Span::raw(format!(
+                "   min profit {:.2}%   alert ≥ {:.2}%   sort {} {}   scans {}   ",
+                self.min_profit,
+                self.high_profit_threshold,
+                self.sort.label(),
+                if self.descending { "↓" } else { "↑" },
+                self.cycle,
+            )),
+            state,
+        ]);
+
+        frame.render_widget(
+            Paragraph::new(line).block(Block::bordered().title(" Crypto Triangular Arbitrage Scanner ")),
+            area,
+        );
+    }
+
+    fn render_table(&mut self, frame: &mut Frame, area: Rect) {
+        let rows: Vec<Row> = self
+            .visible_opportunities()
+            .iter()
+            .enumerate()
+            .map(|(index, o)| {
+                let net_style = if self.high_profit_threshold > 0.0 && o.net_profit_percentage >= self.high_profit_threshold {
+                    Style::new().fg(Color::LightGreen).add_modifier(Modifier::BOLD)
+                } else if o.net_profit_percentage >= 0.0 {
+                    Style::new().fg(Color::Green)
+                } else {
+                    Style::new().fg(Color::Red)
+                };
+                Row::new(vec![
+                    Cell::from((index + 1).to_string()),
+                    Cell::from(o.path.clone()),
+                    Cell::from(o.pairs.clone()),
+                    Cell::from(format!("{:.4}", o.gross_profit_percentage)),
+                    Cell::from(format!("{:.4}", o.net_profit_percentage)).style(net_style),
+                    Cell::from(format!("{:.2}", o.max_notional)),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(4),
+                Constraint::Min(24),
+                Constraint::Min(24),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(12),
+            ],
+        )
+        .header(Row::new(vec!["#", "Path", "Pairs", "Gross %", "Net %", "Notional"]).bold().underlined())
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .block(Block::bordered().title(" Opportunities "));
+
+        frame.render_stateful_widget(table, area, &mut self.table_state);
+    }
+
+    fn render_status(&self, frame: &mut Frame, area: Rect) {
+        let rows: Vec<Row> = self
+            .exchanges
+            .iter()
+            .enumerate()
+            .map(|(index, exchange)| {
+                let (state, pairs, opportunities) = match &exchange.outcome {
+                    None => (Span::raw("…"), "-".to_string(), "-".to_string()),
+                    Some(o) if o.error.is_some() => (Span::raw("✗").red(), "-".to_string(), "-".to_string()),
+                    Some(o) => (
+                        Span::raw("✓").green(),
+                        o.total_pairs.to_string(),
+                        o.opportunities.len().to_string(),
+                    ),
+                };
+                let success_rate = self
+                    .analytics
+                    .as_ref()
+                    .and_then(|a| a.exchanges.iter().find(|e| e.exchange == exchange.name))
+                    .map(|e| format!("{:.0}%", e.fetch_success_rate * 100.0))
+                    .unwrap_or_else(|| "-".to_string());
+                let age = exchange
+                    .scanned_at
+                    .map(|at| format!("{}s", (Local::now() - at).num_seconds()))
+                    .unwrap_or_else(|| "-".to_string());
+
+                let row = Row::new(vec![
+                    Cell::from(exchange.name.clone()),
+                    Cell::from(state),
+                    Cell::from(pairs),
+                    Cell::from(opportunities),
+                    Cell::from(success_rate),
+                    Cell::from(age),
+                ]);
+                if index == self.focused {
+                    row.bold()
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(9),
+                Constraint::Length(2),
+                Constraint::Length(6),
+                Constraint::Length(5),
+                Constraint::Length(5),
+                Constraint::Length(5),
+            ],
+        )
+        .header(Row::new(vec!["Exchange", "", "Pairs", "Opps", "OK", "Age"]).underlined())
+        .block(Block::bordered().title(" Exchanges "));
+
+        frame.render_widget(table, area);
+    }
+
+    fn render_latency(&self, frame: &mut Frame, area: Rect) {
+        let rows: Vec<Row> = self
+            .exchanges
+            .iter()
+            .map(|exchange| {
+                let last = exchange.outcome.as_ref();
+                let history = &exchange.fetch_history;
+                let average = (!history.is_empty()).then(|| history.iter().sum::<u128>() / history.len() as u128);
+                let ms = |value: Option<u128>| value.map_or("-".to_string(), |v| v.to_string());
+
+                Row::new(vec![
+                    exchange.name.clone(),
+                    ms(last.map(|o| o.fetch_ms)),
+                    ms(average),
+                    ms(history.iter().copied().max()),
+                    ms(last.map(|o| o.detect_ms)),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Min(9),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(6),
+            ],
+        )
+        .header(Row::new(vec!["Exchange", "Fetch", "Avg", "Max", "Detect"]).underlined())
+        .block(Block::bordered().title(format!(" Latency ms (last {}) ", LATENCY_HISTORY)));
+
+        frame.render_widget(table, area);
+    }
+
+    fn render_footer(&self, frame: &mut Frame, area: Rect) {
+        let keys = match self.view {
+            View::Table => "q quit · p pause · +/- threshold · ←/→ exchange · ↑/↓ select · ⏎ details · s sort · r reverse",
+            View::Detail(_) => "esc back · q quit",
+        };
+        let mut lines = vec![Line::from(keys).dim()];
+        if let Some(message) = &self.message {
+            lines.push(Line::from(message.clone()).yellow());
+        } else if let Some(e) = self.exchanges[self.focused].outcome.as_ref().and_then(|o| o.error.as_ref()) {
+            lines.push(Line::from(format!("❌ {}", e)).red());
+        }
+
+        frame.render_widget(Paragraph::new(lines).block(Block::default()), area);
+    }
+}
+
+fn render_detail(frame: &mut Frame, area: Rect, opportunity: &TriangularArbitrageOpportunity) {
+    let [summary, legs] = Layout::vertical([Constraint::Length(7), Constraint::Min(5)]).areas(area);
+
+    let lines = vec![
+        Line::from(opportunity.path.clone()).bold(),
+        Line::from(format!("Pairs:     {}", opportunity.pairs)),
+        Line::from(format!(
+            "Profit:    gross {:.4}%   fees {:.4}%   net {:.4}%",
+            opportunity.gross_profit_percentage, opportunity.estimated_fees, opportunity.net_profit_percentage
+        )),
+        Line::from(format!("Max size:  {:.6} (path's first currency)", opportunity.max_notional)),
+        Line::from(format!(
+            "Seen:      {}",
+            opportunity.timestamp.with_timezone(&Local).format("%H:%M:%S")
+        ))
+        .dim(),
+    ];
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(format!(" {} ", opportunity.exchange))),
+        summary,
+    );
+
+    // Quantity is the top-of-book size on the side the leg trades against,
+    // i.e. the depth available at that price.
+    let rows: Vec<Row> = opportunity
+        .legs
+        .iter()
+        .enumerate()
+        .map(|(index, leg)| {
+            let side = format!("{:?}", leg.side).to_uppercase();
+            let side = if side == "BUY" { Span::raw(side).green() } else { Span::raw(side).red() };
+            Row::new(vec![
+                Cell::from((index + 1).to_string()),
+                Cell::from(leg.symbol.clone()),
+                Cell::from(side),
+                Cell::from(format!("{:.8}", leg.price)),
+                Cell::from(format!("{:.6}", leg.quantity)),
+                Cell::from(format!("{:.4}", leg.price * leg.quantity)),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),
+            Constraint::Min(12),
+            Constraint::Length(5),
+            Constraint::Length(16),
+            Constraint::Length(14),
+            Constraint::Length(14),
+        ],
+    )
+    .header(Row::new(vec!["#", "Symbol", "Side", "Price", "Depth (qty)", "Depth (quote)"]).underlined())
+    .block(Block::bordered().title(" Legs "));
+
+    frame.render_widget(table, legs);
+}
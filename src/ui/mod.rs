@@ -1,6 +1,14 @@
 use dialoguer::{Select, Input, Confirm};
 use console::Style;
-use crate::exchanges::{Exchange, get_all_exchanges};
+use colored::Colorize;
+use crate::config::Config;
+use crate::exchanges::{Exchange, build_exchanges};
+use crate::analytics::ExchangeAnalytics;
+use crate::models::TriangularArbitrageOpportunity;
+use crate::scanner::ScanOutcome;
+
+pub mod dashboard;
+pub mod terminal;
 
 pub struct UserInterface;
 
@@ -17,8 +25,8 @@ impl UserInterface {
         println!("{}", subtitle_style.apply_to("Select an exchange below to begin scanning...\n"));
     }
     
-    pub fn select_exchange() -> Option<u32> {
-        let exchanges = get_all_exchanges();
+    pub fn select_exchange(config: &Config) -> Option<u32> {
+        let exchanges = build_exchanges(config, None);
         let enabled_exchanges: Vec<&dyn Exchange> = exchanges
             .iter()
             .filter(|e| e.is_enabled())
@@ -47,20 +55,20 @@ impl UserInterface {
         }
     }
     
-    pub fn get_advanced_scan_parameters() -> ScanConfig {
+    pub fn get_advanced_scan_parameters(config: &Config) -> ScanConfig {
         println!("\n=== ADVANCED SCAN CONFIGURATION ===");
         
         let min_profit: f64 = Input::new()
             .with_prompt("Minimum profit percentage to display")
-            .default(0.1)
+            .default(config.detector.default_min_profit)
             .interact()
-            .unwrap_or(0.1);
+            .unwrap_or(config.detector.default_min_profit);
         
         let high_profit_threshold: f64 = Input::new()
             .with_prompt("High profit threshold for notifications (0 for no notifications)")
-            .default(1.0)
+            .default(config.alerts.high_profit_threshold)
             .interact()
-            .unwrap_or(1.0);
+            .unwrap_or(config.alerts.high_profit_threshold);
         
         let enable_sound = if high_profit_threshold > 0.0 {
             Confirm::new()
@@ -130,6 +138,86 @@ impl UserInterface {
         let style = Style::new().red().bold();
         println!("\n{} {}", style.apply_to("❌ Error:"), error);
     }
+    
+    pub fn show_cycle_header(cycle: u64, outcome: &ScanOutcome) {
+        println!(
+            "\n{} {} {}",
+            format!("[{}]", chrono::Local::now().format("%H:%M:%S")).dimmed(),
+            format!("Scan #{} on {}", cycle, outcome.exchange).cyan().bold(),
+            format!(
+                "({} pairs, {} rejected, fetch {}ms, detect {}ms)",
+                outcome.total_pairs,
+                outcome.rejected.total(),
+                outcome.fetch_ms,
+                outcome.detect_ms
+            )
+            .dimmed(),
+        );
+    }
+    
+    pub fn show_opportunities(opportunities: &[TriangularArbitrageOpportunity], high_profit_threshold: f64) {
+        println!(
+            "{}",
+            format!(
+                "{:<4} {:<36} {:<34} {:>10} {:>8} {:>10}",
+                "#", "Path", "Pairs", "Gross %", "Fees %", "Net %"
+            )
+            .bold()
+            .underline()
+        );
+        
+        for (index, opportunity) in opportunities.iter().enumerate() {
+            let net = format!("{:>10.4}", opportunity.net_profit_percentage);
+            let net = if high_profit_threshold > 0.0
+                && opportunity.net_profit_percentage >= high_profit_threshold
+            {
+                net.bright_green().bold()
+            } else if opportunity.net_profit_percentage >= 0.0 {
+                net.green()
+            } else {
+                net.red()
+            };
+            
+            println!(
+                "{:<4} {:<36} {:<34} {:>10.4} {:>8.4} {}",
+                index + 1,
+                opportunity.path,
+                opportunity.pairs,
+                opportunity.gross_profit_percentage,
+                opportunity.estimated_fees,
+                net,
+            );
+        }
+    }
+    
+    pub fn show_analytics(stats: &ExchangeAnalytics, window_secs: i64) {
+        let percent = |value: Option<f64>| value.map_or("-".to_string(), |v| format!("{:.4}%", v));
+        let top_currencies: Vec<String> = stats
+            .top_currencies
+            .iter()
+            .map(|c| format!("{} ({})", c.currency, c.count))
+            .collect();
+        
+        println!("{}", format!("📊 Analytics (last {} min)", window_secs / 60).magenta().bold());
+        println!(
+            "   Opps/min: {:.2}   Mean net: {}   P95 net: {}   Mean lifetime: {}",
+            stats.opportunities_per_minute,
+            percent(stats.mean_net_profit),
+            percent(stats.p95_net_profit),
+            stats.mean_lifetime_secs.map_or("-".to_string(), |v| format!("{:.1}s", v)),
+        );
+        println!(
+            "   Fetch success: {:.0}% of {} scans   Top currencies: {}",
+            stats.fetch_success_rate * 100.0,
+            stats.scans,
+            if top_currencies.is_empty() { "-".to_string() } else { top_currencies.join(", ") },
+        );
+    }
+    
+    pub fn show_stopped(cycles: u64) {
+        let style = Style::new().yellow().bold();
+        println!("\n{}", style.apply_to(format!("🛑 Scanner stopped after {} scan(s)", cycles)));
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -0,0 +1,200 @@
+use crate::alerts::{self, rules::RuleStore, Alert, AlertEngine};
+use crate::analytics::Analytics;
+use crate::config::{AlertConfig, Config};
+use crate::exchanges::{get_exchange_by_id, Exchange};
+use crate::export::CsvExporter;
+use crate::recorder::Recorder;
+use crate::scan_log::ScanLogger;
+use crate::storage::Storage;
+use crate::scanner::{self, ScanOutcome};
+use crate::ui::{ScanConfig, UserInterface};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Interactive terminal scanner: runs the setup prompts, then scans the chosen
+/// exchange every `ScanConfig.interval` seconds until Ctrl+C.
+pub async fn run(config: &Config) -> std::io::Result<()> {
+    UserInterface::show_welcome();
+
+    let recorder = Arc::new(Recorder::new(&config.recorder.directory));
+    let exchange = match UserInterface::select_exchange(config).and_then(|id| get_exchange_by_id(config, id, Some(&recorder))) {
+        Some(exchange) => exchange,
+        None => {
+            UserInterface::show_error("No exchange selected");
+            return Ok(());
+        }
+    };
+
+    let scan_config = UserInterface::get_advanced_scan_parameters(config);
+    UserInterface::show_scanning_message(exchange.name(), &scan_config);
+
+    let session = TerminalSession::new(config, scan_config);
+    let interval = Duration::from_secs(session.scan_config.interval.max(1));
+    let mut cycle: u64 = 0;
+
+    loop {
+        cycle += 1;
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = session.scan_once(exchange.as_ref(), cycle) => {}
+        }
+
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => break,
+            _ = tokio::time::sleep(interval) => {}
+        }
+    }
+
+    UserInterface::show_stopped(cycle);
+    Ok(())
+}
+
+/// Everything a terminal scan cycle writes to, built once from the prompts.
+pub(crate) struct TerminalSession<'a> {
+    config: &'a Config,
+    scan_config: ScanConfig,
+    exporter: Option<CsvExporter>,
+    scan_logger: Option<ScanLogger>,
+    analytics: Option<Analytics>,
+    storage: Option<Storage>,
+    alert_engine: AlertEngine,
+    alert_config: AlertConfig,
+}
+
+impl<'a> TerminalSession<'a> {
+    pub(crate) fn new(config: &'a Config, scan_config: ScanConfig) -> Self {
+        // The prompts override the configured threshold and sound setting; the
+        // webhook sinks still come from the config.
+        let alert_config = AlertConfig {
+            high_profit_threshold: scan_config.high_profit_threshold,
+            enable_sound: scan_config.enable_sound,
+            ..config.alerts.clone()
+        };
+
+        // Rules are managed through the web API; the terminal only reads them.
+        let rules = RuleStore::load(&config.alerts.rules_file).unwrap_or_else(|e| {
+            UserInterface::show_error(&format!("Alert rules not loaded: {}", e));
+            RuleStore::empty(&config.alerts.rules_file)
+        });
+
+        let storage = if config.storage.enabled {
+            Storage::open(&config.storage.path)
+                .map_err(|e| UserInterface::show_error(&format!("Storage not opened: {}", e)))
+                .ok()
+        } else {
+            None
+        };
+
+        Self {
+            config,
+            exporter: scan_config
+                .enable_csv_export
+                .then(|| CsvExporter::new(&config.export.directory)),
+            scan_logger: scan_config
+                .enable_logging
+                .then(|| ScanLogger::new(&config.logging.directory)),
+            analytics: scan_config.enable_analytics.then(Analytics::default),
+            storage,
+            alert_engine: AlertEngine::new(rules),
+            alert_config,
+            scan_config,
+        }
+    }
+
+    /// Keeps alerts off stdout, for the full-screen dashboard.
+    pub(crate) fn without_console_output(mut self) -> Self {
+        self.alert_engine = self.alert_engine.without_console_output();
+        self
+    }
+
+    pub(crate) fn analytics(&self) -> Option<&Analytics> {
+        self.analytics.as_ref()
+    }
+
+    async fn scan_once(&self, exchange: &dyn Exchange, cycle: u64) {
+        let outcome = scanner::scan_exchange(exchange, self.config, self.scan_config.min_profit, None).await;
+        let processed = self.process(&outcome).await;
+
+        for warning in &processed.warnings {
+            UserInterface::show_error(warning);
+        }
+
+        if let Some(e) = &outcome.error {
+            UserInterface::show_error(&format!("Failed to fetch tickers: {}", e));
+            return;
+        }
+
+        UserInterface::show_cycle_header(cycle, &outcome);
+
+        if outcome.opportunities.is_empty() {
+            UserInterface::show_no_opportunities(self.scan_config.min_profit);
+        } else {
+            UserInterface::show_opportunities(&outcome.opportunities, self.scan_config.high_profit_threshold);
+        }
+
+        // The bell sink prints its own alerts.
+        processed.alerts.iter().filter(|a| !a.printed).for_each(alerts::print_alert);
+
+        if let Some(analytics) = &self.analytics {
+            let snapshot = analytics.snapshot();
+            if let Some(stats) = snapshot.exchanges.iter().find(|e| e.exchange == outcome.exchange) {
+                UserInterface::show_analytics(stats, snapshot.window_secs);
+            }
+        }
+    }
+
+    /// Feeds a scan to analytics, the scan log, the CSV export and the alert
+    /// engine, as enabled in the prompts. Write failures come back as
+    /// warnings for the caller to display.
+    pub(crate) async fn process(&self, outcome: &ScanOutcome) -> ProcessedScan {
+        let mut warnings = Vec::new();
+
+        if let Some(analytics) = &self.analytics {
+            analytics.record(outcome);
+        }
+
+        if let Some(scan_logger) = &self.scan_logger {
+            if let Err(e) = scan_logger.record(outcome) {
+                warnings.push(format!("Scan log write failed: {}", e));
+            }
+        }
+
+        if let Some(storage) = &self.storage {
+            if let Err(e) = storage.record_scan(outcome) {
+                warnings.push(format!("Storage write failed: {}", e));
+            }
+        }
+
+        if outcome.error.is_some() {
+            return ProcessedScan {
+                alerts: Vec::new(),
+                warnings,
+            };
+        }
+
+        if let Some(exporter) = &self.exporter {
+            if let Err(e) = exporter.append(&outcome.opportunities) {
+                warnings.push(format!("CSV export failed: {}", e));
+            }
+        }
+
+        let alerts = self
+            .alert_engine
+            .process(&self.alert_config, &outcome.exchange, &outcome.opportunities)
+            .await;
+
+        if let Some(storage) = &self.storage {
+            if let Err(e) = storage.record_alerts(&alerts) {
+                warnings.push(format!("Storage write failed: {}", e));
+            }
+        }
+
+        ProcessedScan { alerts, warnings }
+    }
+}
+
+pub(crate) struct ProcessedScan {
+    pub alerts: Vec<Alert>,
+    pub warnings: Vec<String>,
+}
@@ -12,7 +12,7 @@ class CryptoArbScanner {
 
     async loadExchanges() {
         try {
-            const response = await fetch('/api/exchanges');
+            const response = await fetch('/api/v1/exchanges');
             this.exchanges = await response.json();
             
             const exchangeSelect = document.getElementById('exchange');
@@ -42,6 +42,7 @@ class CryptoArbScanner {
     async scan() {
         const exchangeId = document.getElementById('exchange').value;
         const minProfit = parseFloat(document.getElementById('minProfit').value) || 0.1;
+        const minQuoteVolume = parseFloat(document.getElementById('minQuoteVolume').value) || 0;
 
         if (!exchangeId) {
             this.showError('Please select an exchange');
@@ -52,14 +53,15 @@ class CryptoArbScanner {
         this.hideError();
 
         try {
-            const response = await fetch('/api/scan', {
+            const response = await fetch('/api/v1/scan', {
                 method: 'POST',
                 headers: {
                     'Content-Type': 'application/json',
                 },
                 body: JSON.stringify({
                     exchange_id: parseInt(exchangeId),
-                    min_profit: minProfit
+                    min_profit: minProfit,
+                    min_quote_volume: minQuoteVolume > 0 ? minQuoteVolume : null
                 })
             });
 